@@ -0,0 +1,60 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use rc5_block::{BlockCipher, OperationMode, RC5ControlBlock, Word, rc5_cipher};
+
+const MESSAGE_BYTES: usize = 64 * 1024;
+
+fn rc5_throughput<W: Word>(c: &mut Criterion, name: &str) {
+    let cipher = rc5_cipher::<W>(b"benchmark key 16", 12).unwrap();
+    let message = vec![0x5au8; MESSAGE_BYTES];
+
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Bytes(MESSAGE_BYTES as u64));
+
+    let modes = [
+        ("ECB", OperationMode::ECB),
+        ("CBC", OperationMode::CBC { iv: [W::ZERO; 2] }),
+        (
+            "CTR",
+            OperationMode::CTR {
+                nonce_and_counter: [W::ZERO; 2],
+            },
+        ),
+    ];
+
+    for (mode_name, mode) in modes {
+        group.bench_with_input(BenchmarkId::new("encrypt", mode_name), &mode, |b, mode| {
+            b.iter(|| cipher.encrypt(&message, *mode).unwrap())
+        });
+    }
+
+    let control_block = cipher.control_block();
+    group.bench_function("blocks/round-trip", |b| {
+        b.iter(|| control_block.generate_bytes_stream(&control_block.generate_blocks(&message)))
+    });
+
+    group.finish();
+}
+
+fn throughput(c: &mut Criterion) {
+    rc5_throughput::<u16>(c, "RC5-16/12");
+    rc5_throughput::<u32>(c, "RC5-32/12");
+    rc5_throughput::<u64>(c, "RC5-64/12");
+    rc5_throughput::<u128>(c, "RC5-128/12");
+}
+
+fn key_setup(c: &mut Criterion) {
+    let control_block = RC5ControlBlock::<u32>::new(b"benchmark key 16", 12).unwrap();
+    let (version, s_table) = control_block.export_schedule();
+
+    let mut group = c.benchmark_group("RC5-32/12/key-setup");
+    group.bench_function("expand", |b| {
+        b.iter(|| RC5ControlBlock::<u32>::new(b"benchmark key 16", 12).unwrap())
+    });
+    group.bench_function("from_schedule", |b| {
+        b.iter(|| RC5ControlBlock::<u32>::from_schedule(&version, &s_table).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, throughput, key_setup);
+criterion_main!(benches);
@@ -0,0 +1,143 @@
+use alloc::string::String;
+use core::{array::from_fn, fmt};
+
+use zeroize::Zeroize;
+
+use crate::{BlockCipher, Reason, Version, Word, rc5::RC5Key};
+
+/// # Fixed-round RC5 control block
+///
+/// An RC5 control block whose round count `R` is a compile-time constant. The
+/// expanded key schedule lives inline as arrays instead of a heap `Vec`, so the
+/// round loops of [BlockCipher::encrypt] and [BlockCipher::decrypt] have a known
+/// trip count and no bounds checks, letting the compiler fully unroll them.
+///
+/// It shares the key expansion of [RC5ControlBlock](crate::RC5ControlBlock) and
+/// produces the exact same cipher-text for the same key and rounds. The inline
+/// round keys are wiped on drop, and [Debug](fmt::Debug) only shows the parametric
+/// version and the key fingerprint.
+///
+/// Parameters:
+///
+/// - `W`: The word type (e.g., `u16`, `u32`, `u64`), which must implement the [`Word`] trait.
+/// - `R`: Number of rounds, within `0-255`.
+///
+/// # Example
+///
+/// ```rust
+/// use rc5_block::{BlockCipher, Cipher, OperationMode, RC5_32_12};
+///
+/// let control_block = RC5_32_12::new(b"mykey").unwrap();
+/// assert_eq!(control_block.parametric_version(), "RC5-v1/32/12/5");
+///
+/// let cipher = Cipher::new(control_block);
+/// let ct = cipher.encrypt(b"Secret message", OperationMode::ECB).unwrap();
+/// assert_eq!(cipher.decrypt(&ct, OperationMode::ECB).unwrap(), b"Secret message");
+/// ```
+#[derive(Clone)]
+pub struct RC5Fixed<W: Word, const R: usize> {
+    /// Pre-whitening round keys `S[0]` and `S[1]`.
+    whitening: [W; 2],
+
+    /// Round keys `S[2i]` and `S[2i + 1]` for the rounds `1..=R`.
+    round_keys: [[W; 2]; R],
+
+    /// Length of the raw key in bytes.
+    key_len: usize,
+}
+
+/// RC5-32/12, the nominal 64-bit block profile of RC5.
+pub type RC5_32_12 = RC5Fixed<u32, 12>;
+
+/// RC5-64/16, the nominal 128-bit block profile of RC5.
+pub type RC5_64_16 = RC5Fixed<u64, 16>;
+
+impl<W: Word, const R: usize> RC5Fixed<W, R> {
+    /// Creates a fixed-round control block from a raw key.
+    ///
+    /// Fails with the same reasons as [RC5ControlBlock::new](crate::RC5ControlBlock::new),
+    /// including [Reason::InvalidRounds] if `R` exceeds 255.
+    pub fn new<K>(key: K) -> Result<Self, Reason>
+    where
+        K: AsRef<[u8]>,
+    {
+        // The schedule is only copied into the inline arrays, it does not
+        // need the locked memory of the `mlock` feature.
+        let key_bytes = key.as_ref();
+        RC5Key::<W>::check_raw(key_bytes, R)?;
+        let key = RC5Key::<W>::expand_or_unlocked(key_bytes, 2 * (R + 1));
+        let s_table = key.s_table();
+
+        Ok(Self {
+            whitening: [s_table[0], s_table[1]],
+            round_keys: from_fn(|r| [s_table[2 * r + 2], s_table[2 * r + 3]]),
+            key_len: key.raw_len(),
+        })
+    }
+
+    /// Returns a string describing the parametric RC5 version,
+    /// e.g. `"RC5-v1/32/12/16"`.
+    pub fn parametric_version(&self) -> String {
+        Version::of_word::<W>("RC5", R, self.key_len).version()
+    }
+}
+
+impl<W: Word, const R: usize> Drop for RC5Fixed<W, R> {
+    fn drop(&mut self) {
+        self.whitening.zeroize();
+        self.round_keys.zeroize();
+    }
+}
+
+impl<W: Word, const R: usize> fmt::Debug for RC5Fixed<W, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RC5Fixed")
+            .field("version", &self.parametric_version())
+            .field("fingerprint", &self.key_check_value())
+            .finish()
+    }
+}
+
+impl<W: Word, const R: usize> BlockCipher<W, 2> for RC5Fixed<W, R> {
+    #[inline]
+    fn encrypt(&self, pt: [W; 2]) -> [W; 2] {
+        let [mut word_a, mut word_b] = pt;
+
+        word_a = word_a.wrapping_add(self.whitening[0]);
+        word_b = word_b.wrapping_add(self.whitening[1]);
+
+        for [key_a, key_b] in self.round_keys {
+            word_a = (word_a ^ word_b).rotate_left(word_b).wrapping_add(key_a);
+            word_b = (word_b ^ word_a).rotate_left(word_a).wrapping_add(key_b);
+        }
+
+        [word_a, word_b]
+    }
+
+    #[inline]
+    fn decrypt(&self, ct: [W; 2]) -> [W; 2] {
+        let [mut word_a, mut word_b] = ct;
+
+        for [key_a, key_b] in self.round_keys.into_iter().rev() {
+            word_b = word_b.wrapping_sub(key_b).rotate_right(word_a) ^ word_a;
+            word_a = word_a.wrapping_sub(key_a).rotate_right(word_b) ^ word_b;
+        }
+
+        word_b = word_b.wrapping_sub(self.whitening[1]);
+        word_a = word_a.wrapping_sub(self.whitening[0]);
+
+        [word_a, word_b]
+    }
+
+    fn control_block_version(&self) -> String {
+        self.parametric_version()
+    }
+
+    fn block_size(&self) -> usize {
+        W::BYTES * 2
+    }
+
+    fn word_size(&self) -> usize {
+        W::BYTES
+    }
+}
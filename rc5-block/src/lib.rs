@@ -1,24 +1,53 @@
 //! # RC5-RS Cipher Library
 //!
 //! This crate provides a generic, parametric implementation of the RC5 block cipher,
-//! supporting variable word sizes (`u16`, `u32`, `u64`) and multiple modes of operation
-//! (ECB, CBC, CTR). It includes PKCS#7 padding helpers, IV/nonce generators, and
+//! supporting variable word sizes (`u8`, `u16`, `u32`, `u64`) and multiple modes of operation
+//! (ECB, CBC, CTR). The RC6 block cipher is provided as a second control block sharing
+//! the same modes of operation. It includes PKCS#7 padding helpers, IV/nonce generators, and
 //! convenient parsing of hex‐encoded parameters.
 //!
 //! ## Features
 //!
-//! - Variable word length: `16-bit`, `32-bit`, `64-bit`.
+//! - Variable word length: `8-bit`, `16-bit`, `32-bit`, `64-bit`.
+//! - Non-native word widths up to 256 bits (e.g. RC5-24, RC5-80, RC5-256) through [WideWord].
+//! - RC5 (two words per block) and RC6 (four words per block) control blocks.
+//! - Compile-time round counts with an inline, unrollable key schedule, see [RC5Fixed].
+//! - Multi-block encryption with runtime selected AVX-512/AVX2 kernels for RC5-32/64
+//!   on `x86_64`, see [BlockCipher::encrypt_blocks].
 //! - Various operation modes:
 //!     - ECB
-//!     - CBC
+//!     - CBC, with or without padding (RC5-CBC-Pad and RC5-CBC of RFC 2040)
 //!     - CTR
-//! - Strict padding using PKCS#7 standard.
+//!     - CFB (full-block and CFB-8)
+//!     - OFB
+//!     - PCBC
+//!     - IGE
+//!     - RC5-CTS, CBC with cipher-text stealing (RFC 2040), and ECB with cipher-text stealing
+//!     - EAX authenticated encryption with associated data, see [Cipher::encrypt_aead]
+//!     - CCM (counter with CBC-MAC) authenticated encryption with associated data
+//!     - GCM authenticated encryption and GMAC for RC5-64, see [Cipher::gcm_seal]
+//!     - SIV deterministic authenticated encryption, see [SivCipher::seal_deterministic]
+//!     - OCB authenticated encryption for 64 and 128-bit blocks
+//! - Strict padding using PKCS#7 standard by default, or ANSI X9.23, ISO 10126,
+//!   ISO/IEC 7816-4, zero or no padding chosen per operation, see [Padding] and
+//!   [Cipher::encrypt_with_padding].
+//! - Allocation-free encryption into caller-provided buffers, see [Cipher::encrypt_in_place]
+//!   and [Cipher::encrypt_into].
+//! - Multi-threaded ECB, CTR and CBC decryption with byte-identical output, see
+//!   [Cipher::with_parallelism].
+//! - Key schedules wiped from memory on drop, locked out of swap with the `mlock`
+//!   feature on Linux, and a [Debug](fmt::Debug) output limited to the version and
+//!   the key fingerprint, see [BlockCipher::key_check_value].
 //! - Pseudo-random IV/nonce generation utitlities , see [random_iv], [random_nonce_and_counter].
 //! - Hex‐string parsing for IVs and nonces.
+//! - RustCrypto `cipher` traits (`BlockEncrypt`, `BlockDecrypt`, `KeyInit`) for
+//!   RC5-16/32/64, and an adapter driving the modes of [Cipher] with foreign block
+//!   ciphers, behind the `cipher` feature.
 //!
 //! ## Example
 //!
 //! ```rust
+//! # #[cfg(feature = "std")] {
 //! use rc5_block::{rc5_cipher, OperationMode};
 //!
 //! // Build a 32‐bit word RC5 cipher with 12 rounds:
@@ -33,6 +62,7 @@
 //! // Decrypt using the same IV:
 //! let recovered = cipher.decrypt(&ciphertext, OperationMode::CBC { iv }).unwrap();
 //! assert_eq!(recovered, plaintext);
+//! # }
 //! ```
 //!
 //! # Utilities
@@ -41,6 +71,7 @@
 //! generation and PKCS#7 padding function:
 //!
 //! ```rust
+//! # #[cfg(feature = "std")] {
 //! // generate a pseudo-random iv-block of block size [u32;2]
 //! let iv = rc5_block::random_iv::<u32, 2>();
 //!
@@ -49,33 +80,75 @@
 //! // Note: Higher part of this block conatins nonce and lower part
 //! // contains counter with initial value set to zero.
 //! let nonce_counter = rc5_block::random_nonce_and_counter::<u32, 2>();
+//! # }
 //! ```
+//!
+//! # `no_std` support
+//!
+//! The crate is `#![no_std]` compatible and only requires `alloc` when the default
+//! `std` feature is disabled. [random_iv] and [random_nonce_and_counter] rely on the
+//! thread-local RNG and are only available with `std`, IVs and nonces can still be
+//! drawn from any [rand::Rng] through [Word::random].
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec, vec::Vec};
+use core::{fmt, marker::PhantomData};
 use hex::FromHexError;
-use std::marker::PhantomData;
 use thiserror::Error;
 
 pub use crate::{
-    modes::OperationMode,
+    fixed::{RC5_32_12, RC5_64_16, RC5Fixed},
+    modes::{GcmMode, GcmNonce, OperationMode},
+    padding::{AnsiX923, Iso7816, NoPadding, Padding, Pkcs7, ZeroPadding},
     rc5::RC5ControlBlock,
-    types::{Version, Word},
-    utils::{pkcs7, random_iv, random_nonce_and_counter},
+    rc6::RC6ControlBlock,
+    types::{MAGIC_MAX_BITS, Version, Word, magic_p, magic_q},
+    utils::pkcs7,
+    wide::{U24, U80, U256, WIDE_MAX_BITS, WideBytes, WideWord},
+};
+
+#[cfg(feature = "std")]
+pub use crate::{
+    padding::Iso10126,
+    parallel::Parallelism,
+    utils::{random_iv, random_nonce_and_counter},
 };
 
+#[cfg(feature = "cipher")]
+pub use crate::rustcrypto::BlockCipherAdapter;
+
+mod fixed;
+mod mac;
 mod modes;
+mod padding;
+#[cfg(feature = "std")]
+mod parallel;
 mod rc5;
+mod rc6;
+#[cfg(feature = "cipher")]
+mod rustcrypto;
+mod secret;
+mod simd;
 mod types;
 mod utils;
+mod wide;
 
 #[cfg(test)]
 mod tests;
 
 /// Errors returned by the Cipher as reasons during
 /// cipher operations.
+///
+/// Without the `std` feature, [FromHexError] does not implement the
+/// error trait, so [Reason::ParseHex] carries it without exposing it
+/// as the error source.
 #[derive(Error, Debug)]
 pub enum Reason {
     #[error("[RC5-Error] Word size mis-match")]
     WordSize,
-    #[error("[RC5-Error] Invalid PKCS7 padding shceme")]
+    #[error("[RC5-Error] Invalid padding")]
     Padding,
     #[error("[RC5-Error] RC5 key is too long, supported: {supported:?} max, current: {current:?}")]
     KeyTooLong { current: usize, supported: usize },
@@ -84,11 +157,62 @@ pub enum Reason {
     #[error("[RC5-Error] Rounds out-of-bounds, must be within 0-255, current{0}")]
     InvalidRounds(usize),
     #[error("[RC5-Error] Unable to parse Hex-String {0}")]
-    ParseHex(#[from] FromHexError),
+    ParseHex(#[cfg_attr(feature = "std", from)] FromHexError),
     #[error("[RC5-Error] IV hex string should be equal to block size {0} bytes")]
     IVinvalid(usize),
     #[error("[RC5-Error] Nonce/Counter hex string should be equal to word-size {0} bytes")]
     NonceInvalid(usize),
+    #[error(
+        "[RC5-Error] Buffer too small, required: {required} bytes, available: {available} bytes"
+    )]
+    BufferTooSmall { required: usize, available: usize },
+    #[error("[RC5-Error] Unable to lock the key schedule in memory, errno: {0}")]
+    MemoryLock(i32),
+    #[error("[RC5-Error] Invalid or mis-matched control block version {0}")]
+    InvalidVersion(String),
+    #[error("[RC5-Error] Key schedule should hold {expected} words, current: {current}")]
+    ScheduleLength { expected: usize, current: usize },
+    #[error(
+        "[RC5-Error] CFB segment should be a whole number of bytes up to the block size, current: {0} bits"
+    )]
+    InvalidSegment(usize),
+    #[error(
+        "[RC5-Error] Cipher-text stealing requires at least one block of {required} bytes, current: {current} bytes"
+    )]
+    StealingTooShort { required: usize, current: usize },
+    #[error("[RC5-Error] Authentication failed, the message or its tag were altered")]
+    AuthenticationFailed,
+    #[error("[RC5-Error] Tag length is not supported by the mode of operation, current: {0} bytes")]
+    InvalidTagLength(usize),
+    #[error(
+        "[RC5-Error] CCM length field should be 2 to 8 bytes and leave a nonce in the block, current: {0} bytes"
+    )]
+    InvalidLengthField(usize),
+    #[error("[RC5-Error] Message length does not fit the CCM length field of {0} bytes")]
+    LengthFieldOverflow(usize),
+    #[error("[RC5-Error] Nonce should be at most the block size {max} bytes, current: {current}")]
+    InvalidNonceLength { max: usize, current: usize },
+    #[error("[RC5-Error] Nonce should not be empty")]
+    EmptyNonce,
+    #[error("[RC5-Error] Message exceeds the {0} bytes limit of the mode of operation")]
+    MessageTooLong(u64),
+    #[error("[RC5-Error] SIV key should be made of two halves of equal length, current: {0} bytes")]
+    SivKeyLength(usize),
+    #[error(
+        "[RC5-Error] At most {max} associated data components are supported, current: {current}"
+    )]
+    TooManyComponents { max: usize, current: usize },
+    #[error("[RC5-Error] Block size of {0} bytes is not supported by the construction")]
+    UnsupportedBlockSize(usize),
+    #[error("[RC5-Error] Requires an authenticated mode of operation")]
+    UnauthenticatedMode,
+}
+
+#[cfg(not(feature = "std"))]
+impl From<FromHexError> for Reason {
+    fn from(err: FromHexError) -> Self {
+        Reason::ParseHex(err)
+    }
 }
 
 /// # Cipher
@@ -101,7 +225,7 @@ pub enum Reason {
 ///
 /// - `B`: Control-Block, e.g. [`RC5ControlBlock<W>`].
 /// - `W`: Underlying type which implements a [Word] trait.
-/// - `N`: number of words per block (for RC5, always 2).
+/// - `N`: number of words per block (for RC5, always 2, for RC6, always 4).
 ///
 pub struct Cipher<B, W, const N: usize>
 where
@@ -109,6 +233,8 @@ where
     B: BlockCipher<W, N>,
 {
     block: B,
+    #[cfg(feature = "std")]
+    kernels: Option<parallel::Kernels<B, W, N>>,
     _marker: PhantomData<W>,
 }
 
@@ -130,10 +256,41 @@ where
     pub fn new(block: B) -> Self {
         Self {
             block,
+            #[cfg(feature = "std")]
+            kernels: None,
             _marker: PhantomData,
         }
     }
 
+    /// Pairs this cipher, keyed with the CTR half `K2` of a SIV key, with
+    /// the control block keyed with the S2V half `K1` of RFC 5297 into a
+    /// [SivCipher] for deterministic encryption, see [rc5_siv_cipher].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use rc5_block::{Cipher, RC6ControlBlock};
+    ///
+    /// let mac_block = RC6ControlBlock::<u32>::new("S2V_KEY", 20).unwrap();
+    /// let ctr_block = RC6ControlBlock::<u32>::new("CTR_KEY", 20).unwrap();
+    /// let cipher = Cipher::new(ctr_block).with_siv_key(mac_block);
+    /// ```
+    pub fn with_siv_key(self, mac_block: B) -> SivCipher<B, W, N> {
+        SivCipher {
+            cipher: self,
+            mac_block,
+        }
+    }
+
+    /// Returns the parallelism setting of this cipher, see
+    /// [Cipher::with_parallelism].
+    #[cfg(feature = "std")]
+    pub fn parallelism(&self) -> Parallelism {
+        self.kernels
+            .as_ref()
+            .map_or(Parallelism::Serial, |kernels| kernels.parallelism)
+    }
+
     /// Encrypt plain-text bytes under selected cryptographic operation mode
     /// and returns cipher-text bytes.
     ///
@@ -142,36 +299,87 @@ where
     ///
     /// - `ECB` : Electronic-code-book mode.
     /// - `CBC` : Cipher-block-chain mode.
+    /// - `CBC_NOPAD` : Cipher-block-chain mode without padding.
     /// - `CTR` : Counter mode.
+    /// - `CFB` : Cipher-feedback mode.
+    /// - `OFB` : Output-feedback mode.
+    /// - `PCBC` : Propagating cipher-block-chain mode.
+    /// - `IGE` : Infinite garble extension mode.
+    /// - `CBC_CTS` : Cipher-block-chain mode with cipher-text stealing.
+    /// - `ECB_CTS` : Electronic-code-book mode with cipher-text stealing.
+    /// - `EAX` : Authenticated EAX mode, see [Cipher::encrypt_aead].
+    /// - `CCM` : Authenticated counter with CBC-MAC mode, see [Cipher::encrypt_aead].
+    /// - `OCB` : Authenticated offset codebook mode, see [Cipher::encrypt_aead].
     ///
     /// Encryption might fail for various reasons, either due to padding or etc,
     /// that's why this function is fallible.
     ///
     /// It returns ciphered bytes, or [Reason] of failure as an err.
     pub fn encrypt(&self, pt: &[u8], mode: OperationMode<W, N>) -> Result<Vec<u8>, Reason> {
-        let mut pt = pt.to_vec();
-
-        match mode {
-            modes::OperationMode::ECB => {
-                let bs = self.block.block_size();
-                utils::pkcs7(&mut pt, bs, true)?;
-                let pt_blocks = self.block.generate_blocks(pt);
-                let ct_blocks = modes::ecb_encrypt(&self.block, pt_blocks);
+        self.encrypt_with_padding(pt, mode, &Pkcs7)
+    }
 
-                Ok(self.block.generate_bytes_stream(ct_blocks))
-            }
-            modes::OperationMode::CBC { iv } => {
-                let bs = self.block.block_size();
-                utils::pkcs7(&mut pt, bs, true)?;
-                let pt_blocks = self.block.generate_blocks(pt);
-                let ct_blocks = modes::cbc_encrypt(&self.block, iv, pt_blocks);
+    /// Encrypt plain-text bytes like [Cipher::encrypt], with the given
+    /// [Padding] scheme instead of PKCS#7 for the `ECB`, `CBC`, `PCBC`
+    /// and `IGE` modes. The other modes do not pad.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use rc5_block::{rc5_cipher, Iso7816, OperationMode};
+    ///
+    /// let cipher = rc5_cipher::<u32>(b"mykey", 12).unwrap();
+    ///
+    /// let ct = cipher.encrypt_with_padding(b"HELLO", OperationMode::ECB, &Iso7816).unwrap();
+    /// let pt = cipher.decrypt_with_padding(&ct, OperationMode::ECB, &Iso7816).unwrap();
+    /// assert_eq!(pt, b"HELLO");
+    /// ```
+    pub fn encrypt_with_padding<P>(
+        &self,
+        pt: &[u8],
+        mode: OperationMode<W, N>,
+        padding: &P,
+    ) -> Result<Vec<u8>, Reason>
+    where
+        P: Padding + ?Sized,
+    {
+        self.encrypt_vec(pt, mode, padding, &[])
+    }
 
-                Ok(self.block.generate_bytes_stream(ct_blocks))
-            }
-            modes::OperationMode::CTR { nonce_and_counter } => {
-                Ok(modes::ctr_encrypt(&self.block, nonce_and_counter, &pt))
-            }
-        }
+    /// Encrypt and authenticate plain-text bytes along with associated data
+    /// under an authenticated mode, [OperationMode::EAX], [OperationMode::CCM]
+    /// or [OperationMode::OCB], or [GcmMode] for RC5-64, and returns the
+    /// cipher-text followed by the authentication tag.
+    ///
+    /// The associated data is authenticated but not encrypted, the same
+    /// bytes must be passed to [Cipher::decrypt_aead]. Fails with
+    /// [Reason::UnauthenticatedMode] under a mode that does not authenticate,
+    /// even without associated data.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "std")] {
+    /// use rc5_block::{rc5_cipher, OperationMode, Reason};
+    ///
+    /// let cipher = rc5_cipher::<u32>(b"mykey", 12).unwrap();
+    /// let mode = OperationMode::EAX { nonce: rc5_block::random_iv(), tag_len: 8 };
+    ///
+    /// let mut ct = cipher.encrypt_aead(b"Secret message", b"header", mode).unwrap();
+    /// assert_eq!(cipher.decrypt_aead(&ct, b"header", mode).unwrap(), b"Secret message");
+    ///
+    /// ct[0] ^= 1;
+    /// assert!(matches!(
+    ///     cipher.decrypt_aead(&ct, b"header", mode),
+    ///     Err(Reason::AuthenticationFailed)
+    /// ));
+    /// # }
+    /// ```
+    pub fn encrypt_aead<M>(&self, pt: &[u8], ad: &[u8], mode: M) -> Result<Vec<u8>, Reason>
+    where
+        M: AeadMode<Self>,
+    {
+        mode.seal(self, pt, ad)
     }
 
     /// Decrypt cipher-text bytes under selected cryptographic operation mode
@@ -182,42 +390,429 @@ where
     ///
     /// - `ECB` : Electronic-code-book mode.
     /// - `CBC` : Cipher-block-chain mode.
+    /// - `CBC_NOPAD` : Cipher-block-chain mode without padding.
     /// - `CTR` : Counter mode.
+    /// - `CFB` : Cipher-feedback mode.
+    /// - `OFB` : Output-feedback mode.
+    /// - `PCBC` : Propagating cipher-block-chain mode.
+    /// - `IGE` : Infinite garble extension mode.
+    /// - `CBC_CTS` : Cipher-block-chain mode with cipher-text stealing.
+    /// - `ECB_CTS` : Electronic-code-book mode with cipher-text stealing.
+    /// - `EAX` : Authenticated EAX mode, see [Cipher::decrypt_aead].
+    /// - `CCM` : Authenticated counter with CBC-MAC mode, see [Cipher::decrypt_aead].
+    /// - `OCB` : Authenticated offset codebook mode, see [Cipher::decrypt_aead].
     ///
     /// Decryption might fail for various reasons, either due to padding or etc,
     /// that's why this function is fallible.
     ///
     /// It returns plain bytes, or [Reason] of failure as an err.
     pub fn decrypt(&self, ct: &[u8], mode: OperationMode<W, N>) -> Result<Vec<u8>, Reason> {
-        let ct = ct.to_vec();
+        self.decrypt_with_padding(ct, mode, &Pkcs7)
+    }
 
-        let deciphered_bytes = match mode {
-            OperationMode::ECB => {
-                let ct_blocks = self.block.generate_blocks(ct);
+    /// Decrypt cipher-text bytes like [Cipher::decrypt], removing the given
+    /// [Padding] scheme instead of PKCS#7 for the `ECB`, `CBC`, `PCBC` and
+    /// `IGE` modes.
+    pub fn decrypt_with_padding<P>(
+        &self,
+        ct: &[u8],
+        mode: OperationMode<W, N>,
+        padding: &P,
+    ) -> Result<Vec<u8>, Reason>
+    where
+        P: Padding + ?Sized,
+    {
+        self.decrypt_vec(ct, mode, padding, &[])
+    }
+
+    /// Verify and decrypt cipher-text bytes followed by their authentication
+    /// tag, as produced by [Cipher::encrypt_aead] with the same associated
+    /// data.
+    ///
+    /// Fails with [Reason::AuthenticationFailed] before any plain-text is
+    /// produced if the cipher-text, the tag or the associated data were
+    /// altered.
+    pub fn decrypt_aead<M>(&self, ct: &[u8], ad: &[u8], mode: M) -> Result<Vec<u8>, Reason>
+    where
+        M: AeadMode<Self>,
+    {
+        mode.open(self, ct, ad)
+    }
+
+    /// Length in bytes of the cipher-text produced by encrypting `len` bytes
+    /// of plain-text under the given mode, i.e. the buffer size required by
+    /// [Cipher::encrypt_in_place] and [Cipher::encrypt_into].
+    pub fn encrypted_len(&self, len: usize, mode: OperationMode<W, N>) -> usize {
+        self.encrypted_len_with_padding(len, mode, &Pkcs7)
+    }
+
+    /// Length in bytes of the cipher-text produced by encrypting `len` bytes
+    /// of plain-text under the given mode and [Padding] scheme, i.e. the
+    /// buffer size required by [Cipher::encrypt_in_place_with_padding].
+    pub fn encrypted_len_with_padding<P>(
+        &self,
+        len: usize,
+        mode: OperationMode<W, N>,
+        padding: &P,
+    ) -> usize
+    where
+        P: Padding + ?Sized,
+    {
+        match mode {
+            OperationMode::ECB
+            | OperationMode::CBC { .. }
+            | OperationMode::PCBC { .. }
+            | OperationMode::IGE { .. } => padding.padded_len(len, self.block.block_size()),
+            OperationMode::CTR { .. }
+            | OperationMode::CFB { .. }
+            | OperationMode::OFB { .. }
+            | OperationMode::CBC_NOPAD { .. }
+            | OperationMode::CBC_CTS { .. }
+            | OperationMode::ECB_CTS => len,
+            OperationMode::EAX { tag_len, .. }
+            | OperationMode::CCM { tag_len, .. }
+            | OperationMode::OCB { tag_len, .. } => len.saturating_add(tag_len),
+        }
+    }
+
+    /// Encrypt the first `len` bytes of `buf` in place, without any heap
+    /// allocation, and returns the cipher-text as a sub-slice of `buf`.
+    ///
+    /// Padding is written into the slack of `buf` after the plain-text, so
+    /// `buf` must be at least [Cipher::encrypted_len] bytes long, otherwise
+    /// this fails with [Reason::BufferTooSmall].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use rc5_block::{rc5_cipher, OperationMode};
+    ///
+    /// let cipher = rc5_cipher::<u32>(b"mykey", 12).unwrap();
+    ///
+    /// let mut buf = [0u8; 16];
+    /// buf[..5].copy_from_slice(b"HELLO");
+    ///
+    /// let ct_len = cipher.encrypt_in_place(&mut buf, 5, OperationMode::ECB).unwrap().len();
+    /// let pt = cipher.decrypt_in_place(&mut buf[..ct_len], OperationMode::ECB).unwrap();
+    /// assert_eq!(pt, b"HELLO");
+    /// ```
+    pub fn encrypt_in_place<'a>(
+        &self,
+        buf: &'a mut [u8],
+        len: usize,
+        mode: OperationMode<W, N>,
+    ) -> Result<&'a [u8], Reason> {
+        self.encrypt_in_place_with_padding(buf, len, mode, &Pkcs7)
+    }
+
+    /// Encrypt the first `len` bytes of `buf` in place like
+    /// [Cipher::encrypt_in_place], with the given [Padding] scheme.
+    ///
+    /// `buf` must be at least [Cipher::encrypted_len_with_padding] bytes
+    /// long, otherwise this fails with [Reason::BufferTooSmall].
+    pub fn encrypt_in_place_with_padding<'a, P>(
+        &self,
+        buf: &'a mut [u8],
+        len: usize,
+        mode: OperationMode<W, N>,
+        padding: &P,
+    ) -> Result<&'a [u8], Reason>
+    where
+        P: Padding + ?Sized,
+    {
+        self.encrypt_in_place_inner(buf, len, mode, padding, &[])
+    }
 
-                let bs = self.block.block_size();
-                let pt_blocks = modes::ecb_decrypt(&self.block, ct_blocks);
-                let mut pt_bytes = self.block.generate_bytes_stream(pt_blocks);
-                utils::pkcs7(&mut pt_bytes, bs, false)?;
+    /// Encrypt and authenticate the first `len` bytes of `buf` in place
+    /// like [Cipher::encrypt_aead], the tag is written after the
+    /// cipher-text, so `buf` must be at least [Cipher::encrypted_len]
+    /// bytes long, or `len + tag_len` bytes for [GcmMode].
+    pub fn encrypt_in_place_aead<'a, M>(
+        &self,
+        buf: &'a mut [u8],
+        len: usize,
+        ad: &[u8],
+        mode: M,
+    ) -> Result<&'a [u8], Reason>
+    where
+        M: AeadMode<Self>,
+    {
+        mode.seal_in_place(self, buf, len, ad)
+    }
 
-                pt_bytes
+    fn encrypt_in_place_inner<'a, P>(
+        &self,
+        buf: &'a mut [u8],
+        len: usize,
+        mode: OperationMode<W, N>,
+        padding: &P,
+        ad: &[u8],
+    ) -> Result<&'a [u8], Reason>
+    where
+        P: Padding + ?Sized,
+    {
+        self.check_mode(mode, ad)?;
+
+        let required = self.encrypted_len_with_padding(len, mode, padding);
+        bail!(
+            required > buf.len(),
+            Reason::BufferTooSmall {
+                required,
+                available: buf.len()
+            }
+        );
+
+        let bs = self.block.block_size();
+        let buf = &mut buf[..required];
+
+        match mode {
+            OperationMode::ECB => {
+                padding.pad(buf, len, bs)?;
+                self.ecb_encrypt(buf);
             }
             OperationMode::CBC { iv } => {
-                let ct_blocks = self.block.generate_blocks(ct);
+                padding.pad(buf, len, bs)?;
+                modes::cbc_encrypt(&self.block, iv, buf);
+            }
+            OperationMode::CBC_NOPAD { iv } => {
+                bail!(!len.is_multiple_of(bs), Reason::Padding);
+                modes::cbc_encrypt(&self.block, iv, buf);
+            }
+            OperationMode::PCBC { iv } => {
+                padding.pad(buf, len, bs)?;
+                modes::pcbc_encrypt(&self.block, iv, buf);
+            }
+            OperationMode::IGE { iv1, iv2 } => {
+                padding.pad(buf, len, bs)?;
+                modes::ige_encrypt(&self.block, iv1, iv2, buf);
+            }
+            OperationMode::CTR { nonce_and_counter } => {
+                self.check_counter_range(buf.len())?;
+                self.ctr_apply(nonce_and_counter, buf);
+            }
+            OperationMode::CFB { iv, segment_bits } => {
+                let segment = self.cfb_segment(segment_bits)?;
+                modes::cfb_encrypt(&self.block, iv, segment, buf);
+            }
+            OperationMode::OFB { iv } => {
+                modes::ofb_apply(&self.block, iv, buf);
+            }
+            OperationMode::CBC_CTS { iv } => {
+                self.check_stealing(buf.len())?;
+                modes::cbc_cts_encrypt(&self.block, iv, buf);
+            }
+            OperationMode::ECB_CTS => {
+                self.check_stealing(buf.len())?;
+                modes::ecb_cts_encrypt(&self.block, buf);
+            }
+            OperationMode::EAX { nonce, .. } => {
+                let (ct, tag) = buf.split_at_mut(len);
+                let full_tag = modes::eax_encrypt(&self.block, nonce, ad, ct);
+                utils::block_to_bytes(&full_tag, tag);
+            }
+            OperationMode::CCM {
+                nonce,
+                length_size,
+                tag_len,
+            } => {
+                self.check_length_field(len, length_size)?;
+                let (ct, tag) = buf.split_at_mut(len);
+                let full_tag = modes::ccm_encrypt(&self.block, nonce, length_size, tag_len, ad, ct);
+                utils::block_to_bytes(&full_tag, tag);
+            }
+            OperationMode::OCB {
+                nonce,
+                nonce_len,
+                tag_len,
+            } => {
+                let (ct, tag) = buf.split_at_mut(len);
+                let full_tag = modes::ocb_encrypt(&self.block, nonce, nonce_len, tag_len, ad, ct);
+                utils::block_to_bytes(&full_tag, tag);
+            }
+        }
 
-                let bs = self.block.block_size();
-                let pt_blocks = modes::cbc_decrypt(&self.block, iv, ct_blocks);
-                let mut pt_bytes = self.block.generate_bytes_stream(pt_blocks);
-                utils::pkcs7(&mut pt_bytes, bs, false)?;
+        Ok(buf)
+    }
+
+    /// Decrypt the whole of `buf` in place, without any heap allocation, and
+    /// returns the plain-text as a sub-slice of `buf` with the padding removed.
+    pub fn decrypt_in_place<'a>(
+        &self,
+        buf: &'a mut [u8],
+        mode: OperationMode<W, N>,
+    ) -> Result<&'a [u8], Reason> {
+        self.decrypt_in_place_with_padding(buf, mode, &Pkcs7)
+    }
+
+    /// Decrypt the whole of `buf` in place like [Cipher::decrypt_in_place],
+    /// removing the given [Padding] scheme.
+    pub fn decrypt_in_place_with_padding<'a, P>(
+        &self,
+        buf: &'a mut [u8],
+        mode: OperationMode<W, N>,
+        padding: &P,
+    ) -> Result<&'a [u8], Reason>
+    where
+        P: Padding + ?Sized,
+    {
+        self.decrypt_in_place_inner(buf, mode, padding, &[])
+    }
+
+    /// Verify and decrypt the whole of `buf`, cipher-text followed by its
+    /// tag, in place like [Cipher::decrypt_aead], and returns the
+    /// plain-text as a sub-slice of `buf`. `buf` is left untouched if the
+    /// authentication fails.
+    pub fn decrypt_in_place_aead<'a, M>(
+        &self,
+        buf: &'a mut [u8],
+        ad: &[u8],
+        mode: M,
+    ) -> Result<&'a [u8], Reason>
+    where
+        M: AeadMode<Self>,
+    {
+        mode.open_in_place(self, buf, ad)
+    }
+
+    fn decrypt_in_place_inner<'a, P>(
+        &self,
+        buf: &'a mut [u8],
+        mode: OperationMode<W, N>,
+        padding: &P,
+        ad: &[u8],
+    ) -> Result<&'a [u8], Reason>
+    where
+        P: Padding + ?Sized,
+    {
+        self.check_mode(mode, ad)?;
+        let bs = self.block.block_size();
 
-                pt_bytes
+        let len = match mode {
+            OperationMode::ECB => {
+                bail!(!buf.len().is_multiple_of(bs), Reason::Padding);
+                self.ecb_decrypt(buf);
+                padding.unpad(buf, bs)?
+            }
+            OperationMode::CBC { iv } => {
+                bail!(!buf.len().is_multiple_of(bs), Reason::Padding);
+                self.cbc_decrypt(iv, buf);
+                padding.unpad(buf, bs)?
+            }
+            OperationMode::CBC_NOPAD { iv } => {
+                bail!(!buf.len().is_multiple_of(bs), Reason::Padding);
+                self.cbc_decrypt(iv, buf);
+                buf.len()
+            }
+            OperationMode::PCBC { iv } => {
+                bail!(!buf.len().is_multiple_of(bs), Reason::Padding);
+                modes::pcbc_decrypt(&self.block, iv, buf);
+                padding.unpad(buf, bs)?
+            }
+            OperationMode::IGE { iv1, iv2 } => {
+                bail!(!buf.len().is_multiple_of(bs), Reason::Padding);
+                modes::ige_decrypt(&self.block, iv1, iv2, buf);
+                padding.unpad(buf, bs)?
             }
             OperationMode::CTR { nonce_and_counter } => {
-                modes::ctr_decrypt(&self.block, nonce_and_counter, &ct)
+                self.ctr_apply(nonce_and_counter, buf);
+                buf.len()
+            }
+            OperationMode::CFB { iv, segment_bits } => {
+                let segment = self.cfb_segment(segment_bits)?;
+                modes::cfb_decrypt(&self.block, iv, segment, buf);
+                buf.len()
+            }
+            OperationMode::OFB { iv } => {
+                modes::ofb_apply(&self.block, iv, buf);
+                buf.len()
+            }
+            OperationMode::CBC_CTS { iv } => {
+                self.check_stealing(buf.len())?;
+                modes::cbc_cts_decrypt(&self.block, iv, buf);
+                buf.len()
+            }
+            OperationMode::ECB_CTS => {
+                self.check_stealing(buf.len())?;
+                modes::ecb_cts_decrypt(&self.block, buf);
+                buf.len()
+            }
+            OperationMode::EAX { nonce, tag_len } => {
+                bail!(buf.len() < tag_len, Reason::AuthenticationFailed);
+                let (ct, tag) = buf.split_at_mut(buf.len() - tag_len);
+                modes::eax_decrypt(&self.block, nonce, ad, ct, tag)?;
+                ct.len()
+            }
+            OperationMode::CCM {
+                nonce,
+                length_size,
+                tag_len,
+            } => {
+                bail!(buf.len() < tag_len, Reason::AuthenticationFailed);
+                let (ct, tag) = buf.split_at_mut(buf.len() - tag_len);
+                self.check_length_field(ct.len(), length_size)?;
+                modes::ccm_decrypt(&self.block, nonce, length_size, ad, ct, tag)?;
+                ct.len()
+            }
+            OperationMode::OCB {
+                nonce,
+                nonce_len,
+                tag_len,
+            } => {
+                bail!(buf.len() < tag_len, Reason::AuthenticationFailed);
+                let (ct, tag) = buf.split_at_mut(buf.len() - tag_len);
+                modes::ocb_decrypt(&self.block, nonce, nonce_len, ad, ct, tag)?;
+                ct.len()
             }
         };
 
-        Ok(deciphered_bytes)
+        Ok(&buf[..len])
+    }
+
+    /// Encrypt `pt` into the caller-provided `out` buffer, without any heap
+    /// allocation, and returns the cipher-text as a sub-slice of `out`.
+    ///
+    /// Fails with [Reason::BufferTooSmall] if `out` is shorter than
+    /// [Cipher::encrypted_len].
+    pub fn encrypt_into<'a>(
+        &self,
+        pt: &[u8],
+        out: &'a mut [u8],
+        mode: OperationMode<W, N>,
+    ) -> Result<&'a [u8], Reason> {
+        let required = self.encrypted_len(pt.len(), mode);
+        bail!(
+            required > out.len(),
+            Reason::BufferTooSmall {
+                required,
+                available: out.len()
+            }
+        );
+
+        out[..pt.len()].copy_from_slice(pt);
+        self.encrypt_in_place(out, pt.len(), mode)
+    }
+
+    /// Decrypt `ct` into the caller-provided `out` buffer, without any heap
+    /// allocation, and returns the plain-text as a sub-slice of `out`.
+    ///
+    /// Fails with [Reason::BufferTooSmall] if `out` is shorter than `ct`.
+    pub fn decrypt_into<'a>(
+        &self,
+        ct: &[u8],
+        out: &'a mut [u8],
+        mode: OperationMode<W, N>,
+    ) -> Result<&'a [u8], Reason> {
+        bail!(
+            ct.len() > out.len(),
+            Reason::BufferTooSmall {
+                required: ct.len(),
+                available: out.len()
+            }
+        );
+
+        let out = &mut out[..ct.len()];
+        out.copy_from_slice(ct);
+        self.decrypt_in_place(out, mode)
     }
 
     /// Parse an IV from a hex‐encoded string, validating length = block size.
@@ -228,15 +823,51 @@ where
     where
         V: AsRef<[u8]>,
     {
-        let iv_bytes = hex::decode(iv_hex)?;
+        self.iv_from_bytes(hex::decode(iv_hex)?)
+    }
+
+    /// Build an IV from its raw bytes, validating length = block size.
+    ///
+    /// The bytes are loaded into the IV block like a plain-text block,
+    /// i.e. little-endian words, which is the IV convention of RFC 2040.
+    pub fn iv_from_bytes<V>(&self, iv_bytes: V) -> Result<[W; N], Reason>
+    where
+        V: AsRef<[u8]>,
+    {
+        let iv_bytes = iv_bytes.as_ref();
         let bs = self.control_block().block_size();
         bail!(iv_bytes.len() != bs, Reason::IVinvalid(bs));
 
-        Ok(*self
-            .control_block()
-            .generate_blocks(iv_bytes)
-            .last()
-            .unwrap())
+        Ok(utils::block_from_bytes(iv_bytes))
+    }
+
+    /// Loads a nonce shorter than a block, e.g. the 7 to 13 bytes nonces
+    /// of [OperationMode::CCM] or the nonces of [OperationMode::OCB], into
+    /// the leading bytes of a nonce block, the remaining bytes are zero.
+    ///
+    /// Fails with [Reason::InvalidNonceLength] if the nonce is longer than
+    /// a block.
+    pub fn nonce_from_bytes<V>(&self, nonce_bytes: V) -> Result<[W; N], Reason>
+    where
+        V: AsRef<[u8]>,
+    {
+        let nonce_bytes = nonce_bytes.as_ref();
+        let bs = self.control_block().block_size();
+        bail!(
+            nonce_bytes.len() > bs,
+            Reason::InvalidNonceLength {
+                max: bs,
+                current: nonce_bytes.len()
+            }
+        );
+
+        Ok(core::array::from_fn(|wx| {
+            let mut word = W::Bytes::default();
+            for (jx, b) in word.as_mut().iter_mut().enumerate() {
+                *b = nonce_bytes.get(wx * W::BYTES + jx).copied().unwrap_or(0);
+            }
+            W::from_bytes(word)
+        }))
     }
 
     /// Parses nonce and counter from their respective
@@ -264,7 +895,7 @@ where
 
         Ok(*self
             .control_block()
-            .generate_blocks(nonce_bytes)
+            .generate_blocks(&nonce_bytes)
             .last()
             .unwrap())
     }
@@ -274,6 +905,643 @@ where
     pub fn control_block(&self) -> &B {
         &self.block
     }
+
+    /// Validates a CFB segment size in bits, and returns it in bytes.
+    fn cfb_segment(&self, segment_bits: usize) -> Result<usize, Reason> {
+        bail!(
+            segment_bits == 0
+                || !segment_bits.is_multiple_of(8)
+                || segment_bits > self.block.block_size() * 8,
+            Reason::InvalidSegment(segment_bits)
+        );
+
+        Ok(segment_bits / 8)
+    }
+
+    /// Encrypts `pt` into a new buffer of [Cipher::encrypted_len_with_padding]
+    /// bytes, the mode is validated before the buffer is allocated.
+    fn encrypt_vec<P>(
+        &self,
+        pt: &[u8],
+        mode: OperationMode<W, N>,
+        padding: &P,
+        ad: &[u8],
+    ) -> Result<Vec<u8>, Reason>
+    where
+        P: Padding + ?Sized,
+    {
+        self.check_mode(mode, ad)?;
+
+        let mut ct = vec![0u8; self.encrypted_len_with_padding(pt.len(), mode, padding)];
+        ct[..pt.len()].copy_from_slice(pt);
+        self.encrypt_in_place_inner(&mut ct, pt.len(), mode, padding, ad)?;
+
+        Ok(ct)
+    }
+
+    fn decrypt_vec<P>(
+        &self,
+        ct: &[u8],
+        mode: OperationMode<W, N>,
+        padding: &P,
+        ad: &[u8],
+    ) -> Result<Vec<u8>, Reason>
+    where
+        P: Padding + ?Sized,
+    {
+        let mut pt = ct.to_vec();
+        let len = self
+            .decrypt_in_place_inner(&mut pt, mode, padding, ad)?
+            .len();
+        pt.truncate(len);
+
+        Ok(pt)
+    }
+
+    /// Validates the tag length of the authenticated modes, and that
+    /// associated data is only given to them.
+    fn check_mode(&self, mode: OperationMode<W, N>, ad: &[u8]) -> Result<(), Reason> {
+        bail!(
+            !ad.is_empty() && !mode.is_authenticated(),
+            Reason::UnauthenticatedMode
+        );
+
+        let bs = self.block.block_size();
+        if let OperationMode::EAX { tag_len, .. } = mode {
+            bail!(
+                tag_len == 0 || tag_len > bs,
+                Reason::InvalidTagLength(tag_len)
+            );
+        }
+
+        if let OperationMode::CCM {
+            length_size,
+            tag_len,
+            ..
+        } = mode
+        {
+            bail!(
+                !(4..=16).contains(&tag_len) || tag_len % 2 != 0 || tag_len > bs,
+                Reason::InvalidTagLength(tag_len)
+            );
+            // The length field must leave room for a nonce in the block,
+            // without one every message would share the same counters.
+            const CCM_MIN_NONCE_LEN: usize = 1;
+            bail!(
+                !(2..=8).contains(&length_size) || length_size + CCM_MIN_NONCE_LEN >= bs,
+                Reason::InvalidLengthField(length_size)
+            );
+        }
+
+        if let OperationMode::OCB {
+            nonce_len, tag_len, ..
+        } = mode
+        {
+            bail!(bs != 8 && bs != 16, Reason::UnsupportedBlockSize(bs));
+            bail!(
+                tag_len == 0 || tag_len > bs,
+                Reason::InvalidTagLength(tag_len)
+            );
+            bail!(
+                nonce_len == 0 || nonce_len >= bs,
+                Reason::InvalidNonceLength {
+                    max: bs - 1,
+                    current: nonce_len
+                }
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Validates that a CCM message length fits its length field.
+    fn check_length_field(&self, len: usize, length_size: usize) -> Result<(), Reason> {
+        bail!(
+            length_size < 8 && (len as u64) >> (8 * length_size) != 0,
+            Reason::LengthFieldOverflow(length_size)
+        );
+
+        Ok(())
+    }
+
+    /// Validates that a CTR message to encrypt does not step the 8-bit
+    /// counter word of RC5-8 through all of its values, after which the
+    /// key-stream would repeat. Decryption is left unbounded.
+    fn check_counter_range(&self, len: usize) -> Result<(), Reason> {
+        if W::BYTES == 1 {
+            let limit = 256 * self.block.block_size() as u64;
+            bail!(len as u64 > limit, Reason::MessageTooLong(limit));
+        }
+
+        Ok(())
+    }
+
+    /// Validates that a cipher-text stealing input holds at least one block.
+    fn check_stealing(&self, len: usize) -> Result<(), Reason> {
+        let bs = self.block.block_size();
+        bail!(
+            len < bs,
+            Reason::StealingTooShort {
+                required: bs,
+                current: len
+            }
+        );
+
+        Ok(())
+    }
+
+    // The block parallel modes run on the threads of the parallelism
+    // setting once one is set with `std`, and serially otherwise.
+
+    fn ecb_encrypt(&self, buf: &mut [u8]) {
+        #[cfg(feature = "std")]
+        if let Some(kernels) = &self.kernels {
+            return (kernels.ecb_encrypt)(&self.block, buf, kernels.parallelism);
+        }
+        modes::ecb_encrypt(&self.block, buf);
+    }
+
+    fn ecb_decrypt(&self, buf: &mut [u8]) {
+        #[cfg(feature = "std")]
+        if let Some(kernels) = &self.kernels {
+            return (kernels.ecb_decrypt)(&self.block, buf, kernels.parallelism);
+        }
+        modes::ecb_decrypt(&self.block, buf);
+    }
+
+    fn cbc_decrypt(&self, iv: [W; N], buf: &mut [u8]) {
+        #[cfg(feature = "std")]
+        if let Some(kernels) = &self.kernels {
+            return (kernels.cbc_decrypt)(&self.block, iv, buf, kernels.parallelism);
+        }
+        modes::cbc_decrypt(&self.block, iv, buf);
+    }
+
+    fn ctr_apply(&self, nonce_and_counter: [W; N], buf: &mut [u8]) {
+        #[cfg(feature = "std")]
+        if let Some(kernels) = &self.kernels {
+            return (kernels.ctr_apply)(&self.block, nonce_and_counter, buf, kernels.parallelism);
+        }
+        modes::ctr_apply(&self.block, nonce_and_counter, buf);
+    }
+}
+
+/// Multi-threading, for the control blocks that can be shared between
+/// threads.
+#[cfg(feature = "std")]
+impl<B, W, const N: usize> Cipher<B, W, N>
+where
+    W: Word,
+    B: BlockCipher<W, N> + Sync,
+{
+    /// Sets the number of threads used by ECB, CTR and CBC decryption,
+    /// the output is byte-identical to the serial path. Defaults to
+    /// [Parallelism::Serial].
+    ///
+    /// Only control blocks that are `Sync` can be shared between threads,
+    /// the others always run serially.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use rc5_block::{rc5_cipher, OperationMode, Parallelism};
+    ///
+    /// let cipher = rc5_cipher::<u32>(b"mykey", 12)
+    ///     .unwrap()
+    ///     .with_parallelism(Parallelism::Auto);
+    ///
+    /// let pt = vec![0x5a; 1 << 20];
+    /// let ct = cipher.encrypt(&pt, OperationMode::ECB).unwrap();
+    /// assert_eq!(cipher.decrypt(&ct, OperationMode::ECB).unwrap(), pt);
+    /// ```
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.kernels = Some(parallel::Kernels::new(parallelism));
+        self
+    }
+}
+
+/// GCM and GMAC, for the 128-bit blocks of RC5-64 only.
+impl Cipher<RC5ControlBlock<u64>, u64, 2> {
+    /// Derives the [GcmNonce] of [Cipher::gcm_seal] and [Cipher::gmac]
+    /// from nonce bytes of any non-zero length. 96-bit nonces are
+    /// recommended, others are hashed with GHASH under the key of this
+    /// cipher.
+    pub fn gcm_nonce<V>(&self, nonce_bytes: V) -> Result<GcmNonce, Reason>
+    where
+        V: AsRef<[u8]>,
+    {
+        let nonce_bytes = nonce_bytes.as_ref();
+        bail!(nonce_bytes.is_empty(), Reason::EmptyNonce);
+
+        Ok(modes::gcm_nonce(&self.block, nonce_bytes))
+    }
+
+    /// Encrypt and authenticate plain-text bytes along with associated data
+    /// in Galois/counter mode (NIST SP 800-38D), and returns the
+    /// cipher-text followed by a tag of `tag_len` bytes, 16, 15, 14, 13 or
+    /// 12, or 8 and 4 for protocols that bound the message lengths.
+    ///
+    /// The same as [Cipher::encrypt_aead] with a [GcmMode].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use rc5_block::rc5_cipher;
+    ///
+    /// let cipher = rc5_cipher::<u64>(b"mykey", 16).unwrap();
+    /// let nonce = cipher.gcm_nonce(b"96-bit nonce").unwrap();
+    ///
+    /// let ct = cipher.gcm_seal(nonce, b"Secret message", b"header", 16).unwrap();
+    /// let pt = cipher.gcm_open(nonce, &ct, b"header", 16).unwrap();
+    /// assert_eq!(pt, b"Secret message");
+    /// ```
+    ///
+    /// Only RC5-64 has GCM, neither the 64-bit blocks of the other word
+    /// sizes nor the 128-bit blocks of RC6-32 can be used with it:
+    ///
+    /// ```rust,compile_fail
+    /// use rc5_block::{rc5_cipher, rc6_cipher};
+    ///
+    /// let nonce = rc5_cipher::<u64>(b"mykey", 16).unwrap().gcm_nonce(b"96-bit nonce").unwrap();
+    /// let cipher = rc6_cipher::<u32>(b"mykey", 20).unwrap();
+    /// let ct = cipher.gcm_seal(nonce, b"Secret message", b"header", 16);
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// use rc5_block::rc5_cipher;
+    ///
+    /// let nonce = rc5_cipher::<u64>(b"mykey", 16).unwrap().gcm_nonce(b"96-bit nonce").unwrap();
+    /// let cipher = rc5_cipher::<u32>(b"mykey", 12).unwrap();
+    /// let ct = cipher.gcm_seal(nonce, b"Secret message", b"header", 16);
+    /// ```
+    pub fn gcm_seal(
+        &self,
+        nonce: GcmNonce,
+        pt: &[u8],
+        ad: &[u8],
+        tag_len: usize,
+    ) -> Result<Vec<u8>, Reason> {
+        let mut buf = vec![0u8; pt.len().saturating_add(tag_len)];
+        buf[..pt.len()].copy_from_slice(pt);
+        self.gcm_seal_in_place(nonce, &mut buf, pt.len(), ad, tag_len)?;
+
+        Ok(buf)
+    }
+
+    /// Verify and decrypt cipher-text bytes followed by their tag of
+    /// `tag_len` bytes, as produced by [Cipher::gcm_seal] with the same
+    /// nonce and associated data.
+    ///
+    /// Fails with [Reason::AuthenticationFailed] before any plain-text is
+    /// produced if the cipher-text, the tag or the associated data were
+    /// altered.
+    pub fn gcm_open(
+        &self,
+        nonce: GcmNonce,
+        ct: &[u8],
+        ad: &[u8],
+        tag_len: usize,
+    ) -> Result<Vec<u8>, Reason> {
+        let mut buf = ct.to_vec();
+        let len = self.gcm_open_in_place(nonce, &mut buf, ad, tag_len)?.len();
+        buf.truncate(len);
+
+        Ok(buf)
+    }
+
+    /// Encrypt the first `len` bytes of `buf` in place like
+    /// [Cipher::gcm_seal], and returns the cipher-text followed by its tag
+    /// as a sub-slice of `buf`, which must be at least `len + tag_len`
+    /// bytes long.
+    pub fn gcm_seal_in_place<'a>(
+        &self,
+        nonce: GcmNonce,
+        buf: &'a mut [u8],
+        len: usize,
+        ad: &[u8],
+        tag_len: usize,
+    ) -> Result<&'a [u8], Reason> {
+        check_gcm_tag_len(tag_len)?;
+        check_gcm_len(len)?;
+
+        let required = len + tag_len;
+        bail!(
+            required > buf.len(),
+            Reason::BufferTooSmall {
+                required,
+                available: buf.len()
+            }
+        );
+
+        let buf = &mut buf[..required];
+        let (ct, tag) = buf.split_at_mut(len);
+        let full_tag = modes::gcm_encrypt(&self.block, nonce, ad, ct);
+        utils::block_to_bytes(&full_tag, tag);
+
+        Ok(buf)
+    }
+
+    /// Verify and decrypt the whole of `buf`, cipher-text followed by its
+    /// tag, in place like [Cipher::gcm_open], and returns the plain-text as
+    /// a sub-slice of `buf`. `buf` is left untouched if the authentication
+    /// fails.
+    pub fn gcm_open_in_place<'a>(
+        &self,
+        nonce: GcmNonce,
+        buf: &'a mut [u8],
+        ad: &[u8],
+        tag_len: usize,
+    ) -> Result<&'a [u8], Reason> {
+        check_gcm_tag_len(tag_len)?;
+        bail!(buf.len() < tag_len, Reason::AuthenticationFailed);
+
+        let (ct, tag) = buf.split_at_mut(buf.len() - tag_len);
+        check_gcm_len(ct.len())?;
+        modes::gcm_decrypt(&self.block, nonce, ad, ct, tag)?;
+
+        Ok(ct)
+    }
+
+    /// Computes the GMAC of `ad`, i.e. the GCM tag of an empty message
+    /// with `ad` as associated data. The tag may be truncated to the
+    /// lengths allowed by [Cipher::gcm_seal].
+    pub fn gmac(&self, nonce: GcmNonce, ad: &[u8]) -> [u8; 16] {
+        let mut tag = [0u8; 16];
+        let full_tag = modes::gcm_encrypt(&self.block, nonce, ad, &mut []);
+        utils::block_to_bytes(&full_tag, &mut tag);
+
+        tag
+    }
+
+    /// Verifies a GMAC tag of `ad` in constant time, possibly truncated to
+    /// the lengths allowed by [Cipher::gcm_seal].
+    ///
+    /// Fails with [Reason::InvalidTagLength] for other tag lengths and with
+    /// [Reason::AuthenticationFailed] if the tag does not match.
+    pub fn verify_gmac(&self, nonce: GcmNonce, ad: &[u8], tag: &[u8]) -> Result<(), Reason> {
+        check_gcm_tag_len(tag.len())?;
+        modes::gcm_decrypt(&self.block, nonce, ad, &mut [], tag)
+    }
+}
+
+/// Validates a GCM tag length, the 128 to 96-bit tags of NIST SP 800-38D,
+/// and the 64 and 32-bit tags of its appendix C.
+fn check_gcm_tag_len(tag_len: usize) -> Result<(), Reason> {
+    bail!(
+        !matches!(tag_len, 4 | 8 | 12..=16),
+        Reason::InvalidTagLength(tag_len)
+    );
+
+    Ok(())
+}
+
+/// Validates that a GCM plain-text stays within the 2^39 - 256 bits
+/// limit of NIST SP 800-38D.
+fn check_gcm_len(len: usize) -> Result<(), Reason> {
+    const GCM_MAX_LEN: u64 = (1 << 36) - 32;
+    bail!(
+        len as u64 > GCM_MAX_LEN,
+        Reason::MessageTooLong(GCM_MAX_LEN)
+    );
+
+    Ok(())
+}
+
+/// A mode of operation accepted by the authenticated methods of a cipher
+/// `C`, e.g. [Cipher::encrypt_aead]: every [OperationMode] of the cipher,
+/// and [GcmMode] for the `Cipher<RC5ControlBlock<u64>, u64, 2>` of RC5-64
+/// only.
+///
+/// ```rust,compile_fail
+/// use rc5_block::{rc5_cipher, GcmMode};
+///
+/// let nonce = rc5_cipher::<u64>(b"mykey", 16).unwrap().gcm_nonce(b"96-bit nonce").unwrap();
+/// let cipher = rc5_cipher::<u32>(b"mykey", 12).unwrap();
+/// let ct = cipher.encrypt_aead(b"Secret message", b"header", GcmMode { nonce, tag_len: 16 });
+/// ```
+pub trait AeadMode<C>: Copy {
+    /// Encrypts and authenticates `pt` and `ad` into a new buffer, see
+    /// [Cipher::encrypt_aead].
+    fn seal(self, cipher: &C, pt: &[u8], ad: &[u8]) -> Result<Vec<u8>, Reason>;
+
+    /// Verifies and decrypts `ct` and `ad` into a new buffer, see
+    /// [Cipher::decrypt_aead].
+    fn open(self, cipher: &C, ct: &[u8], ad: &[u8]) -> Result<Vec<u8>, Reason>;
+
+    /// Encrypts and authenticates the first `len` bytes of `buf` in place,
+    /// see [Cipher::encrypt_in_place_aead].
+    fn seal_in_place<'a>(
+        self,
+        cipher: &C,
+        buf: &'a mut [u8],
+        len: usize,
+        ad: &[u8],
+    ) -> Result<&'a [u8], Reason>;
+
+    /// Verifies and decrypts the whole of `buf` in place, see
+    /// [Cipher::decrypt_in_place_aead].
+    fn open_in_place<'a>(
+        self,
+        cipher: &C,
+        buf: &'a mut [u8],
+        ad: &[u8],
+    ) -> Result<&'a [u8], Reason>;
+}
+
+impl<B, W, const N: usize> AeadMode<Cipher<B, W, N>> for OperationMode<W, N>
+where
+    W: Word,
+    B: BlockCipher<W, N>,
+{
+    fn seal(self, cipher: &Cipher<B, W, N>, pt: &[u8], ad: &[u8]) -> Result<Vec<u8>, Reason> {
+        bail!(!self.is_authenticated(), Reason::UnauthenticatedMode);
+        cipher.encrypt_vec(pt, self, &Pkcs7, ad)
+    }
+
+    fn open(self, cipher: &Cipher<B, W, N>, ct: &[u8], ad: &[u8]) -> Result<Vec<u8>, Reason> {
+        bail!(!self.is_authenticated(), Reason::UnauthenticatedMode);
+        cipher.decrypt_vec(ct, self, &Pkcs7, ad)
+    }
+
+    fn seal_in_place<'a>(
+        self,
+        cipher: &Cipher<B, W, N>,
+        buf: &'a mut [u8],
+        len: usize,
+        ad: &[u8],
+    ) -> Result<&'a [u8], Reason> {
+        bail!(!self.is_authenticated(), Reason::UnauthenticatedMode);
+        cipher.encrypt_in_place_inner(buf, len, self, &Pkcs7, ad)
+    }
+
+    fn open_in_place<'a>(
+        self,
+        cipher: &Cipher<B, W, N>,
+        buf: &'a mut [u8],
+        ad: &[u8],
+    ) -> Result<&'a [u8], Reason> {
+        bail!(!self.is_authenticated(), Reason::UnauthenticatedMode);
+        cipher.decrypt_in_place_inner(buf, self, &Pkcs7, ad)
+    }
+}
+
+impl AeadMode<RC5Cipher<u64>> for GcmMode {
+    fn seal(self, cipher: &RC5Cipher<u64>, pt: &[u8], ad: &[u8]) -> Result<Vec<u8>, Reason> {
+        cipher.gcm_seal(self.nonce, pt, ad, self.tag_len)
+    }
+
+    fn open(self, cipher: &RC5Cipher<u64>, ct: &[u8], ad: &[u8]) -> Result<Vec<u8>, Reason> {
+        cipher.gcm_open(self.nonce, ct, ad, self.tag_len)
+    }
+
+    fn seal_in_place<'a>(
+        self,
+        cipher: &RC5Cipher<u64>,
+        buf: &'a mut [u8],
+        len: usize,
+        ad: &[u8],
+    ) -> Result<&'a [u8], Reason> {
+        cipher.gcm_seal_in_place(self.nonce, buf, len, ad, self.tag_len)
+    }
+
+    fn open_in_place<'a>(
+        self,
+        cipher: &RC5Cipher<u64>,
+        buf: &'a mut [u8],
+        ad: &[u8],
+    ) -> Result<&'a [u8], Reason> {
+        cipher.gcm_open_in_place(self.nonce, buf, ad, self.tag_len)
+    }
+}
+
+/// Only shows the control block version and its key fingerprint, see
+/// [BlockCipher::key_check_value].
+impl<B, W, const N: usize> fmt::Debug for Cipher<B, W, N>
+where
+    W: Word,
+    B: BlockCipher<W, N>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cipher")
+            .field("version", &self.block.control_block_version())
+            .field("fingerprint", &self.block.key_check_value())
+            .finish()
+    }
+}
+
+/// # SivCipher
+///
+/// Deterministic authenticated encryption, SIV of RFC 5297, with the two
+/// halves of a SIV key: a [Cipher] keyed with the CTR half `K2`, and the
+/// control block keyed with the S2V half `K1`. Built with
+/// [Cipher::with_siv_key] or [rc5_siv_cipher].
+pub struct SivCipher<B, W, const N: usize>
+where
+    W: Word,
+    B: BlockCipher<W, N>,
+{
+    cipher: Cipher<B, W, N>,
+    mac_block: B,
+}
+
+impl<B, W, const N: usize> SivCipher<B, W, N>
+where
+    W: Word,
+    B: BlockCipher<W, N>,
+{
+    /// Deterministic authenticated encryption of plain-text bytes along with
+    /// associated data components, SIV of RFC 5297, and returns the
+    /// synthetic iv followed by the cipher-text.
+    ///
+    /// Encrypting the same plain-text and associated data twice gives the
+    /// same cipher-text, which only tells that the messages are equal, so
+    /// no nonce is required and a repeated nonce component does not
+    /// weaken the encryption. Requires 64-bit or larger blocks, otherwise
+    /// fails with [Reason::UnsupportedBlockSize].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use rc5_block::rc5_siv_cipher;
+    ///
+    /// let cipher = rc5_siv_cipher::<u64>(b"0123456789abcdef0123456789abcdef", 16).unwrap();
+    ///
+    /// let ct = cipher.seal_deterministic(&[b"users", b"email"], b"alice@example.com").unwrap();
+    /// assert_eq!(ct, cipher.seal_deterministic(&[b"users", b"email"], b"alice@example.com").unwrap());
+    ///
+    /// let pt = cipher.open_deterministic(&[b"users", b"email"], &ct).unwrap();
+    /// assert_eq!(pt, b"alice@example.com");
+    /// ```
+    pub fn seal_deterministic(&self, ad: &[&[u8]], pt: &[u8]) -> Result<Vec<u8>, Reason> {
+        let bs = self.check_siv(ad)?;
+
+        let mut ct = vec![0u8; bs + pt.len()];
+        let (iv, payload) = ct.split_at_mut(bs);
+        payload.copy_from_slice(pt);
+
+        let synthetic_iv = modes::siv_encrypt(&self.mac_block, &self.cipher.block, ad, payload);
+        utils::block_to_bytes(&synthetic_iv, iv);
+
+        Ok(ct)
+    }
+
+    /// Verify and decrypt a synthetic iv followed by cipher-text bytes, as
+    /// produced by [SivCipher::seal_deterministic] with the same associated
+    /// data components.
+    ///
+    /// Fails with [Reason::AuthenticationFailed] if the cipher-text, the
+    /// iv or any associated data component were altered.
+    pub fn open_deterministic(&self, ad: &[&[u8]], ct: &[u8]) -> Result<Vec<u8>, Reason> {
+        let bs = self.check_siv(ad)?;
+        bail!(ct.len() < bs, Reason::AuthenticationFailed);
+
+        let (iv, payload) = ct.split_at(bs);
+        let mut pt = payload.to_vec();
+        modes::siv_decrypt(
+            &self.mac_block,
+            &self.cipher.block,
+            ad,
+            utils::block_from_bytes(iv),
+            &mut pt,
+        )?;
+
+        Ok(pt)
+    }
+
+    /// Validates the block size and the number of associated data
+    /// components of SIV, and returns the block size.
+    fn check_siv(&self, ad: &[&[u8]]) -> Result<usize, Reason> {
+        let bs = self.cipher.block.block_size();
+        bail!(bs < 8, Reason::UnsupportedBlockSize(bs));
+
+        // S2V takes at most `8 * bs - 1` strings, the plain-text included.
+        let max = 8 * bs - 2;
+        bail!(
+            ad.len() > max,
+            Reason::TooManyComponents {
+                max,
+                current: ad.len()
+            }
+        );
+
+        Ok(bs)
+    }
+}
+
+/// Only shows the control block version and the fingerprints of both
+/// halves of the key, see [BlockCipher::key_check_value].
+impl<B, W, const N: usize> fmt::Debug for SivCipher<B, W, N>
+where
+    W: Word,
+    B: BlockCipher<W, N>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SivCipher")
+            .field("version", &self.cipher.block.control_block_version())
+            .field("fingerprint", &self.cipher.block.key_check_value())
+            .field("siv_fingerprint", &self.mac_block.key_check_value())
+            .finish()
+    }
 }
 
 /// A core trait that any block-cipher must implement to work with [Cipher].
@@ -295,15 +1563,28 @@ pub trait BlockCipher<W: Word, const N: usize> {
     /// Word-szie in bytes per block for a block-cipher.
     fn word_size(&self) -> usize;
 
-    /// Split a byte‐vector into a `Vec` of length-`N` word blocks.
+    /// Split a byte‐slice into a `Vec` of length-`N` word blocks.
     /// More generally, it creates a list of blocks from a stream of
-    /// plain bytes.
-    fn generate_blocks(&self, pt: Vec<u8>) -> Vec<[W; N]>;
+    /// plain bytes, trailing bytes of an incomplete block are ignored.
+    ///
+    /// Words are read in little-endian order directly from the slice.
+    fn generate_blocks(&self, pt: &[u8]) -> Vec<[W; N]> {
+        pt.chunks_exact(self.block_size())
+            .map(utils::block_from_bytes)
+            .collect()
+    }
 
     /// Generates a stream of bytes from a list of blocks. More
     /// specefically from `N` word blocks list generates byte-vector.
     /// Its counterfiet of generate_blocks method.
-    fn generate_bytes_stream(&self, blocks: Vec<[W; N]>) -> Vec<u8>;
+    fn generate_bytes_stream(&self, blocks: &[[W; N]]) -> Vec<u8> {
+        let bs = self.block_size();
+        let mut stream = vec![0u8; blocks.len() * bs];
+        for (chunk, block) in stream.chunks_exact_mut(bs).zip(blocks) {
+            utils::block_to_bytes(block, chunk);
+        }
+        stream
+    }
 
     /// Raw encryption, encrypt a single `[W;N]` block.
     ///
@@ -314,6 +1595,36 @@ pub trait BlockCipher<W: Word, const N: usize> {
     ///
     /// Returns a plain-text `[W;N]` block
     fn decrypt(&self, ct: [W; N]) -> [W; N];
+
+    /// Raw encryption of a batch of independent blocks in place.
+    ///
+    /// Encrypts block by block by default, block-ciphers may override it
+    /// with a multi-lane implementation (e.g. SIMD).
+    fn encrypt_blocks(&self, blocks: &mut [[W; N]]) {
+        for block in blocks {
+            *block = self.encrypt(*block);
+        }
+    }
+
+    /// Raw decryption of a batch of independent blocks in place.
+    ///
+    /// Decrypts block by block by default, block-ciphers may override it
+    /// with a multi-lane implementation (e.g. SIMD).
+    fn decrypt_blocks(&self, blocks: &mut [[W; N]]) {
+        for block in blocks {
+            *block = self.decrypt(*block);
+        }
+    }
+
+    /// Key check value, a fingerprint identifying the key without
+    /// revealing it: the first 3 bytes of the encrypted all-zero block,
+    /// hex-encoded in upper case (e.g. `"8F681D"`).
+    fn key_check_value(&self) -> String {
+        let mut block = [0u8; 3];
+        let len = block.len().min(self.block_size());
+        utils::block_to_bytes(&self.encrypt([W::ZERO; N]), &mut block[..len]);
+        hex::encode_upper(&block[..len])
+    }
 }
 
 pub type RC5Cipher<W> = Cipher<RC5ControlBlock<W>, W, 2>;
@@ -330,6 +1641,44 @@ where
     Ok(Cipher::new(control_block))
 }
 
+pub type RC5SivCipher<W> = SivCipher<RC5ControlBlock<W>, W, 2>;
+
+/// Construct an RC5 cipher for [SivCipher::seal_deterministic] from a
+/// double-length SIV key and round count.
+///
+/// As in RFC 5297, the first half of the key keys S2V and the second half
+/// keys CTR, both halves must have the same length, otherwise this fails
+/// with [Reason::SivKeyLength].
+pub fn rc5_siv_cipher<W>(key: impl AsRef<[u8]>, rounds: usize) -> Result<RC5SivCipher<W>, Reason>
+where
+    W: Word,
+{
+    let key = key.as_ref();
+    bail!(
+        key.is_empty() || !key.len().is_multiple_of(2),
+        Reason::SivKeyLength(key.len())
+    );
+
+    let (mac_key, ctr_key) = key.split_at(key.len() / 2);
+    let mac_block = RC5ControlBlock::<W>::new(mac_key, rounds)?;
+    let ctr_block = RC5ControlBlock::<W>::new(ctr_key, rounds)?;
+    Ok(Cipher::new(ctr_block).with_siv_key(mac_block))
+}
+
+pub type RC6Cipher<W> = Cipher<RC6ControlBlock<W>, W, 4>;
+
+/// Construct a new RC6 cipher from a raw key and round count.
+///
+/// This is a help function which initializes Cipher with RC6
+/// control-bock.
+pub fn rc6_cipher<W>(key: impl AsRef<[u8]>, rounds: usize) -> Result<RC6Cipher<W>, Reason>
+where
+    W: Word,
+{
+    let control_block = RC6ControlBlock::<W>::new(key, rounds)?;
+    Ok(Cipher::new(control_block))
+}
+
 /// Helper macro to bail out early with a `Reason` error
 /// if any condition is true.
 #[macro_export]
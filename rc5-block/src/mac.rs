@@ -0,0 +1,326 @@
+//! Message authentication building blocks of the authenticated modes of
+//! operation: CBC-MAC, CMAC (OMAC1) and the doubling in GF(2^n) behind
+//! the CMAC subkeys, and the GHASH of GCM.
+//!
+//! Blocks are handled as the byte strings written by the modes of
+//! operation (little-endian words), the first byte holding the most
+//! significant bits of the field element, so the 64 and 128-bit blocks
+//! match NIST SP 800-38B CMAC.
+use core::array::from_fn;
+
+use crate::{BlockCipher, Word, utils::block_from_bytes};
+
+/// Low terms of the lexicographically first irreducible pentanomial
+/// `x^n + x^a + x^b + x^c + 1` of every block size `n` from 16 to 1024
+/// bits, indexed by `n / 8 - 2`, e.g. `0x1b` for 64-bit and `0x87` for
+/// 128-bit blocks as in NIST SP 800-38B.
+const POLYNOMIALS: [u32; 127] = [
+    0x02b, 0x01b, 0x08d, 0x039, 0x02d, 0x095, 0x01b, 0x609, 0x215, 0x0c5, 0x641, 0x01b, 0x039,
+    0x01b, 0x087, 0x02d, 0x095, 0x04d, 0x02d, 0x800d, 0x80d, 0x381, 0x087, 0x02d, 0x20b, 0x08b,
+    0x309, 0x215, 0x129, 0xc401, 0x425, 0x245, 0x20d, 0x225, 0xc03, 0x08d, 0x807, 0x291, 0x01b,
+    0x10b, 0x093, 0x087, 0x2841, 0x02d, 0x08d, 0x1a1, 0x100d, 0x2441, 0x02d, 0x02d, 0x225, 0x285,
+    0x2019, 0x01b, 0x851, 0x40241, 0xc2001, 0x80d, 0x8241, 0x01b, 0x10025, 0xc041, 0x125, 0x8805,
+    0x845, 0x0a9, 0x10b, 0x90201, 0xa41, 0x80c1, 0x2019, 0x6009, 0x2049, 0x225, 0x82041, 0x80409,
+    0x861, 0x207, 0x400d, 0x200b, 0x0b1, 0xb01, 0x861, 0x810201, 0x84041, 0x800405, 0x10d, 0x039,
+    0x251, 0x01d, 0x2141, 0x2803, 0x2409, 0x861, 0xa0011, 0xc081, 0x2241, 0x289, 0x283, 0x400d,
+    0x905, 0x851, 0x2025, 0x823, 0x813, 0x80409, 0x200441, 0x200b, 0x80a1, 0xc0401, 0x0a9, 0x1085,
+    0x0a3, 0x4241, 0x40d, 0xb001, 0x1a01, 0x10281, 0x1209, 0x225, 0x20441, 0x1000209, 0x2a001,
+    0x039, 0xa0101, 0x8049, 0x80043,
+];
+
+/// Returns the reduction polynomial of a block of `bs` bytes.
+///
+/// # Panics
+///
+/// Panics for blocks shorter than 2 bytes or longer than 128 bytes, which
+/// no RC5 or RC6 control block produces.
+fn polynomial(bs: usize) -> u32 {
+    POLYNOMIALS[bs - 2]
+}
+
+/// Multiplies a block by `x` in GF(2^n), i.e. shifts its bit string left
+/// by one and reduces it, without branching on the carried out bit.
+pub fn dbl<W: Word, const N: usize>(block: &[W; N]) -> [W; N] {
+    let bs = N * W::BYTES;
+    let bytes = block.map(W::to_bytes);
+    let byte = |ix: usize| bytes[ix / W::BYTES].as_ref()[ix % W::BYTES];
+
+    let carry = 0u32.wrapping_sub((byte(0) >> 7) as u32);
+    let reduction = polynomial(bs) & carry;
+
+    from_fn(|wx| {
+        let mut word = W::Bytes::default();
+        for (jx, b) in word.as_mut().iter_mut().enumerate() {
+            let ix = wx * W::BYTES + jx;
+            let next = if ix + 1 < bs { byte(ix + 1) >> 7 } else { 0 };
+            *b = (byte(ix) << 1) | next;
+
+            // The reduction terms land in the last four bytes at most.
+            let shift = bs - 1 - ix;
+            if shift < 4 {
+                *b ^= (reduction >> (8 * shift)) as u8;
+            }
+        }
+        W::from_bytes(word)
+    })
+}
+
+/// # CbcMac
+///
+/// Incremental CBC-MAC of a byte stream, the last block of its CBC
+/// encryption under a zero iv. Partial blocks are zero padded, either at
+/// [CbcMac::finalize] or to start the following bytes on a fresh block
+/// with [CbcMac::pad].
+pub struct CbcMac<'a, C, W: Word, const N: usize> {
+    control_block: &'a C,
+    state: [W; N],
+    pending: [W::Bytes; N],
+    filled: usize,
+}
+
+impl<'a, C, W, const N: usize> CbcMac<'a, C, W, N>
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    /// Starts a CBC-MAC computation keyed by the control block.
+    pub fn new(control_block: &'a C) -> Self {
+        Self {
+            control_block,
+            state: [W::ZERO; N],
+            pending: from_fn(|_| W::Bytes::default()),
+            filled: 0,
+        }
+    }
+
+    /// Appends bytes to the authenticated message.
+    pub fn update(&mut self, mut data: &[u8]) {
+        let bs = N * W::BYTES;
+
+        while !data.is_empty() {
+            if self.filled == bs {
+                let block = self.pending.map(W::from_bytes);
+                self.absorb(&block);
+                self.filled = 0;
+            }
+
+            // Whole blocks are absorbed straight from `data`, except the
+            // last one which [Cmac] masks with a subkey.
+            if self.filled == 0 && data.len() > bs {
+                self.absorb(&block_from_bytes(&data[..bs]));
+                data = &data[bs..];
+                continue;
+            }
+
+            let take = (bs - self.filled).min(data.len());
+            for (ix, b) in data[..take].iter().enumerate() {
+                let ix = self.filled + ix;
+                self.pending[ix / W::BYTES].as_mut()[ix % W::BYTES] = *b;
+            }
+            self.filled += take;
+            data = &data[take..];
+        }
+    }
+
+    /// Appends the little-endian bytes of a block to the authenticated
+    /// message.
+    pub fn update_block(&mut self, block: &[W; N]) {
+        for word in block {
+            self.update(word.to_bytes().as_ref());
+        }
+    }
+
+    /// Zero pads the message up to the next block boundary, nothing is
+    /// added when it is block aligned.
+    pub fn pad(&mut self) {
+        if self.filled > 0 {
+            for ix in self.filled..N * W::BYTES {
+                self.pending[ix / W::BYTES].as_mut()[ix % W::BYTES] = 0;
+            }
+
+            let block = self.pending.map(W::from_bytes);
+            self.absorb(&block);
+            self.filled = 0;
+        }
+    }
+
+    /// Returns the CBC-MAC of the zero padded message.
+    pub fn finalize(mut self) -> [W; N] {
+        self.pad();
+        self.state
+    }
+
+    fn absorb(&mut self, block: &[W; N]) {
+        self.state = self
+            .control_block
+            .encrypt(from_fn(|ix| self.state[ix] ^ block[ix]));
+    }
+}
+
+/// # Cmac
+///
+/// Incremental CMAC (OMAC1) of a byte stream, a [CbcMac] whose last block
+/// is held back until [Cmac::finalize], since it is masked with a subkey.
+pub struct Cmac<'a, C, W: Word, const N: usize> {
+    mac: CbcMac<'a, C, W, N>,
+}
+
+impl<'a, C, W, const N: usize> Cmac<'a, C, W, N>
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    /// Starts a CMAC computation keyed by the control block.
+    pub fn new(control_block: &'a C) -> Self {
+        Self {
+            mac: CbcMac::new(control_block),
+        }
+    }
+
+    /// Appends bytes to the authenticated message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.mac.update(data);
+    }
+
+    /// Appends the little-endian bytes of a block to the authenticated
+    /// message.
+    pub fn update_block(&mut self, block: &[W; N]) {
+        self.mac.update_block(block);
+    }
+
+    /// Returns the CMAC of the message.
+    pub fn finalize(self) -> [W; N] {
+        let mut mac = self.mac;
+        let bs = N * W::BYTES;
+        let k1 = dbl(&mac.control_block.encrypt([W::ZERO; N]));
+
+        let subkey = match mac.filled == bs {
+            true => k1,
+            false => {
+                for ix in mac.filled..bs {
+                    let b = if ix == mac.filled { 0x80 } else { 0 };
+                    mac.pending[ix / W::BYTES].as_mut()[ix % W::BYTES] = b;
+                }
+                dbl(&k1)
+            }
+        };
+
+        let last = mac.pending.map(W::from_bytes);
+        mac.absorb(&from_fn(|ix| last[ix] ^ subkey[ix]));
+        mac.state
+    }
+}
+
+/// # Ghash
+///
+/// Incremental GHASH of GCM (NIST SP 800-38D) over 128-bit blocks, read
+/// as big-endian integers, keyed by the hash subkey `H`. Partial blocks
+/// are zero padded like in [CbcMac].
+pub struct Ghash {
+    key: u128,
+    state: u128,
+    pending: [u8; 16],
+    filled: usize,
+}
+
+impl Ghash {
+    /// Starts a GHASH computation keyed by the hash subkey.
+    pub fn new(key: u128) -> Self {
+        Self {
+            key,
+            state: 0,
+            pending: [0; 16],
+            filled: 0,
+        }
+    }
+
+    /// Appends bytes to the hashed message.
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.filled == 0 && data.len() >= 16 {
+                let (block, rest) = data.split_at(16);
+                self.absorb(u128::from_be_bytes(block.try_into().unwrap()));
+                data = rest;
+                continue;
+            }
+
+            let take = (16 - self.filled).min(data.len());
+            self.pending[self.filled..self.filled + take].copy_from_slice(&data[..take]);
+            self.filled += take;
+            data = &data[take..];
+
+            if self.filled == 16 {
+                self.absorb(u128::from_be_bytes(self.pending));
+                self.filled = 0;
+            }
+        }
+    }
+
+    /// Zero pads the message up to the next block boundary, nothing is
+    /// added when it is block aligned.
+    pub fn pad(&mut self) {
+        if self.filled > 0 {
+            self.pending[self.filled..].fill(0);
+            self.absorb(u128::from_be_bytes(self.pending));
+            self.filled = 0;
+        }
+    }
+
+    /// Returns the GHASH of the zero padded message.
+    pub fn finalize(mut self) -> u128 {
+        self.pad();
+        self.state
+    }
+
+    fn absorb(&mut self, block: u128) {
+        self.state = gf_mul(self.state ^ block, self.key);
+    }
+}
+
+/// Multiplies two elements of GF(2^128) in the bit-reflected convention
+/// of GCM, the first bit being the coefficient of `x^0`, without branching
+/// on their bits.
+fn gf_mul(x: u128, y: u128) -> u128 {
+    let mut z = 0;
+    let mut v = y;
+
+    for ix in (0..128).rev() {
+        z ^= v & 0u128.wrapping_sub((x >> ix) & 1);
+        v = (v >> 1) ^ ((0xe1 << 120) & 0u128.wrapping_sub(v & 1));
+    }
+
+    z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{POLYNOMIALS, dbl, gf_mul};
+
+    #[test]
+    fn doubling_reduces_the_carried_bit() {
+        assert_eq!(POLYNOMIALS[8 - 2], 0x1b);
+        assert_eq!(POLYNOMIALS[16 - 2], 0x87);
+
+        // The first byte is the most significant, bits move towards it.
+        assert_eq!(dbl(&[0x0100u32, 0]), [0x0200, 0]);
+        assert_eq!(dbl(&[0x8000_0000u32, 0]), [0x0001_0000, 0]);
+        assert_eq!(dbl(&[0u32, 0x80]), [0x0100_0000, 0]);
+
+        // 0x80 followed by zeros carries out and reduces to 0x1b in the
+        // last byte.
+        assert_eq!(dbl(&[0x80u32, 0]), [0, 0x1b00_0000]);
+    }
+
+    #[test]
+    fn multiplication_in_the_gcm_field() {
+        // The leading bit is the unit of the field.
+        let one = 1u128 << 127;
+        let y = 0x66e94bd4ef8a2c3b884cfa59ca342b2e;
+        assert_eq!(gf_mul(one, y), y);
+        assert_eq!(gf_mul(y, one), y);
+        assert_eq!(gf_mul(0, y), 0);
+
+        // x^127 * x = x^128 = x^7 + x^2 + x + 1.
+        assert_eq!(gf_mul(1, 1 << 126), 0xe1 << 120);
+    }
+}
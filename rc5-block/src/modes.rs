@@ -1,15 +1,35 @@
-use crate::{BlockCipher, Word};
+use core::{array::from_fn, cmp::Ordering};
+
+use crate::{
+    BlockCipher, Reason, Word, bail,
+    mac::{CbcMac, Cmac, Ghash, dbl},
+    utils::{
+        block_from_bytes, block_matches_bytes, block_to_bytes, blocks_match, xor_block_into_bytes,
+    },
+};
 
 /// Modes of operation for a block cipher.
 ///
 /// - **ECB**: Electronic Codebook mode.  
 /// - **CBC**: Cipher Block Chaining mode.  
+/// - **CBC_NOPAD**: Cipher Block Chaining mode without padding (RC5-CBC of RFC 2040).
 /// - **CTR**: Counter mode.
+/// - **CFB**: Cipher Feedback mode.
+/// - **OFB**: Output Feedback mode.
+/// - **PCBC**: Propagating Cipher Block Chaining mode.
+/// - **IGE**: Infinite Garble Extension mode.
+/// - **CBC_CTS**: RC5-CTS, CBC with cipher-text stealing (RFC 2040).
+/// - **ECB_CTS**: ECB with cipher-text stealing.
+/// - **EAX**: EAX authenticated encryption with associated data.
+/// - **CCM**: Counter with CBC-MAC authenticated encryption with associated data.
+/// - **OCB**: Offset Codebook authenticated encryption with associated data.
 ///
 /// ECB mode of operation is less secure and is not recommended
 /// to use in production applications since it can be broken
 /// muc easily, special care should be kept while using this
 /// mode.
+#[derive(Clone, Copy)]
+#[allow(non_camel_case_types)]
 pub enum OperationMode<W: Word, const N: usize> {
     /// Electronic Codebook
     ///
@@ -23,180 +43,1471 @@ pub enum OperationMode<W: Word, const N: usize> {
     /// enhanced security.
     CBC { iv: [W; N] },
 
+    /// Cipher Block Chaining without padding
+    ///
+    /// RC5-CBC of RFC 2040, the plain-text must be a whole number of
+    /// blocks and the cipher-text is as long as the plain-text. [OperationMode::CBC]
+    /// is RC5-CBC-Pad, its PKCS#7 padding is the padding of RFC 2040.
+    CBC_NOPAD { iv: [W; N] },
+
     /// Counter
     ///
     /// Requires a starting nonce + counter block, this way
     /// it adds two stage complexity over encryption/decryption.
+    /// The last word is the counter, it wraps around without carrying,
+    /// so RC5-8 encrypts at most 256 blocks, i.e. 512 bytes.
     CTR { nonce_and_counter: [W; N] },
+
+    /// Cipher Feedback
+    ///
+    /// Requires an initialization vector, and turns the block cipher
+    /// into a self-synchronising stream cipher, no padding is applied.
+    /// `segment_bits` is the number of bits fed back per step, a whole
+    /// number of bytes up to the block size, e.g. `8` for CFB-8 or the
+    /// block size in bits for full-block CFB (OpenSSL's `rc5-cfb`).
+    CFB { iv: [W; N], segment_bits: usize },
+
+    /// Output Feedback
+    ///
+    /// Requires an initialization vector, the key-stream is the chain
+    /// of encryptions of the iv, so no padding is applied (OpenSSL's
+    /// `rc5-ofb`).
+    OFB { iv: [W; N] },
+
+    /// Propagating Cipher Block Chaining
+    ///
+    /// Like CBC, but both the previous plain-text and cipher-text
+    /// blocks are chained into the next one (Kerberos v4 style), so a
+    /// single error garbles every following block.
+    PCBC { iv: [W; N] },
+
+    /// Infinite Garble Extension
+    ///
+    /// Requires two initialization vectors, `iv1` stands for the
+    /// cipher-text block and `iv2` for the plain-text block preceding
+    /// the first one (OpenSSL's IGE layout). A single error garbles
+    /// every following block.
+    IGE { iv1: [W; N], iv2: [W; N] },
+
+    /// Cipher Block Chaining with cipher-text stealing
+    ///
+    /// RC5-CTS of RFC 2040, requires an initialization vector and at
+    /// least one block of input. No padding is applied, the cipher-text
+    /// is as long as the plain-text.
+    CBC_CTS { iv: [W; N] },
+
+    /// Electronic Codebook with cipher-text stealing
+    ///
+    /// Requires at least one block of input. No padding is applied, the
+    /// cipher-text is as long as the plain-text.
+    ECB_CTS,
+
+    /// EAX authenticated encryption
+    ///
+    /// CTR encryption authenticated with OMAC, for any block size.
+    /// Requires a nonce that is never reused under the same key, and
+    /// appends a tag of `tag_len` bytes, from 1 up to the block size, to
+    /// the cipher-text. Associated data is passed to [Cipher::encrypt_aead](crate::Cipher::encrypt_aead).
+    EAX { nonce: [W; N], tag_len: usize },
+
+    /// Counter with CBC-MAC authenticated encryption
+    ///
+    /// CTR encryption authenticated with CBC-MAC (RFC 3610), only the
+    /// encryption direction of the block cipher is used. The message length
+    /// is written in a `length_size` field of 2 to 8 bytes, `L` in RFC 3610,
+    /// and the nonce is made of the leading `block size - 1 - length_size`
+    /// bytes of `nonce`, e.g. 13 bytes for 128-bit blocks and `L = 2`, see
+    /// [Cipher::nonce_from_bytes](crate::Cipher::nonce_from_bytes). The
+    /// nonce is at least 1 byte long, so `length_size` is at most the block
+    /// size - 2, e.g. 2 for the 32-bit blocks of RC5-16. The
+    /// tag of `tag_len` bytes, even from 4 up to 16 and the block size, is
+    /// appended to the cipher-text.
+    CCM {
+        nonce: [W; N],
+        length_size: usize,
+        tag_len: usize,
+    },
+
+    /// Offset Codebook authenticated encryption
+    ///
+    /// Single-pass encryption and authentication of OCB (RFC 7253) for
+    /// 128-bit blocks, and of its 64-bit block variant for RC5-32, other
+    /// block sizes are not supported. The nonce is made of the leading
+    /// `nonce_len` bytes of `nonce`, from 1 up to the block size minus
+    /// one, see [Cipher::nonce_from_bytes](crate::Cipher::nonce_from_bytes),
+    /// and is never reused under the same key. Appends a tag of `tag_len`
+    /// bytes, from 1 up to the block size, to the cipher-text.
+    OCB {
+        nonce: [W; N],
+        nonce_len: usize,
+        tag_len: usize,
+    },
+}
+
+/// # GcmNonce
+///
+/// The nonce of [Cipher::gcm_seal](crate::Cipher::gcm_seal), held as the
+/// pre-counter block `J_0` it derives. Nonces other than 96-bit ones are
+/// hashed with the key of the cipher, so a nonce must be derived again for
+/// another key.
+#[derive(Clone, Copy)]
+pub struct GcmNonce {
+    pre_counter: [u64; 2],
+}
+
+/// # GcmMode
+///
+/// Galois/counter mode (NIST SP 800-38D) for the authenticated methods of
+/// [Cipher](crate::Cipher), e.g. [Cipher::encrypt_aead](crate::Cipher::encrypt_aead),
+/// which only accept it for the 128-bit blocks of RC5-64. Appends a tag of
+/// `tag_len` bytes, 16, 15, 14, 13 or 12, or 8 and 4 for protocols that
+/// bound the message lengths, to the cipher-text.
+#[derive(Clone, Copy)]
+pub struct GcmMode {
+    pub nonce: GcmNonce,
+    pub tag_len: usize,
+}
+
+impl<W: Word, const N: usize> OperationMode<W, N> {
+    /// Returns `true` for the modes authenticating the cipher-text and
+    /// associated data, e.g. [OperationMode::EAX].
+    pub fn is_authenticated(&self) -> bool {
+        matches!(
+            self,
+            OperationMode::EAX { .. } | OperationMode::CCM { .. } | OperationMode::OCB { .. }
+        )
+    }
 }
 
-/// Encrypt a sequence of blocks in ECB mode.
+/// Number of blocks handed at once to [BlockCipher::encrypt_blocks], enough
+/// to fill the widest multi-lane kernels.
+const BATCH_BLOCKS: usize = 16;
+
+/// Encrypt a buffer of whole blocks in place in ECB mode.
 ///
 /// # Parameters
 /// - `control_block`: the underlying block cipher instance.  
-/// - `input_blocks`: vector of full `[W; N]` plaintext blocks.
+/// - `buf`: plaintext bytes, a multiple of the block size, overwritten
+///   by the ciphertext.
+pub fn ecb_encrypt<C, W, const N: usize>(control_block: &C, buf: &mut [u8])
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    ecb_batches(control_block, buf, C::encrypt_blocks);
+}
+
+/// Decrypt a buffer of whole blocks in place in ECB mode.
 ///
-/// # Returns
-/// A vector of `[W; N]` ciphertext blocks.
-pub fn ecb_encrypt<C, W, const N: usize>(
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `buf`: ciphertext bytes, a multiple of the block size, overwritten
+///   by the plaintext.
+pub fn ecb_decrypt<C, W, const N: usize>(control_block: &C, buf: &mut [u8])
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    ecb_batches(control_block, buf, C::decrypt_blocks);
+}
+
+/// Runs `process` over batches of up to [BATCH_BLOCKS] blocks read from
+/// `buf`, writing the processed blocks back in place.
+fn ecb_batches<C, W, const N: usize>(
     control_block: &C,
-    input_blocks: Vec<[W; N]>,
-) -> Vec<[W; N]>
+    buf: &mut [u8],
+    process: impl Fn(&C, &mut [[W; N]]),
+) where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let bs = control_block.block_size();
+    let mut batch = [[W::ZERO; N]; BATCH_BLOCKS];
+
+    for chunk in buf.chunks_mut(bs * BATCH_BLOCKS) {
+        let blocks = &mut batch[..chunk.len() / bs];
+        for (block, bytes) in blocks.iter_mut().zip(chunk.chunks_exact(bs)) {
+            *block = block_from_bytes(bytes);
+        }
+
+        process(control_block, blocks);
+
+        for (block, bytes) in blocks.iter().zip(chunk.chunks_exact_mut(bs)) {
+            block_to_bytes(block, bytes);
+        }
+    }
+}
+
+/// Encrypt a buffer of whole blocks in place in CBC mode.
+///
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `iv`: Initialization Vector (`[W; N]`).  
+/// - `buf`: plaintext bytes, a multiple of the block size, overwritten
+///   by the ciphertext.
+pub fn cbc_encrypt<C, W, const N: usize>(control_block: &C, iv: [W; N], buf: &mut [u8])
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let mut prev = iv;
+
+    for chunk in buf.chunks_exact_mut(control_block.block_size()) {
+        let block: [W; N] = block_from_bytes(chunk);
+        prev.iter_mut()
+            .enumerate()
+            .for_each(|(ix, word)| *word = *word ^ block[ix]);
+
+        prev = control_block.encrypt(prev);
+        block_to_bytes(&prev, chunk);
+    }
+}
+
+/// Decrypt a buffer of whole blocks in place in CBC mode.
+///
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `iv`: Initialization Vector (`[W; N]`).  
+/// - `buf`: ciphertext bytes, a multiple of the block size, overwritten
+///   by the plaintext.
+pub fn cbc_decrypt<C, W, const N: usize>(control_block: &C, iv: [W; N], buf: &mut [u8])
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let mut prev = iv;
+
+    for chunk in buf.chunks_exact_mut(control_block.block_size()) {
+        let block: [W; N] = block_from_bytes(chunk);
+        let mut decrypted = control_block.decrypt(block);
+        prev.iter()
+            .enumerate()
+            .for_each(|(ix, word)| decrypted[ix] = decrypted[ix] ^ *word);
+
+        prev = block;
+        block_to_bytes(&decrypted, chunk);
+    }
+}
+
+/// Encrypt a buffer of whole blocks in place in PCBC mode.
+///
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `iv`: Initialization Vector (`[W; N]`).  
+/// - `buf`: plaintext bytes, a multiple of the block size, overwritten
+///   by the ciphertext.
+pub fn pcbc_encrypt<C, W, const N: usize>(control_block: &C, iv: [W; N], buf: &mut [u8])
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let mut chain = iv;
+
+    for chunk in buf.chunks_exact_mut(control_block.block_size()) {
+        let block: [W; N] = block_from_bytes(chunk);
+        let encrypted = control_block.encrypt(xor_blocks(block, &chain));
+
+        chain = xor_blocks(block, &encrypted);
+        block_to_bytes(&encrypted, chunk);
+    }
+}
+
+/// Decrypt a buffer of whole blocks in place in PCBC mode.
+///
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `iv`: Initialization Vector (`[W; N]`).  
+/// - `buf`: ciphertext bytes, a multiple of the block size, overwritten
+///   by the plaintext.
+pub fn pcbc_decrypt<C, W, const N: usize>(control_block: &C, iv: [W; N], buf: &mut [u8])
 where
     C: BlockCipher<W, N>,
     W: Word,
 {
-    input_blocks
-        .iter()
-        .map(|block| control_block.encrypt(*block))
-        .collect()
+    let mut chain = iv;
+
+    for chunk in buf.chunks_exact_mut(control_block.block_size()) {
+        let block: [W; N] = block_from_bytes(chunk);
+        let decrypted = xor_blocks(control_block.decrypt(block), &chain);
+
+        chain = xor_blocks(block, &decrypted);
+        block_to_bytes(&decrypted, chunk);
+    }
 }
 
-/// Decrypt a sequence of blocks in ECB mode.
+/// Encrypt a buffer of whole blocks in place in IGE mode.
 ///
 /// # Parameters
 /// - `control_block`: the underlying block cipher instance.  
-/// - `input_blocks`: vector of full `[W; N]` ciphertext blocks.
+/// - `iv1`: cipher-text block preceding the first block (`[W; N]`).  
+/// - `iv2`: plain-text block preceding the first block (`[W; N]`).  
+/// - `buf`: plaintext bytes, a multiple of the block size, overwritten
+///   by the ciphertext.
+pub fn ige_encrypt<C, W, const N: usize>(
+    control_block: &C,
+    iv1: [W; N],
+    iv2: [W; N],
+    buf: &mut [u8],
+) where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let (mut prev_ct, mut prev_pt) = (iv1, iv2);
+
+    for chunk in buf.chunks_exact_mut(control_block.block_size()) {
+        let block: [W; N] = block_from_bytes(chunk);
+        let encrypted = xor_blocks(control_block.encrypt(xor_blocks(block, &prev_ct)), &prev_pt);
+
+        (prev_ct, prev_pt) = (encrypted, block);
+        block_to_bytes(&encrypted, chunk);
+    }
+}
+
+/// Decrypt a buffer of whole blocks in place in IGE mode.
 ///
-/// # Returns
-/// A vector of `[W; N]` plaintext blocks.
-pub fn ecb_decrypt<C, W, const N: usize>(
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `iv1`: cipher-text block preceding the first block (`[W; N]`).  
+/// - `iv2`: plain-text block preceding the first block (`[W; N]`).  
+/// - `buf`: ciphertext bytes, a multiple of the block size, overwritten
+///   by the plaintext.
+pub fn ige_decrypt<C, W, const N: usize>(
     control_block: &C,
-    input_blocks: Vec<[W; N]>,
-) -> Vec<[W; N]>
+    iv1: [W; N],
+    iv2: [W; N],
+    buf: &mut [u8],
+) where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let (mut prev_ct, mut prev_pt) = (iv1, iv2);
+
+    for chunk in buf.chunks_exact_mut(control_block.block_size()) {
+        let block: [W; N] = block_from_bytes(chunk);
+        let decrypted = xor_blocks(control_block.decrypt(xor_blocks(block, &prev_pt)), &prev_ct);
+
+        (prev_ct, prev_pt) = (block, decrypted);
+        block_to_bytes(&decrypted, chunk);
+    }
+}
+
+/// Encrypt a byte stream of at least one block in place in RC5-CTS mode
+/// (RFC 2040 section 8), CBC with cipher-text stealing.
+///
+/// The last two cipher-text blocks are swapped and the last one is
+/// truncated to the length of the last plain-text block, so the
+/// cipher-text is as long as the plain-text. A single block is plain CBC.
+///
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.
+/// - `iv`: Initialization Vector (`[W; N]`).
+/// - `buf`: plaintext bytes, at least one block, overwritten by the
+///   ciphertext.
+pub fn cbc_cts_encrypt<C, W, const N: usize>(control_block: &C, iv: [W; N], buf: &mut [u8])
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let bs = control_block.block_size();
+    let Some((head, tail)) = split_stolen(buf, bs) else {
+        return cbc_encrypt(control_block, iv, buf);
+    };
+
+    cbc_encrypt(control_block, iv, head);
+
+    // En-1 is the CBC encryption of Pn-1, and Cn-1 = E(En-1 ^ Pn) with Pn
+    // zero padded, so the padding keeps the bytes of En-1.
+    let (_, stolen) = head.split_at_mut(head.len() - bs);
+    let last: [W; N] = block_from_bytes(stolen);
+    xor_block_into_bytes(&last, tail);
+    let chained = with_head_bytes(&last, tail);
+
+    block_to_bytes(&last, tail);
+    block_to_bytes(&control_block.encrypt(chained), stolen);
+}
+
+/// Decrypt a byte stream of at least one block in place in RC5-CTS mode
+/// (RFC 2040 section 8), see [cbc_cts_encrypt].
+///
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.
+/// - `iv`: Initialization Vector (`[W; N]`).
+/// - `buf`: ciphertext bytes, at least one block, overwritten by the
+///   plaintext.
+pub fn cbc_cts_decrypt<C, W, const N: usize>(control_block: &C, iv: [W; N], buf: &mut [u8])
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let bs = control_block.block_size();
+    let Some((head, tail)) = split_stolen(buf, bs) else {
+        return cbc_decrypt(control_block, iv, buf);
+    };
+
+    let (head, stolen) = head.split_at_mut(head.len() - bs);
+    let prev = match head.len() {
+        0 => iv,
+        len => block_from_bytes(&head[len - bs..]),
+    };
+    cbc_decrypt(control_block, iv, head);
+
+    // D(Cn-1) = En-1 ^ Pn, the bytes of En-1 past Pn are recovered from it.
+    let decrypted = control_block.decrypt(block_from_bytes(stolen));
+    let last = with_head_bytes(&decrypted, tail);
+    xor_block_into_bytes(&decrypted, tail);
+
+    block_to_bytes(&xor_blocks(control_block.decrypt(last), &prev), stolen);
+}
+
+/// Encrypt a byte stream of at least one block in place in ECB mode with
+/// cipher-text stealing.
+///
+/// The last partial plain-text block is completed with the tail of the
+/// preceding cipher-text block, and the two last cipher-text blocks are
+/// swapped, so the cipher-text is as long as the plain-text. A whole
+/// number of blocks is plain ECB.
+///
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.
+/// - `buf`: plaintext bytes, at least one block, overwritten by the
+///   ciphertext.
+pub fn ecb_cts_encrypt<C, W, const N: usize>(control_block: &C, buf: &mut [u8])
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let bs = control_block.block_size();
+    let Some((head, tail)) = split_stolen(buf, bs).filter(|(_, tail)| tail.len() < bs) else {
+        return ecb_encrypt(control_block, buf);
+    };
+
+    ecb_encrypt(control_block, head);
+
+    let (_, stolen) = head.split_at_mut(head.len() - bs);
+    let last: [W; N] = block_from_bytes(stolen);
+    let completed = with_head_bytes(&last, tail);
+
+    block_to_bytes(&last, tail);
+    block_to_bytes(&control_block.encrypt(completed), stolen);
+}
+
+/// Decrypt a byte stream of at least one block in place in ECB mode with
+/// cipher-text stealing, see [ecb_cts_encrypt].
+///
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.
+/// - `buf`: ciphertext bytes, at least one block, overwritten by the
+///   plaintext.
+pub fn ecb_cts_decrypt<C, W, const N: usize>(control_block: &C, buf: &mut [u8])
 where
     C: BlockCipher<W, N>,
     W: Word,
 {
-    input_blocks
-        .iter()
-        .map(|block| control_block.decrypt(*block))
-        .collect()
+    let bs = control_block.block_size();
+    let Some((head, tail)) = split_stolen(buf, bs).filter(|(_, tail)| tail.len() < bs) else {
+        return ecb_decrypt(control_block, buf);
+    };
+
+    let (head, stolen) = head.split_at_mut(head.len() - bs);
+    ecb_decrypt(control_block, head);
+
+    let completed = control_block.decrypt(block_from_bytes(stolen));
+    let last = with_head_bytes(&completed, tail);
+
+    block_to_bytes(&completed, tail);
+    block_to_bytes(&control_block.decrypt(last), stolen);
+}
+
+/// Splits a buffer of more than one block before its last, possibly
+/// partial, block. Returns `None` for a single block.
+fn split_stolen(buf: &mut [u8], bs: usize) -> Option<(&mut [u8], &mut [u8])> {
+    let head = (buf.len() - 1) / bs * bs;
+    (head > 0).then(|| buf.split_at_mut(head))
+}
+
+/// Returns `block` with its first `bytes.len()` bytes replaced by `bytes`.
+fn with_head_bytes<W: Word, const N: usize>(block: &[W; N], bytes: &[u8]) -> [W; N] {
+    let mut words = block.map(W::to_bytes);
+    for (ix, b) in bytes.iter().enumerate() {
+        words[ix / W::BYTES].as_mut()[ix % W::BYTES] = *b;
+    }
+
+    words.map(W::from_bytes)
+}
+
+/// XORs two `[W; N]` blocks word by word.
+#[inline]
+fn xor_blocks<W: Word, const N: usize>(block: [W; N], other: &[W; N]) -> [W; N] {
+    from_fn(|ix| block[ix] ^ other[ix])
 }
 
-/// Encrypt in CBC mode.
+/// Encrypt a byte stream in place in CFB mode.
 ///
 /// # Parameters
 /// - `control_block`: the underlying block cipher instance.  
 /// - `iv`: Initialization Vector (`[W; N]`).  
-/// - `input_blocks`: vector of full `[W; N]` plaintext blocks.
+/// - `segment`: segment size in bytes, from 1 (CFB-8) up to the block size.  
+/// - `buf`: plaintext bytes (any length), overwritten by the ciphertext.
+pub fn cfb_encrypt<C, W, const N: usize>(
+    control_block: &C,
+    iv: [W; N],
+    segment: usize,
+    buf: &mut [u8],
+) where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let mut register = iv;
+
+    for chunk in buf.chunks_mut(segment) {
+        xor_block_into_bytes(&control_block.encrypt(register), chunk);
+        register = shift_register(&register, chunk);
+    }
+}
+
+/// Decrypt a byte stream in place in CFB mode.
 ///
-/// # Returns
-/// A vector of `[W; N]` ciphertext blocks.
-pub fn cbc_encrypt<C, W, const N: usize>(
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `iv`: Initialization Vector (`[W; N]`).  
+/// - `segment`: segment size in bytes, from 1 (CFB-8) up to the block size.  
+/// - `buf`: ciphertext bytes (any length), overwritten by the plaintext.
+pub fn cfb_decrypt<C, W, const N: usize>(
     control_block: &C,
     iv: [W; N],
-    input_blocks: Vec<[W; N]>,
-) -> Vec<[W; N]>
+    segment: usize,
+    buf: &mut [u8],
+) where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let mut register = iv;
+
+    for chunk in buf.chunks_mut(segment) {
+        let key_stream = control_block.encrypt(register);
+        register = shift_register(&register, chunk);
+        xor_block_into_bytes(&key_stream, chunk);
+    }
+}
+
+/// Shifts the CFB register left by `segment.len()` bytes of the byte
+/// stream, and appends the cipher-text `segment` to it.
+fn shift_register<W: Word, const N: usize>(register: &[W; N], segment: &[u8]) -> [W; N] {
+    let bytes = register.map(W::to_bytes);
+    let kept = N * W::BYTES - segment.len();
+    let byte = |ix: usize| match ix < kept {
+        true => bytes[(ix + segment.len()) / W::BYTES].as_ref()[(ix + segment.len()) % W::BYTES],
+        false => segment[ix - kept],
+    };
+
+    from_fn(|ix| {
+        let mut word = W::Bytes::default();
+        for (jx, b) in word.as_mut().iter_mut().enumerate() {
+            *b = byte(ix * W::BYTES + jx);
+        }
+        W::from_bytes(word)
+    })
+}
+
+/// Apply the CTR mode key-stream to a byte stream in place (stream cipher).
+///
+/// Encryption and decryption are the same operation, the last word of
+/// the counter block is incremented after every block.
+///
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `nonce_and_counter`: initial counter block (`[W; N]`).  
+/// - `buf`: bytes to encrypt or decrypt (any length), overwritten by
+///   the result.
+pub fn ctr_apply<C, W, const N: usize>(control_block: &C, nonce_and_counter: [W; N], buf: &mut [u8])
 where
     C: BlockCipher<W, N>,
     W: Word,
 {
-    let mut prev = iv;
+    ctr_key_stream(control_block, nonce_and_counter, buf, |counter| {
+        counter[N - 1] = counter[N - 1].wrapping_add(W::from_u8(1))
+    });
+}
+
+/// XORs the CTR key-stream into `buf`, the counter block is stepped with
+/// `increment` after every block.
+fn ctr_key_stream<C, W, const N: usize>(
+    control_block: &C,
+    mut nonce_and_counter: [W; N],
+    buf: &mut [u8],
+    increment: impl Fn(&mut [W; N]),
+) where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let bs = control_block.block_size();
+    let mut batch = [[W::ZERO; N]; BATCH_BLOCKS];
 
-    input_blocks
-        .iter()
-        .map(|block| {
-            prev.iter_mut()
-                .enumerate()
-                .for_each(|(ix, word)| *word = *word ^ block[ix]);
-            let ct = control_block.encrypt(prev);
-            prev = ct;
+    for chunk in buf.chunks_mut(bs * BATCH_BLOCKS) {
+        let key_stream = &mut batch[..chunk.len().div_ceil(bs)];
+        for block in key_stream.iter_mut() {
+            *block = nonce_and_counter;
+            increment(&mut nonce_and_counter);
+        }
+
+        control_block.encrypt_blocks(key_stream);
 
-            ct
-        })
-        .collect()
+        for (block, bytes) in key_stream.iter().zip(chunk.chunks_mut(bs)) {
+            xor_block_into_bytes(block, bytes);
+        }
+    }
 }
 
-/// Decrypt in CBC mode.
+/// Apply the OFB mode key-stream to a byte stream in place (stream cipher).
+///
+/// Encryption and decryption are the same operation, every key-stream
+/// block is the encryption of the previous one, starting from the iv.
 ///
 /// # Parameters
 /// - `control_block`: the underlying block cipher instance.  
 /// - `iv`: Initialization Vector (`[W; N]`).  
-/// - `input_blocks`: vector of full `[W; N]` ciphertext blocks.
+/// - `buf`: bytes to encrypt or decrypt (any length), overwritten by
+///   the result.
+pub fn ofb_apply<C, W, const N: usize>(control_block: &C, iv: [W; N], buf: &mut [u8])
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let mut key_stream = iv;
+
+    for chunk in buf.chunks_mut(control_block.block_size()) {
+        key_stream = control_block.encrypt(key_stream);
+        xor_block_into_bytes(&key_stream, chunk);
+    }
+}
+
+/// Encrypt a byte stream in place in EAX mode, and returns the full
+/// authentication tag.
+///
+/// The counter of the CTR key-stream is the OMAC of the nonce, and is
+/// incremented as a big-endian integer over the whole block, as in the
+/// EAX specification.
 ///
-/// # Returns
-/// A vector of `[W; N]` plaintext blocks.
-pub fn cbc_decrypt<C, W, const N: usize>(
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `nonce`: nonce block (`[W; N]`), never reused under the same key.  
+/// - `ad`: associated data, authenticated but not encrypted.  
+/// - `buf`: plaintext bytes (any length), overwritten by the ciphertext.
+pub fn eax_encrypt<C, W, const N: usize>(
     control_block: &C,
-    iv: [W; N],
-    input_blocks: Vec<[W; N]>,
-) -> Vec<[W; N]>
+    nonce: [W; N],
+    ad: &[u8],
+    buf: &mut [u8],
+) -> [W; N]
 where
     C: BlockCipher<W, N>,
     W: Word,
 {
-    let mut prev = iv;
+    let counter = omac(control_block, 0, |mac| mac.update_block(&nonce));
+    ctr_key_stream(control_block, counter, buf, increment_be);
+
+    eax_tag(control_block, counter, ad, buf)
+}
+
+/// Verify the authentication tag of a byte stream and decrypt it in place
+/// in EAX mode.
+///
+/// The tag is checked in constant time before any byte of `buf` is
+/// decrypted, `buf` is left untouched on failure.
+///
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `nonce`: nonce block (`[W; N]`).  
+/// - `ad`: associated data.  
+/// - `buf`: ciphertext bytes (any length), overwritten by the plaintext.  
+/// - `tag`: the leading bytes of the authentication tag.
+pub fn eax_decrypt<C, W, const N: usize>(
+    control_block: &C,
+    nonce: [W; N],
+    ad: &[u8],
+    buf: &mut [u8],
+    tag: &[u8],
+) -> Result<(), Reason>
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let counter = omac(control_block, 0, |mac| mac.update_block(&nonce));
+    let expected = eax_tag(control_block, counter, ad, buf);
+    bail!(
+        !block_matches_bytes(&expected, tag),
+        Reason::AuthenticationFailed
+    );
+
+    ctr_key_stream(control_block, counter, buf, increment_be);
+    Ok(())
+}
+
+/// EAX tag of the cipher-text `ct`, the XOR of the OMACs of the nonce,
+/// the associated data and the cipher-text.
+fn eax_tag<C, W, const N: usize>(control_block: &C, counter: [W; N], ad: &[u8], ct: &[u8]) -> [W; N]
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let header = omac(control_block, 1, |mac| mac.update(ad));
+    let message = omac(control_block, 2, |mac| mac.update(ct));
+
+    xor_blocks(xor_blocks(counter, &header), &message)
+}
 
-    input_blocks
-        .iter()
-        .map(|block| {
-            let mut decrypted = control_block.decrypt(*block);
-            prev.iter_mut()
-                .enumerate()
-                .for_each(|(ix, word)| decrypted[ix] = decrypted[ix] ^ *word);
+/// The tweaked OMAC of EAX, the CMAC of the block `[tweak]_n`, i.e. the
+/// tweak in the last byte of a zero block, followed by the message fed
+/// by `message`.
+fn omac<C, W, const N: usize>(
+    control_block: &C,
+    tweak: u8,
+    message: impl FnOnce(&mut Cmac<'_, C, W, N>),
+) -> [W; N]
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let mut last = W::Bytes::default();
+    last.as_mut()[W::BYTES - 1] = tweak;
+    let mut tweak_block = [W::ZERO; N];
+    tweak_block[N - 1] = W::from_bytes(last);
 
-            prev = *block;
-            decrypted
-        })
-        .collect()
+    let mut mac = Cmac::new(control_block);
+    mac.update_block(&tweak_block);
+    message(&mut mac);
+    mac.finalize()
 }
 
-/// Encrypt a byte stream in CTR mode (stream cipher).
+/// Encrypt a byte stream in place in CCM mode, and returns the encrypted
+/// CBC-MAC block, whose leading `tag_len` bytes are the tag.
+///
+/// The message length must fit in `length_size` bytes, and the tag
+/// length is encoded in the first block, so truncated tags are not
+/// prefixes of each other.
 ///
 /// # Parameters
 /// - `control_block`: the underlying block cipher instance.  
-/// - `nonce_and_counter`: initial counter block (`[W; N]`).  
-/// - `input_stream`: plaintext bytes to encrypt (any length).
+/// - `nonce`: nonce block (`[W; N]`), its leading `bs - 1 - length_size`
+///   bytes are the nonce, never reused under the same key.  
+/// - `length_size`: size in bytes of the message length field.  
+/// - `tag_len`: tag length in bytes.  
+/// - `ad`: associated data, authenticated but not encrypted.  
+/// - `buf`: plaintext bytes (any length), overwritten by the ciphertext.
+pub fn ccm_encrypt<C, W, const N: usize>(
+    control_block: &C,
+    nonce: [W; N],
+    length_size: usize,
+    tag_len: usize,
+    ad: &[u8],
+    buf: &mut [u8],
+) -> [W; N]
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let tag = ccm_mac(control_block, nonce, length_size, tag_len, ad, buf);
+    ccm_ctr(control_block, nonce, length_size, tag, buf)
+}
+
+/// Decrypt a byte stream in place in CCM mode and verify its
+/// authentication tag.
+///
+/// The tag is checked in constant time, on failure the key-stream is
+/// applied again so that `buf` is left untouched.
 ///
-/// # Returns
-/// A `Vec<u8>` ciphertext stream, same length as input.
-pub fn ctr_encrypt<C, W, const N: usize>(
+/// # Parameters
+/// - `control_block`: the underlying block cipher instance.  
+/// - `nonce`: nonce block (`[W; N]`).  
+/// - `length_size`: size in bytes of the message length field.  
+/// - `ad`: associated data.  
+/// - `buf`: ciphertext bytes (any length), overwritten by the plaintext.  
+/// - `tag`: the authentication tag.
+pub fn ccm_decrypt<C, W, const N: usize>(
     control_block: &C,
-    mut nonce_and_counter: [W; N],
-    input_stream: &[u8],
-) -> Vec<u8>
+    nonce: [W; N],
+    length_size: usize,
+    ad: &[u8],
+    buf: &mut [u8],
+    tag: &[u8],
+) -> Result<(), Reason>
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let zero = [W::ZERO; N];
+    let key_stream = ccm_ctr(control_block, nonce, length_size, zero, buf);
+
+    let expected = ccm_mac(control_block, nonce, length_size, tag.len(), ad, buf);
+    let authentic = block_matches_bytes(&xor_blocks(expected, &key_stream), tag);
+    if !authentic {
+        ccm_ctr(control_block, nonce, length_size, zero, buf);
+    }
+
+    bail!(!authentic, Reason::AuthenticationFailed);
+    Ok(())
+}
+
+/// CBC-MAC of the first block `B_0`, the length prefixed and zero padded
+/// associated data and the zero padded plain-text.
+fn ccm_mac<C, W, const N: usize>(
+    control_block: &C,
+    nonce: [W; N],
+    length_size: usize,
+    tag_len: usize,
+    ad: &[u8],
+    pt: &[u8],
+) -> [W; N]
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let adata = if ad.is_empty() { 0 } else { 0x40 };
+    let flags = adata | (((tag_len - 2) / 2) << 3) as u8 | (length_size - 1) as u8;
+
+    let mut mac = CbcMac::new(control_block);
+    mac.update_block(&ccm_block(flags, nonce, length_size, pt.len()));
+
+    if !ad.is_empty() {
+        let len = ad.len();
+        match u32::try_from(len) {
+            Ok(len) if len < 0xff00 => mac.update(&(len as u16).to_be_bytes()),
+            Ok(len) => {
+                mac.update(&[0xff, 0xfe]);
+                mac.update(&len.to_be_bytes());
+            }
+            Err(_) => {
+                mac.update(&[0xff, 0xff]);
+                mac.update(&(len as u64).to_be_bytes());
+            }
+        }
+        mac.update(ad);
+        mac.pad();
+    }
+
+    mac.update(pt);
+    mac.finalize()
+}
+
+/// Applies the CCM key-stream, from the counter block `A_1`, to `buf`,
+/// and returns `tag` encrypted with the key-stream block of `A_0`.
+fn ccm_ctr<C, W, const N: usize>(
+    control_block: &C,
+    nonce: [W; N],
+    length_size: usize,
+    tag: [W; N],
+    buf: &mut [u8],
+) -> [W; N]
 where
     C: BlockCipher<W, N>,
     W: Word,
 {
-    let mut ciphered_stream = vec![];
+    let mut counter = ccm_block((length_size - 1) as u8, nonce, length_size, 0);
+    let tag = xor_blocks(tag, &control_block.encrypt(counter));
+
+    // The message length fits the counter field, it never carries into
+    // the nonce.
+    increment_be(&mut counter);
+    ctr_key_stream(control_block, counter, buf, increment_be);
 
-    for input_chunk in input_stream.chunks(control_block.block_size()) {
-        let encrypted = control_block.encrypt(nonce_and_counter);
-        let key_stream = encrypted
-            .iter()
-            .flat_map(|word| word.to_bytes_slice())
-            .collect::<Vec<_>>();
+    tag
+}
+
+/// A CCM block, the flags byte, the nonce, and `value` as a big-endian
+/// integer in the last `length_size` bytes.
+fn ccm_block<W: Word, const N: usize>(
+    flags: u8,
+    nonce: [W; N],
+    length_size: usize,
+    value: usize,
+) -> [W; N] {
+    let bs = N * W::BYTES;
+    let nonce = nonce.map(W::to_bytes);
+    let value = (value as u64).to_be_bytes();
 
-        for (ix, input) in input_chunk.iter().enumerate() {
-            ciphered_stream.push(*input ^ key_stream[ix]);
+    from_fn(|wx| {
+        let mut word = W::Bytes::default();
+        for (jx, b) in word.as_mut().iter_mut().enumerate() {
+            let ix = wx * W::BYTES + jx;
+            *b = match ix {
+                0 => flags,
+                _ if ix < bs - length_size => {
+                    nonce[(ix - 1) / W::BYTES].as_ref()[(ix - 1) % W::BYTES]
+                }
+                _ => value[ix + 8 - bs],
+            };
         }
-        nonce_and_counter[N - 1] = nonce_and_counter[N - 1].wrapping_add(W::from_u8(1));
+        W::from_bytes(word)
+    })
+}
+
+/// Encrypt a byte stream in place with SIV (RFC 5297), and returns the
+/// synthetic iv, the S2V of the associated data components and the
+/// plain-text, which authenticates them and seeds the CTR key-stream.
+///
+/// # Parameters
+/// - `mac_block`: the block cipher instance keyed with the S2V half of
+///   the key (`K1`).  
+/// - `ctr_block`: the block cipher instance keyed with the CTR half of
+///   the key (`K2`).  
+/// - `ad`: associated data components, authenticated but not encrypted.  
+/// - `buf`: plaintext bytes (any length), overwritten by the ciphertext.
+pub fn siv_encrypt<C, W, const N: usize>(
+    mac_block: &C,
+    ctr_block: &C,
+    ad: &[&[u8]],
+    buf: &mut [u8],
+) -> [W; N]
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let iv = s2v(mac_block, ad, buf);
+    ctr_key_stream(ctr_block, siv_counter(iv), buf, increment_be);
+
+    iv
+}
+
+/// Decrypt a byte stream in place with SIV (RFC 5297) and verify its
+/// synthetic iv.
+///
+/// The iv is checked in constant time, on failure the key-stream is
+/// applied again so that `buf` is left untouched.
+///
+/// # Parameters
+/// - `mac_block`: the block cipher instance keyed with `K1`.  
+/// - `ctr_block`: the block cipher instance keyed with `K2`.  
+/// - `ad`: associated data components.  
+/// - `iv`: the synthetic iv preceding the ciphertext.  
+/// - `buf`: ciphertext bytes (any length), overwritten by the plaintext.
+pub fn siv_decrypt<C, W, const N: usize>(
+    mac_block: &C,
+    ctr_block: &C,
+    ad: &[&[u8]],
+    iv: [W; N],
+    buf: &mut [u8],
+) -> Result<(), Reason>
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let counter = siv_counter(iv);
+    ctr_key_stream(ctr_block, counter, buf, increment_be);
+
+    let authentic = blocks_match(&s2v(mac_block, ad, buf), &iv);
+    if !authentic {
+        ctr_key_stream(ctr_block, counter, buf, increment_be);
+    }
+
+    bail!(!authentic, Reason::AuthenticationFailed);
+    Ok(())
+}
+
+/// S2V of RFC 5297, a CMAC based PRF of a vector of strings, the
+/// associated data components followed by the plain-text.
+fn s2v<C, W, const N: usize>(control_block: &C, ad: &[&[u8]], pt: &[u8]) -> [W; N]
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let bs = N * W::BYTES;
+    let cmac = |data: &[u8]| {
+        let mut mac = Cmac::new(control_block);
+        mac.update(data);
+        mac.finalize()
+    };
+
+    let mut d = {
+        let mut mac = Cmac::new(control_block);
+        mac.update_block(&[W::ZERO; N]);
+        mac.finalize()
+    };
+    for component in ad {
+        d = xor_blocks(dbl(&d), &cmac(component));
     }
-    ciphered_stream
+
+    let mut mac = Cmac::new(control_block);
+    if pt.len() >= bs {
+        // The last block of the plain-text is xor-ed with `D`, "xorend".
+        let (head, tail) = pt.split_at(pt.len() - bs);
+        mac.update(head);
+        mac.update_block(&xor_blocks(block_from_bytes(tail), &d));
+    } else {
+        mac.update_block(&xor_blocks(dbl(&d), &padded_block(pt)));
+    }
+
+    mac.finalize()
+}
+
+/// Loads less than a block of bytes followed by a `0x80` byte and zeros,
+/// the `10*` padding of SIV and OCB.
+fn padded_block<W: Word, const N: usize>(bytes: &[u8]) -> [W; N] {
+    from_fn(|wx| {
+        let mut word = W::Bytes::default();
+        for (jx, b) in word.as_mut().iter_mut().enumerate() {
+            let ix = wx * W::BYTES + jx;
+            *b = match ix.cmp(&bytes.len()) {
+                Ordering::Less => bytes[ix],
+                Ordering::Equal => 0x80,
+                Ordering::Greater => 0,
+            };
+        }
+        W::from_bytes(word)
+    })
 }
 
-/// Decrypt a byte stream in CTR mode (identical to encryption).
+/// The initial counter block of SIV, the synthetic iv with the top bit of
+/// its last two 32-bit words cleared, bits 63 and 31 of RFC 5297, so that
+/// implementations with 32-bit counters do not carry over. The same two
+/// bits are cleared for every block size, all of a 64-bit block.
+fn siv_counter<W: Word, const N: usize>(iv: [W; N]) -> [W; N] {
+    let bs = N * W::BYTES;
+    let mut bytes = iv.map(W::to_bytes);
+    let mut clear_top_bit = |ix: usize| bytes[ix / W::BYTES].as_mut()[ix % W::BYTES] &= 0x7f;
+
+    clear_top_bit(bs - 8);
+    clear_top_bit(bs - 4);
+
+    bytes.map(W::from_bytes)
+}
+
+/// Derive the GCM pre-counter block `J_0` of a nonce, `nonce || 0^31 || 1`
+/// for 96-bit nonces, and the GHASH of the zero padded nonce followed by
+/// its length in bits otherwise.
 ///
 /// # Parameters
-/// - `control_block`: the underlying block cipher instance.  
-/// - `nonce_and_counter`: same initial counter block used in encryption.  
-/// - `input_stream`: ciphertext bytes to decrypt (any length).
+/// - `control_block`: the underlying 128-bit block cipher instance.  
+/// - `nonce`: nonce bytes, at least one byte long.
+pub fn gcm_nonce<C>(control_block: &C, nonce: &[u8]) -> GcmNonce
+where
+    C: BlockCipher<u64, 2>,
+{
+    let pre_counter = match nonce.len() {
+        12 => {
+            let mut bytes = [0u8; 16];
+            bytes[..12].copy_from_slice(nonce);
+            bytes[15] = 1;
+            block_from_bytes(&bytes)
+        }
+        len => {
+            let mut ghash = Ghash::new(ghash_key(control_block));
+            ghash.update(nonce);
+            ghash.pad();
+            ghash.update(&[0; 8]);
+            ghash.update(&(8 * len as u64).to_be_bytes());
+            u128_to_block(ghash.finalize())
+        }
+    };
+
+    GcmNonce { pre_counter }
+}
+
+/// Encrypt a byte stream in place in GCM mode, and returns the full
+/// authentication tag.
 ///
-/// # Returns
-/// A `Vec<u8>` plaintext stream.
-pub fn ctr_decrypt<C, W, const N: usize>(
+/// # Parameters
+/// - `control_block`: the underlying 128-bit block cipher instance.  
+/// - `nonce`: GCM nonce, never reused under the same key.  
+/// - `ad`: associated data, authenticated but not encrypted.  
+/// - `buf`: plaintext bytes (any length), overwritten by the ciphertext.
+pub fn gcm_encrypt<C>(control_block: &C, nonce: GcmNonce, ad: &[u8], buf: &mut [u8]) -> [u64; 2]
+where
+    C: BlockCipher<u64, 2>,
+{
+    let mut counter = nonce.pre_counter;
+    increment_be32(&mut counter);
+    ctr_key_stream(control_block, counter, buf, increment_be32);
+
+    gcm_tag(control_block, nonce, ad, buf)
+}
+
+/// Verify the authentication tag of a byte stream and decrypt it in place
+/// in GCM mode.
+///
+/// The tag is checked in constant time before any byte of `buf` is
+/// decrypted, `buf` is left untouched on failure.
+///
+/// # Parameters
+/// - `control_block`: the underlying 128-bit block cipher instance.  
+/// - `nonce`: GCM nonce.  
+/// - `ad`: associated data.  
+/// - `buf`: ciphertext bytes (any length), overwritten by the plaintext.  
+/// - `tag`: the leading bytes of the authentication tag.
+pub fn gcm_decrypt<C>(
+    control_block: &C,
+    nonce: GcmNonce,
+    ad: &[u8],
+    buf: &mut [u8],
+    tag: &[u8],
+) -> Result<(), Reason>
+where
+    C: BlockCipher<u64, 2>,
+{
+    let expected = gcm_tag(control_block, nonce, ad, buf);
+    bail!(
+        !block_matches_bytes(&expected, tag),
+        Reason::AuthenticationFailed
+    );
+
+    let mut counter = nonce.pre_counter;
+    increment_be32(&mut counter);
+    ctr_key_stream(control_block, counter, buf, increment_be32);
+    Ok(())
+}
+
+/// GCM tag of the cipher-text `ct`, the GHASH of the zero padded
+/// associated data and cipher-text followed by their lengths in bits,
+/// encrypted with the key-stream block of `J_0`.
+fn gcm_tag<C>(control_block: &C, nonce: GcmNonce, ad: &[u8], ct: &[u8]) -> [u64; 2]
+where
+    C: BlockCipher<u64, 2>,
+{
+    let mut ghash = Ghash::new(ghash_key(control_block));
+    ghash.update(ad);
+    ghash.pad();
+    ghash.update(ct);
+    ghash.pad();
+    ghash.update(&(8 * ad.len() as u64).to_be_bytes());
+    ghash.update(&(8 * ct.len() as u64).to_be_bytes());
+
+    let hash = u128_to_block(ghash.finalize());
+    xor_blocks(hash, &control_block.encrypt(nonce.pre_counter))
+}
+
+/// The hash subkey `H` of GHASH, the encryption of the zero block.
+fn ghash_key<C>(control_block: &C) -> u128
+where
+    C: BlockCipher<u64, 2>,
+{
+    let mut bytes = [0u8; 16];
+    block_to_bytes(&control_block.encrypt([0; 2]), &mut bytes);
+    u128::from_be_bytes(bytes)
+}
+
+/// Loads a GHASH value into a block, its big-endian bytes being the byte
+/// string of the block.
+fn u128_to_block(value: u128) -> [u64; 2] {
+    block_from_bytes(&value.to_be_bytes())
+}
+
+/// Encrypt a byte stream in place in OCB mode, and returns the full
+/// authentication tag.
+///
+/// Each block is encrypted once between two XORs with an offset, which
+/// moves along the [OcbOffsets] from block to block. The checksum of the
+/// plain-text blocks is encrypted into the tag, along with the hash of the
+/// associated data.
+///
+/// # Parameters
+/// - `control_block`: the underlying 64 or 128-bit block cipher instance.  
+/// - `nonce`: nonce block (`[W; N]`), its leading `nonce_len` bytes are
+///   the nonce, never reused under the same key.  
+/// - `nonce_len`: nonce length in bytes, less than the block size.  
+/// - `tag_len`: tag length in bytes.  
+/// - `ad`: associated data, authenticated but not encrypted.  
+/// - `buf`: plaintext bytes (any length), overwritten by the ciphertext.
+pub fn ocb_encrypt<C, W, const N: usize>(
+    control_block: &C,
+    nonce: [W; N],
+    nonce_len: usize,
+    tag_len: usize,
+    ad: &[u8],
+    buf: &mut [u8],
+) -> [W; N]
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let offsets = OcbOffsets::new(control_block, ad.len().max(buf.len()));
+    let offset = ocb_nonce_offset(control_block, nonce, nonce_len, tag_len);
+    let (offset, checksum) = ocb_apply(control_block, &offsets, offset, buf, true);
+
+    ocb_tag(control_block, &offsets, offset, checksum, ad)
+}
+
+/// Decrypt a byte stream in place in OCB mode and verify its
+/// authentication tag.
+///
+/// The tag covers the checksum of the plain-text, so it is checked in
+/// constant time after decryption, on failure the blocks are encrypted
+/// again so that `buf` is left untouched.
+///
+/// # Parameters
+/// - `control_block`: the underlying 64 or 128-bit block cipher instance.  
+/// - `nonce`: nonce block (`[W; N]`).  
+/// - `nonce_len`: nonce length in bytes.  
+/// - `ad`: associated data.  
+/// - `buf`: ciphertext bytes (any length), overwritten by the plaintext.  
+/// - `tag`: the authentication tag.
+pub fn ocb_decrypt<C, W, const N: usize>(
+    control_block: &C,
+    nonce: [W; N],
+    nonce_len: usize,
+    ad: &[u8],
+    buf: &mut [u8],
+    tag: &[u8],
+) -> Result<(), Reason>
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let offsets = OcbOffsets::new(control_block, ad.len().max(buf.len()));
+    let offset = ocb_nonce_offset(control_block, nonce, nonce_len, tag.len());
+    let (last, checksum) = ocb_apply(control_block, &offsets, offset, buf, false);
+
+    let expected = ocb_tag(control_block, &offsets, last, checksum, ad);
+    let authentic = block_matches_bytes(&expected, tag);
+    if !authentic {
+        ocb_apply(control_block, &offsets, offset, buf, true);
+    }
+
+    bail!(!authentic, Reason::AuthenticationFailed);
+    Ok(())
+}
+
+/// # OcbOffsets
+///
+/// The key dependent offsets of OCB, precomputed from the encryption of
+/// the zero block: `L_*`, `L_$ = dbl(L_*)`, and `L_i = dbl(L_{i-1})` from
+/// `L_0 = dbl(L_$)`. Block `i` of a message moves the offset by
+/// `L_ntz(i)`, so a message of `m` blocks only needs `L_0` to
+/// `L_log2(m)`.
+struct OcbOffsets<W: Word, const N: usize> {
+    star: [W; N],
+    dollar: [W; N],
+    table: [[W; N]; usize::BITS as usize],
+}
+
+impl<W: Word, const N: usize> OcbOffsets<W, N> {
+    /// Precomputes the offsets of messages of up to `len` bytes.
+    fn new<C>(control_block: &C, len: usize) -> Self
+    where
+        C: BlockCipher<W, N>,
+    {
+        let star = control_block.encrypt([W::ZERO; N]);
+        let dollar = dbl(&star);
+
+        let blocks = len / (N * W::BYTES);
+        let mut table = [[W::ZERO; N]; usize::BITS as usize];
+        let mut offset = dbl(&dollar);
+        for entry in &mut table[..(usize::BITS - blocks.leading_zeros()) as usize] {
+            *entry = offset;
+            offset = dbl(&offset);
+        }
+
+        Self {
+            star,
+            dollar,
+            table,
+        }
+    }
+
+    /// `L_ntz(index)`, the offset increment of the block `index`, from 1.
+    fn increment(&self, index: usize) -> &[W; N] {
+        &self.table[index.trailing_zeros() as usize]
+    }
+}
+
+/// The stretch shift and the number of bottom bits of the OCB nonce,
+/// `(8, 6)` for 128-bit blocks as in RFC 7253, and `(25, 5)` for 64-bit
+/// blocks from the 64-bit row of the parameter table of T. Krovetz,
+/// "OCB For Block Ciphers Without 128-Bit Blocks",
+/// draft-krovetz-ocb-wideblock-00.
+fn ocb_stretch(bs: usize) -> (usize, usize) {
+    match bs {
+        8 => (25, 5),
+        _ => (8, 6),
+    }
+}
+
+/// The initial offset `Offset_0` of OCB. The nonce is formatted as
+/// `[tag_len * 8 mod n]_7 || 0* || 1 || nonce`, its bottom bits are
+/// cleared and encrypted into `Ktop`, and the offset is the window of
+/// `Ktop || (Ktop ^ (Ktop << shift))` starting at the bottom bits.
+fn ocb_nonce_offset<C, W, const N: usize>(
+    control_block: &C,
+    nonce: [W; N],
+    nonce_len: usize,
+    tag_len: usize,
+) -> [W; N]
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let bs = N * W::BYTES;
+    let (shift, bottom_bits) = ocb_stretch(bs);
+    let mask = (1u8 << bottom_bits) - 1;
+
+    let nonce = nonce.map(W::to_bytes);
+    let nonce_byte = |ix: usize| nonce[ix / W::BYTES].as_ref()[ix % W::BYTES];
+    let start = bs - nonce_len;
+    let tag_bits = ((8 * tag_len) % (8 * bs)) as u8;
+
+    let mut bottom = 0;
+    let top = from_fn(|wx| {
+        let mut word = W::Bytes::default();
+        for (jx, b) in word.as_mut().iter_mut().enumerate() {
+            let ix = wx * W::BYTES + jx;
+            if ix >= start {
+                *b = nonce_byte(ix - start);
+            }
+            if ix == start - 1 {
+                *b |= 1;
+            }
+            if ix == 0 {
+                *b |= tag_bits << 1;
+            }
+            if ix == bs - 1 {
+                bottom = (*b & mask) as usize;
+                *b &= !mask;
+            }
+        }
+        W::from_bytes(word)
+    });
+
+    let ktop = control_block.encrypt(top).map(W::to_bytes);
+    let ktop_byte = |ix: usize| {
+        if ix < bs {
+            ktop[ix / W::BYTES].as_ref()[ix % W::BYTES]
+        } else {
+            0
+        }
+    };
+    let stretch_byte = |ix: usize| {
+        if ix < bs {
+            ktop_byte(ix)
+        } else {
+            ktop_byte(ix - bs) ^ shifted_byte(ktop_byte, ix - bs, shift)
+        }
+    };
+
+    from_fn(|wx| {
+        let mut word = W::Bytes::default();
+        for (jx, b) in word.as_mut().iter_mut().enumerate() {
+            *b = shifted_byte(stretch_byte, wx * W::BYTES + jx, bottom);
+        }
+        W::from_bytes(word)
+    })
+}
+
+/// Byte `ix` of the bit string of `bytes` shifted left by `bits`.
+fn shifted_byte(bytes: impl Fn(usize) -> u8, ix: usize, bits: usize) -> u8 {
+    let at = ix + bits / 8;
+    let pair = (bytes(at) as u16) << 8 | bytes(at + 1) as u16;
+    (pair >> (8 - bits % 8)) as u8
+}
+
+/// Encrypts, or decrypts, the blocks of `buf` in place from the offset
+/// `offset`, and returns the offset of the last block and the checksum of
+/// the plain-text. A final partial block is XOR-ed with the encryption of
+/// its offset.
+fn ocb_apply<C, W, const N: usize>(
+    control_block: &C,
+    offsets: &OcbOffsets<W, N>,
+    mut offset: [W; N],
+    buf: &mut [u8],
+    encrypt: bool,
+) -> ([W; N], [W; N])
+where
+    C: BlockCipher<W, N>,
+    W: Word,
+{
+    let bs = N * W::BYTES;
+    let mut checksum = [W::ZERO; N];
+
+    let mut chunks = buf.chunks_exact_mut(bs);
+    for (index, chunk) in (1..).zip(&mut chunks) {
+        offset = xor_blocks(offset, offsets.increment(index));
+        let block = xor_blocks(block_from_bytes(chunk), &offset);
+
+        let block = if encrypt {
+            checksum = xor_blocks(checksum, &block_from_bytes(chunk));
+            xor_blocks(control_block.encrypt(block), &offset)
+        } else {
+            let pt = xor_blocks(control_block.decrypt(block), &offset);
+            checksum = xor_blocks(checksum, &pt);
+            pt
+        };
+        block_to_bytes(&block, chunk);
+    }
+
+    let tail = chunks.into_remainder();
+    if !tail.is_empty() {
+        offset = xor_blocks(offset, &offsets.star);
+        let pad = control_block.encrypt(offset);
+
+        if encrypt {
+            checksum = xor_blocks(checksum, &padded_block(tail));
+            xor_block_into_bytes(&pad, tail);
+        } else {
+            xor_block_into_bytes(&pad, tail);
+            checksum = xor_blocks(checksum, &padded_block(tail));
+        }
+    }
+
+    (offset, checksum)
+}
+
+/// OCB tag, the encryption of the checksum XOR-ed with the last offset
+/// and `L_$`, XOR-ed with the hash of the associated data.
+fn ocb_tag<C, W, const N: usize>(
     control_block: &C,
-    nonce_and_counter: [W; N],
-    input_blocks: &[u8],
-) -> Vec<u8>
+    offsets: &OcbOffsets<W, N>,
+    offset: [W; N],
+    checksum: [W; N],
+    ad: &[u8],
+) -> [W; N]
 where
     C: BlockCipher<W, N>,
     W: Word,
 {
-    // Counter mode decryption is vice versa of counter mode encryption.
-    // A cipher text can be decrypted by reeating the encryption with same
-    // parameter configs.
-    ctr_encrypt(control_block, nonce_and_counter, input_blocks)
+    let bs = N * W::BYTES;
+    let mut sum = [W::ZERO; N];
+    let mut offset_ad = [W::ZERO; N];
+
+    let mut chunks = ad.chunks_exact(bs);
+    for (index, chunk) in (1..).zip(&mut chunks) {
+        offset_ad = xor_blocks(offset_ad, offsets.increment(index));
+        let block = xor_blocks(block_from_bytes(chunk), &offset_ad);
+        sum = xor_blocks(sum, &control_block.encrypt(block));
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        offset_ad = xor_blocks(offset_ad, &offsets.star);
+        let block = xor_blocks(padded_block(tail), &offset_ad);
+        sum = xor_blocks(sum, &control_block.encrypt(block));
+    }
+
+    let tag = xor_blocks(xor_blocks(checksum, &offset), &offsets.dollar);
+    xor_blocks(control_block.encrypt(tag), &sum)
+}
+
+/// Increments a counter block as a big-endian integer over its bytes,
+/// wrapping around at the block size.
+fn increment_be<W: Word, const N: usize>(counter: &mut [W; N]) {
+    increment_be_tail(counter, N * W::BYTES);
+}
+
+/// Increments the last 32 bits of a counter block as a big-endian
+/// integer, `inc_32` of GCM.
+fn increment_be32<W: Word, const N: usize>(counter: &mut [W; N]) {
+    increment_be_tail(counter, 4);
+}
+
+/// Increments the last `width` bytes of a counter block as a big-endian
+/// integer, wrapping around without carrying into the leading bytes.
+fn increment_be_tail<W: Word, const N: usize>(counter: &mut [W; N], width: usize) {
+    let bs = N * W::BYTES;
+    let mut bytes = counter.map(W::to_bytes);
+
+    for ix in (bs - width..bs).rev() {
+        let byte = &mut bytes[ix / W::BYTES].as_mut()[ix % W::BYTES];
+        *byte = byte.wrapping_add(1);
+        if *byte != 0 {
+            break;
+        }
+    }
+
+    *counter = bytes.map(W::from_bytes);
 }
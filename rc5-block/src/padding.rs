@@ -0,0 +1,288 @@
+//! Padding schemes of the block modes of operation (ECB, CBC, PCBC and
+//! IGE), selected per operation with [Cipher::encrypt_with_padding](crate::Cipher::encrypt_with_padding)
+//! and [Cipher::decrypt_with_padding](crate::Cipher::decrypt_with_padding).
+//!
+//! Every scheme validates the padding it removes and fails with
+//! [Reason::Padding] on any mismatch, without telling which byte was wrong.
+use crate::{Reason, bail, utils};
+
+#[cfg(feature = "std")]
+use rand::{RngCore, thread_rng};
+
+/// # Padding
+///
+/// A padding scheme, filling the last block of a message up to the block
+/// size before encryption and removing it after decryption.
+pub trait Padding {
+    /// Length of a `len` bytes message once padded to a multiple of `bs`.
+    fn padded_len(&self, len: usize, bs: usize) -> usize;
+
+    /// Writes the padding of the first `len` bytes of `buf` into its
+    /// slack, without allocating.
+    ///
+    /// Fails with [Reason::BufferTooSmall] if `buf` cannot hold the padded
+    /// message, returns the padded length otherwise.
+    fn pad(&self, buf: &mut [u8], len: usize, bs: usize) -> Result<usize, Reason>;
+
+    /// Validates the padding at the end of `buf`, a whole number of
+    /// blocks, and returns the length of the message without it.
+    fn unpad(&self, buf: &[u8], bs: usize) -> Result<usize, Reason>;
+}
+
+/// PKCS#7 padding, `n` bytes of value `n`, and a whole block when the
+/// message is block aligned. The default padding of [Cipher](crate::Cipher).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pkcs7;
+
+/// ANSI X9.23 padding, zero bytes followed by the padding length, and a
+/// whole block when the message is block aligned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AnsiX923;
+
+/// ISO 10126 padding, random bytes followed by the padding length, and a
+/// whole block when the message is block aligned. The random bytes are
+/// not checked on removal.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Iso10126;
+
+/// ISO/IEC 7816-4 padding, a `0x80` byte followed by zero bytes, and a
+/// whole block when the message is block aligned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Iso7816;
+
+/// Zero padding, zero bytes up to the next block boundary and nothing
+/// when the message is block aligned.
+///
+/// Removal strips every trailing zero byte, so messages ending with zero
+/// bytes do not survive a round trip.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ZeroPadding;
+
+/// No padding, the message must be block aligned, otherwise padding
+/// fails with [Reason::Padding].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NoPadding;
+
+impl Padding for Pkcs7 {
+    fn padded_len(&self, len: usize, bs: usize) -> usize {
+        utils::pkcs7_padded_len(len, bs)
+    }
+
+    fn pad(&self, buf: &mut [u8], len: usize, bs: usize) -> Result<usize, Reason> {
+        utils::pkcs7_pad_in_place(buf, len, bs)
+    }
+
+    fn unpad(&self, buf: &[u8], bs: usize) -> Result<usize, Reason> {
+        utils::pkcs7_unpad_in_place(buf, bs)
+    }
+}
+
+impl Padding for AnsiX923 {
+    fn padded_len(&self, len: usize, bs: usize) -> usize {
+        utils::pkcs7_padded_len(len, bs)
+    }
+
+    fn pad(&self, buf: &mut [u8], len: usize, bs: usize) -> Result<usize, Reason> {
+        let required = slack(buf, self.padded_len(len, bs))?;
+
+        buf[len..required - 1].fill(0);
+        buf[required - 1] = (required - len) as u8;
+        Ok(required)
+    }
+
+    fn unpad(&self, buf: &[u8], bs: usize) -> Result<usize, Reason> {
+        let pad_len = length_byte(buf, bs)?;
+        let len = buf.len() - pad_len;
+
+        bail!(
+            !buf[len..buf.len() - 1].iter().all(|b| *b == 0),
+            Reason::Padding
+        );
+        Ok(len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Padding for Iso10126 {
+    fn padded_len(&self, len: usize, bs: usize) -> usize {
+        utils::pkcs7_padded_len(len, bs)
+    }
+
+    fn pad(&self, buf: &mut [u8], len: usize, bs: usize) -> Result<usize, Reason> {
+        let required = slack(buf, self.padded_len(len, bs))?;
+
+        thread_rng().fill_bytes(&mut buf[len..required - 1]);
+        buf[required - 1] = (required - len) as u8;
+        Ok(required)
+    }
+
+    fn unpad(&self, buf: &[u8], bs: usize) -> Result<usize, Reason> {
+        Ok(buf.len() - length_byte(buf, bs)?)
+    }
+}
+
+impl Padding for Iso7816 {
+    fn padded_len(&self, len: usize, bs: usize) -> usize {
+        utils::pkcs7_padded_len(len, bs)
+    }
+
+    fn pad(&self, buf: &mut [u8], len: usize, bs: usize) -> Result<usize, Reason> {
+        let required = slack(buf, self.padded_len(len, bs))?;
+
+        buf[len] = 0x80;
+        buf[len + 1..required].fill(0);
+        Ok(required)
+    }
+
+    fn unpad(&self, buf: &[u8], bs: usize) -> Result<usize, Reason> {
+        let len = buf.len();
+        bail!(len == 0 || !len.is_multiple_of(bs), Reason::Padding);
+
+        // The marker is within the last block, after the last non-zero byte.
+        let last_block = &buf[len - bs..];
+        match last_block.iter().rposition(|b| *b != 0) {
+            Some(ix) if last_block[ix] == 0x80 => Ok(len - bs + ix),
+            _ => Err(Reason::Padding),
+        }
+    }
+}
+
+impl Padding for ZeroPadding {
+    fn padded_len(&self, len: usize, bs: usize) -> usize {
+        len.next_multiple_of(bs)
+    }
+
+    fn pad(&self, buf: &mut [u8], len: usize, bs: usize) -> Result<usize, Reason> {
+        let required = slack(buf, self.padded_len(len, bs))?;
+
+        buf[len..required].fill(0);
+        Ok(required)
+    }
+
+    fn unpad(&self, buf: &[u8], bs: usize) -> Result<usize, Reason> {
+        bail!(!buf.len().is_multiple_of(bs), Reason::Padding);
+
+        Ok(buf.iter().rposition(|b| *b != 0).map_or(0, |ix| ix + 1))
+    }
+}
+
+impl Padding for NoPadding {
+    fn padded_len(&self, len: usize, _bs: usize) -> usize {
+        len
+    }
+
+    fn pad(&self, buf: &mut [u8], len: usize, bs: usize) -> Result<usize, Reason> {
+        bail!(!len.is_multiple_of(bs), Reason::Padding);
+        slack(buf, len)
+    }
+
+    fn unpad(&self, buf: &[u8], bs: usize) -> Result<usize, Reason> {
+        bail!(!buf.len().is_multiple_of(bs), Reason::Padding);
+        Ok(buf.len())
+    }
+}
+
+/// Checks that `buf` can hold the `required` bytes of the padded message.
+fn slack(buf: &[u8], required: usize) -> Result<usize, Reason> {
+    bail!(
+        required > buf.len(),
+        Reason::BufferTooSmall {
+            required,
+            available: buf.len()
+        }
+    );
+
+    Ok(required)
+}
+
+/// Reads the padding length stored in the last byte of `buf`, a whole
+/// number of blocks, for the length-terminated schemes.
+fn length_byte(buf: &[u8], bs: usize) -> Result<usize, Reason> {
+    let len = buf.len();
+    bail!(len == 0 || !len.is_multiple_of(bs), Reason::Padding);
+
+    let pad_len = buf[len - 1] as usize;
+    bail!(pad_len == 0 || pad_len > bs, Reason::Padding);
+
+    Ok(pad_len)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use super::Iso10126;
+    use super::{AnsiX923, Iso7816, NoPadding, Padding, Pkcs7, ZeroPadding};
+    use crate::Reason;
+
+    fn padded(padding: &impl Padding, msg: &[u8], bs: usize) -> Vec<u8> {
+        let mut buf = msg.to_vec();
+        buf.resize(padding.padded_len(msg.len(), bs), 0xaa);
+        let len = padding.pad(&mut buf, msg.len(), bs).unwrap();
+        assert_eq!(len, buf.len());
+        assert_eq!(msg.len(), padding.unpad(&buf, bs).unwrap());
+        buf
+    }
+
+    #[test]
+    fn padding_bytes() {
+        assert_eq!(padded(&Pkcs7, b"HELLO", 8), b"HELLO\x03\x03\x03");
+        assert_eq!(padded(&AnsiX923, b"HELLO", 8), b"HELLO\x00\x00\x03");
+        assert_eq!(padded(&Iso7816, b"HELLO", 8), b"HELLO\x80\x00\x00");
+        assert_eq!(padded(&ZeroPadding, b"HELLO", 8), b"HELLO\x00\x00\x00");
+    }
+
+    #[test]
+    fn aligned_messages() {
+        assert_eq!(
+            padded(&AnsiX923, b"8 bytes!", 8)[8..],
+            [0, 0, 0, 0, 0, 0, 0, 8]
+        );
+        assert_eq!(
+            padded(&Iso7816, b"8 bytes!", 8)[8..],
+            [0x80, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(padded(&ZeroPadding, b"8 bytes!", 8), b"8 bytes!");
+        assert_eq!(padded(&NoPadding, b"8 bytes!", 8), b"8 bytes!");
+        assert_eq!(padded(&ZeroPadding, b"", 8), b"");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn iso10126_padding() {
+        assert_eq!(padded(&Iso10126, b"HELLO", 8)[7], 3);
+        assert_eq!(padded(&Iso10126, b"8 bytes!", 8).len(), 16);
+        assert!(matches!(
+            Iso10126.unpad(b"HELLO\x00\x00\x00", 8),
+            Err(Reason::Padding)
+        ));
+    }
+
+    #[test]
+    fn invalid_padding_is_rejected() {
+        let bad = |padding: &dyn Padding, buf: &[u8]| {
+            matches!(padding.unpad(buf, 8), Err(Reason::Padding))
+        };
+
+        assert!(bad(&AnsiX923, b"HELLO\x00\x01\x03"));
+        assert!(bad(&AnsiX923, b"HELLO\x00\x00\x09"));
+        assert!(bad(&Iso7816, b"HELLO\x81\x00\x00"));
+        assert!(bad(&Iso7816, &[0u8; 8]));
+        assert!(bad(&Iso7816, b""));
+        assert!(bad(&NoPadding, b"HELLO"));
+        assert!(bad(&ZeroPadding, b"HELLO"));
+
+        let mut buf = *b"HELLO";
+        assert!(matches!(
+            NoPadding.pad(&mut buf, 5, 8),
+            Err(Reason::Padding)
+        ));
+        assert!(matches!(
+            Iso7816.pad(&mut buf, 5, 8),
+            Err(Reason::BufferTooSmall {
+                required: 8,
+                available: 5
+            })
+        ));
+    }
+}
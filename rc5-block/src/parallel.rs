@@ -0,0 +1,213 @@
+//! Multi-threaded execution of the modes of operation whose blocks can be
+//! processed independently: ECB, CTR and CBC decryption.
+//!
+//! The buffer is split into contiguous runs of whole blocks, one per thread,
+//! and each run is processed with the serial mode functions, so the output
+//! is byte-identical to the serial path.
+use alloc::vec::Vec;
+use core::{iter, num::NonZeroUsize};
+use std::thread;
+
+use crate::{BlockCipher, Word, modes, utils::block_from_bytes};
+
+/// Minimum number of blocks handed to a thread, smaller buffers are not
+/// worth the cost of spawning threads and are processed serially.
+const MIN_BLOCKS_PER_THREAD: usize = 4096;
+
+/// # Parallelism
+///
+/// Number of threads used by [Cipher](crate::Cipher) for ECB, CTR and CBC
+/// decryption, set with [Cipher::with_parallelism](crate::Cipher::with_parallelism).
+/// CBC encryption is inherently sequential and always runs on one thread.
+///
+/// Each thread processes at least 4096 blocks, so short messages are always
+/// processed serially.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Parallelism {
+    /// Process every block on the calling thread.
+    #[default]
+    Serial,
+
+    /// One thread per core, as reported by [std::thread::available_parallelism].
+    Auto,
+
+    /// A fixed number of threads.
+    Threads(NonZeroUsize),
+}
+
+impl Parallelism {
+    /// Returns the number of threads this setting resolves to.
+    pub fn threads(&self) -> usize {
+        match self {
+            Parallelism::Serial => 1,
+            Parallelism::Auto => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            Parallelism::Threads(threads) => threads.get(),
+        }
+    }
+
+    /// Number of blocks per thread for a buffer of `blocks` blocks.
+    fn blocks_per_thread(&self, blocks: usize) -> usize {
+        blocks.div_ceil(self.threads()).max(MIN_BLOCKS_PER_THREAD)
+    }
+}
+
+/// # Kernels
+///
+/// The multi-threaded modes of a control block along with their
+/// [Parallelism]. They are instantiated where the control block is known
+/// to be `Sync`, by [Cipher::with_parallelism](crate::Cipher::with_parallelism),
+/// so the rest of the [Cipher](crate::Cipher) API does not require it.
+pub(crate) struct Kernels<C, W, const N: usize> {
+    pub(crate) parallelism: Parallelism,
+    pub(crate) ecb_encrypt: fn(&C, &mut [u8], Parallelism),
+    pub(crate) ecb_decrypt: fn(&C, &mut [u8], Parallelism),
+    pub(crate) cbc_decrypt: fn(&C, [W; N], &mut [u8], Parallelism),
+    pub(crate) ctr_apply: fn(&C, [W; N], &mut [u8], Parallelism),
+}
+
+impl<C, W, const N: usize> Kernels<C, W, N>
+where
+    C: BlockCipher<W, N> + Sync,
+    W: Word,
+{
+    pub(crate) fn new(parallelism: Parallelism) -> Self {
+        Self {
+            parallelism,
+            ecb_encrypt,
+            ecb_decrypt,
+            cbc_decrypt,
+            ctr_apply,
+        }
+    }
+}
+
+/// Runs `process` over the runs of `run_bytes` bytes of `buf`, one per
+/// thread, along with the state of each run. A single run is processed
+/// on the calling thread.
+fn spawn_runs<T, F>(
+    buf: &mut [u8],
+    run_bytes: usize,
+    states: impl IntoIterator<Item = T>,
+    process: F,
+) where
+    T: Send,
+    F: Fn(T, &mut [u8]) + Sync,
+{
+    let mut states = states.into_iter();
+
+    if buf.len() <= run_bytes {
+        if let Some(state) = states.next() {
+            process(state, buf);
+        }
+        return;
+    }
+
+    thread::scope(|scope| {
+        for (run, state) in buf.chunks_mut(run_bytes).zip(states) {
+            let process = &process;
+            scope.spawn(move || process(state, run));
+        }
+    });
+}
+
+/// Encrypt a buffer of whole blocks in place in ECB mode on multiple threads.
+pub fn ecb_encrypt<C, W, const N: usize>(
+    control_block: &C,
+    buf: &mut [u8],
+    parallelism: Parallelism,
+) where
+    C: BlockCipher<W, N> + Sync,
+    W: Word,
+{
+    let bs = control_block.block_size();
+    let run_bytes = parallelism.blocks_per_thread(buf.len() / bs) * bs;
+
+    spawn_runs(buf, run_bytes, iter::repeat(()), |_, run| {
+        modes::ecb_encrypt(control_block, run)
+    });
+}
+
+/// Decrypt a buffer of whole blocks in place in ECB mode on multiple threads.
+pub fn ecb_decrypt<C, W, const N: usize>(
+    control_block: &C,
+    buf: &mut [u8],
+    parallelism: Parallelism,
+) where
+    C: BlockCipher<W, N> + Sync,
+    W: Word,
+{
+    let bs = control_block.block_size();
+    let run_bytes = parallelism.blocks_per_thread(buf.len() / bs) * bs;
+
+    spawn_runs(buf, run_bytes, iter::repeat(()), |_, run| {
+        modes::ecb_decrypt(control_block, run)
+    });
+}
+
+/// Decrypt a buffer of whole blocks in place in CBC mode on multiple threads.
+///
+/// The chaining block of every run, i.e. the last cipher-text block of the
+/// preceding run, is captured before any run is decrypted in place.
+pub fn cbc_decrypt<C, W, const N: usize>(
+    control_block: &C,
+    iv: [W; N],
+    buf: &mut [u8],
+    parallelism: Parallelism,
+) where
+    C: BlockCipher<W, N> + Sync,
+    W: Word,
+{
+    let bs = control_block.block_size();
+    let run_bytes = parallelism.blocks_per_thread(buf.len() / bs) * bs;
+
+    let ivs: Vec<[W; N]> = iter::once(iv)
+        .chain(
+            buf.chunks(run_bytes)
+                .map(|run| block_from_bytes(&run[run.len() - bs..])),
+        )
+        .collect();
+
+    spawn_runs(buf, run_bytes, ivs, |iv, run| {
+        modes::cbc_decrypt(control_block, iv, run)
+    });
+}
+
+/// Apply the CTR mode key-stream to a byte stream in place on multiple
+/// threads, the counter of every run is offset by its first block index.
+pub fn ctr_apply<C, W, const N: usize>(
+    control_block: &C,
+    nonce_and_counter: [W; N],
+    buf: &mut [u8],
+    parallelism: Parallelism,
+) where
+    C: BlockCipher<W, N> + Sync,
+    W: Word,
+{
+    let bs = control_block.block_size();
+    let blocks_per_thread = parallelism.blocks_per_thread(buf.len().div_ceil(bs));
+
+    let counters = (0..).map(|run: usize| {
+        let mut counter = nonce_and_counter;
+        let offset = W::from_u64((run * blocks_per_thread) as u64);
+        counter[N - 1] = counter[N - 1].wrapping_add(offset);
+        counter
+    });
+
+    spawn_runs(buf, blocks_per_thread * bs, counters, |counter, run| {
+        modes::ctr_apply(control_block, counter, run)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Parallelism;
+
+    #[test]
+    fn short_buffers_run_on_one_thread() {
+        let parallelism = Parallelism::Threads(8.try_into().unwrap());
+        assert_eq!(parallelism.threads(), 8);
+        assert_eq!(parallelism.blocks_per_thread(10), 4096);
+        assert_eq!(parallelism.blocks_per_thread(8 * 5000), 5000);
+        assert_eq!(Parallelism::Serial.blocks_per_thread(0), 4096);
+    }
+}
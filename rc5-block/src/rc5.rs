@@ -1,4 +1,9 @@
-use crate::{BlockCipher, Reason, Version, Word, bail};
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt;
+
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{BlockCipher, Reason, Version, Word, bail, secret::KeyTable};
 
 /// # RC5 control block
 ///
@@ -8,6 +13,11 @@ use crate::{BlockCipher, Reason, Version, Word, bail};
 /// Parameters:
 ///
 /// - `W`: The word type (e.g., `u16`, `u32`, `u64`), which must implement the [`Word`] trait.
+///
+/// The expanded key is wiped from memory when the control block is dropped, and
+/// its [Debug](fmt::Debug) output only shows the parametric version and the key
+/// fingerprint, see [BlockCipher::key_check_value].
+#[derive(Clone)]
 pub struct RC5ControlBlock<W: Word> {
     /// RC5 parametric version
     version: Version,
@@ -33,22 +43,45 @@ impl<W: Word> RC5ControlBlock<W> {
         K: AsRef<[u8]>,
     {
         let key = RC5Key::from_raw(key, rounds)?;
-        Ok(Self {
+        Ok(Self::from_key(key, rounds))
+    }
+
+    /// Creates a control block keyed with the empty key, `RC5-w/r/0` of
+    /// the RC5 paper, as used by some conformance vectors.
+    ///
+    /// [RC5ControlBlock::new] rejects empty keys with [Reason::InvalidKey],
+    /// since they are almost always a mistake, so this configuration has
+    /// to be asked for explicitly.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use rc5_block::RC5ControlBlock;
+    ///
+    /// let rc5_block = RC5ControlBlock::<u32>::with_empty_key(12).unwrap();
+    /// assert_eq!(rc5_block.parametric_version(), "RC5-v1/32/12/0");
+    /// ```
+    pub fn with_empty_key(rounds: usize) -> Result<Self, Reason> {
+        bail!(rounds > MAX_ROUNDS, Reason::InvalidRounds(rounds));
+
+        let key = RC5Key::expand(&[], 2 * (rounds + 1))?;
+        Ok(Self::from_key(key, rounds))
+    }
+
+    /// Creates a control block from an already validated and
+    /// expanded RC5 key.
+    pub(crate) fn from_key(key: RC5Key<W>, rounds: usize) -> Self {
+        Self {
             rounds,
-            version: Version::from_parametric_vector(vec![
-                1,
-                (W::BYTES * 8) as u8,
-                rounds as u8,
-                key.raw_len() as u8,
-            ]),
+            version: Version::of_word::<W>("RC5", rounds, key.raw_len()),
             key,
-        })
+        }
     }
 
     /// Returns a reference to the expanded S table used for round keys.
     #[inline]
     pub fn s_table(&self) -> &[W] {
-        &self.key.s_table
+        self.key.s_table()
     }
 
     // Returns the configured number of RC5 rounds.
@@ -63,6 +96,81 @@ impl<W: Word> RC5ControlBlock<W> {
     pub fn parametric_version(&self) -> String {
         self.version.version()
     }
+
+    /// Returns the parametric RC5 version.
+    #[inline]
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Exports the parametric version and a copy of the expanded S-table,
+    /// so the key expansion can be skipped later on with
+    /// [RC5ControlBlock::from_schedule].
+    ///
+    /// The S-table is as sensitive as the raw key, the copy is wiped when
+    /// dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use rc5_block::{BlockCipher, RC5ControlBlock, Version};
+    ///
+    /// let control_block = RC5ControlBlock::<u32>::new(b"mykey", 12).unwrap();
+    /// let (version, s_table) = control_block.export_schedule();
+    ///
+    /// // e.g. stored along with the table as "RC5-v1/32/12/5"
+    /// let version: Version = version.to_string().parse().unwrap();
+    ///
+    /// let restored = RC5ControlBlock::<u32>::from_schedule(&version, &s_table).unwrap();
+    /// assert_eq!(restored.encrypt([1, 2]), control_block.encrypt([1, 2]));
+    /// ```
+    pub fn export_schedule(&self) -> (Version, Zeroizing<Vec<W>>) {
+        (
+            self.version.clone(),
+            Zeroizing::new(self.s_table().to_vec()),
+        )
+    }
+
+    /// Creates a control block from an already expanded S-table and its
+    /// parametric version, as returned by [RC5ControlBlock::export_schedule].
+    ///
+    /// Fails with:
+    ///
+    /// - [Reason::InvalidVersion] if `version` is not a well-formed `RC5-v1` version.
+    /// - [Reason::WordSize] if the version word-size is not the one of `W`.
+    /// - [Reason::ScheduleLength] if `s_table` does not hold `2 * (rounds + 1)` words.
+    pub fn from_schedule(version: &Version, s_table: &[W]) -> Result<Self, Reason> {
+        let invalid = || Reason::InvalidVersion(format!("{version:?}"));
+
+        let &[revision, word_bits, rounds, key_len] = version.params() else {
+            return Err(invalid());
+        };
+        let expected = 2 * (rounds as usize + 1);
+
+        bail!(
+            version.algorithm() != "RC5" || revision != 1 || rounds > 255 || key_len > 255,
+            invalid(),
+            word_bits as usize != W::BYTES * 8,
+            Reason::WordSize,
+            s_table.len() != expected,
+            Reason::ScheduleLength {
+                expected,
+                current: s_table.len()
+            }
+        );
+
+        let key = RC5Key::from_table(key_len as usize, s_table)?;
+        Ok(Self::from_key(key, rounds as usize))
+    }
+}
+
+impl<W: Word> fmt::Debug for RC5ControlBlock<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RC5ControlBlock")
+            .field("version", &self.parametric_version())
+            .field("fingerprint", &self.key_check_value())
+            .finish()
+    }
 }
 
 impl<W: Word> BlockCipher<W, 2> for RC5ControlBlock<W> {
@@ -103,25 +211,24 @@ impl<W: Word> BlockCipher<W, 2> for RC5ControlBlock<W> {
         [word_a, word_b]
     }
 
-    fn generate_blocks(&self, pt: Vec<u8>) -> Vec<[W; 2]> {
-        let mut blocks = Vec::with_capacity(pt.len() / self.block_size());
-        for chunks in pt.chunks_exact(self.block_size()) {
-            blocks.push([
-                W::from_bytes_slice(&chunks[..W::BYTES]).unwrap(),
-                W::from_bytes_slice(&chunks[W::BYTES..]).unwrap(),
-            ]);
+    /// Encrypts the blocks with the widest SIMD kernel available on the
+    /// running CPU for `u32` and `u64` words (AVX-512 or AVX2 on `x86_64`),
+    /// the remaining blocks are encrypted one by one.
+    fn encrypt_blocks(&self, blocks: &mut [[W; 2]]) {
+        let processed = W::rc5_encrypt_lanes(self.s_table(), blocks);
+        for block in &mut blocks[processed..] {
+            *block = self.encrypt(*block);
         }
-
-        blocks
     }
 
-    fn generate_bytes_stream(&self, blocks: Vec<[W; 2]>) -> Vec<u8> {
-        let mut stream = Vec::with_capacity(blocks.len() * self.block_size());
-        for blcok in blocks.iter() {
-            stream.extend_from_slice(&blcok[0].to_bytes_slice());
-            stream.extend_from_slice(&blcok[1].to_bytes_slice());
+    /// Decrypts the blocks with the widest SIMD kernel available on the
+    /// running CPU for `u32` and `u64` words (AVX-512 or AVX2 on `x86_64`),
+    /// the remaining blocks are decrypted one by one.
+    fn decrypt_blocks(&self, blocks: &mut [[W; 2]]) {
+        let processed = W::rc5_decrypt_lanes(self.s_table(), blocks);
+        for block in &mut blocks[processed..] {
+            *block = self.decrypt(*block);
         }
-        stream
     }
 
     fn control_block_version(&self) -> String {
@@ -142,22 +249,47 @@ const MAX_KEY_BYTES: usize = 255;
 
 /// # RC5Key
 ///
-/// Internal rc5 key  container which holds the raw key as
-/// well as expanded s-table of the raw key.
+/// Internal rc5 key container which holds the expanded s-table
+/// of the raw key, and the raw key length. The raw key itself is
+/// not retained once expanded.
+///
+/// The s-table is wiped on drop and, with the `mlock` feature on
+/// Linux, kept in memory locked out of swap.
+#[derive(Clone)]
 pub struct RC5Key<W: Word> {
-    raw_key: Vec<u8>,
-    s_table: Vec<W>,
+    raw_len: usize,
+    s_table: KeyTable<W>,
 }
 
 impl<W: Word> RC5Key<W> {
     /// Creates RC5Key from raw key bytes.
     /// Reutrns a result type containing Key or an err.
     pub fn from_raw<K>(raw: K, rounds: usize) -> Result<Self, Reason>
+    where
+        K: AsRef<[u8]>,
+    {
+        Self::with_table_size(raw, rounds, 2 * (rounds + 1))
+    }
+
+    /// Creates a key from raw key bytes, expanding it into an S-table
+    /// of `table_size` words. This lets the other members of the RC5
+    /// family (e.g. RC6 with `2r + 4` round keys) share the schedule.
+    pub(crate) fn with_table_size<K>(
+        raw: K,
+        rounds: usize,
+        table_size: usize,
+    ) -> Result<Self, Reason>
     where
         K: AsRef<[u8]>,
     {
         let key_bytes = raw.as_ref();
+        Self::check_raw(key_bytes, rounds)?;
+
+        Self::expand(key_bytes, table_size)
+    }
 
+    /// Validates the raw key length and the round count.
+    pub(crate) fn check_raw(key_bytes: &[u8], rounds: usize) -> Result<(), Reason> {
         bail!(
             key_bytes.is_empty(),
             Reason::InvalidKey,
@@ -170,14 +302,59 @@ impl<W: Word> RC5Key<W> {
             Reason::InvalidRounds(rounds)
         );
 
-        Ok(Self {
-            s_table: expand_key::<W>(key_bytes, rounds),
-            raw_key: key_bytes.to_vec(),
-        })
+        Ok(())
     }
 
+    /// Expands raw key bytes into an S-table of `table_size` words
+    /// without validating the key length.
+    ///
+    /// Only fails with [Reason::MemoryLock] if the table cannot be locked
+    /// in memory with the `mlock` feature.
+    pub(crate) fn expand(key_bytes: &[u8], table_size: usize) -> Result<Self, Reason> {
+        Ok(Self::expand_into(key_bytes, KeyTable::zeroed(table_size)?))
+    }
+
+    /// Expands raw key bytes like [RC5Key::expand], but keeps the S-table
+    /// in ordinary heap memory, still wiped on drop, if it cannot be locked
+    /// with the `mlock` feature. Never fails.
+    pub(crate) fn expand_or_unlocked(key_bytes: &[u8], table_size: usize) -> Self {
+        Self::expand_into(key_bytes, KeyTable::zeroed_or_unlocked(table_size))
+    }
+
+    fn expand_into(key_bytes: &[u8], mut s_table: KeyTable<W>) -> Self {
+        expand_key(key_bytes, &mut s_table);
+
+        Self {
+            raw_len: key_bytes.len(),
+            s_table,
+        }
+    }
+
+    /// Creates a key from an already expanded S-table, for a raw key of
+    /// `raw_len` bytes.
+    pub(crate) fn from_table(raw_len: usize, table: &[W]) -> Result<Self, Reason> {
+        let mut s_table = KeyTable::zeroed(table.len())?;
+        s_table.copy_from_slice(table);
+
+        Ok(Self { raw_len, s_table })
+    }
+
+    /// Returns the length of the raw key in bytes.
     pub fn raw_len(&self) -> usize {
-        self.raw_key.len()
+        self.raw_len
+    }
+
+    /// Returns the expanded S-table of this key.
+    pub fn s_table(&self) -> &[W] {
+        &self.s_table
+    }
+}
+
+impl<W: Word> fmt::Debug for RC5Key<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RC5Key")
+            .field("raw_len", &self.raw_len)
+            .finish_non_exhaustive()
     }
 }
 
@@ -191,15 +368,16 @@ impl<W: Word> RC5Key<W> {
 ///
 /// # Parameters
 /// - `key`: raw key bytes.
-/// - `rounds`: number of RC5 rounds.
+/// - `s_table`: the table to fill, of `2 * (rounds + 1)` round-key words for RC5.
 ///
-/// # Returns
-/// A vector containing the expanded key schedule.
-fn expand_key<W: Word>(key: &[u8], rounds: usize) -> Vec<W> {
+/// The intermediate key words are wiped before returning.
+fn expand_key<W: Word>(key: &[u8], s_table: &mut [W]) {
+    let table_size = s_table.len();
     let word_bytes = W::BYTES;
-    let key_length = key.len().max(1);
+    let key_length = key.len();
 
-    let expanded_length = key_length.div_ceil(word_bytes);
+    // An empty key still occupies a single zero word.
+    let expanded_length = key_length.div_ceil(word_bytes).max(1);
     let mut key_words = vec![W::ZERO; expanded_length];
 
     // Packing the raw bytes ino list of word-szie
@@ -211,9 +389,6 @@ fn expand_key<W: Word>(key: &[u8], rounds: usize) -> Vec<W> {
             .wrapping_add(W::from_u8(key[index]));
     }
 
-    let table_size = 2 * (rounds + 1);
-    let mut s_table = vec![W::ZERO; table_size];
-
     s_table[0] = W::P;
 
     // Key flattening with magic constants
@@ -244,5 +419,7 @@ fn expand_key<W: Word>(key: &[u8], rounds: usize) -> Vec<W> {
         j = (j + 1) % expanded_length;
     }
 
-    s_table
+    key_words.zeroize();
+    a.zeroize();
+    b.zeroize();
 }
@@ -0,0 +1,158 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::{BlockCipher, Reason, Version, Word, rc5::RC5Key};
+
+/// # RC6 control block
+///
+/// This struct represents the internal state of an RC6 cipher with a given word size.
+/// RC6 operates on four-word blocks (128-bit blocks for `u32`) and shares RC5's key
+/// schedule and `P`/`Q` magic constants, expanded into `2 * rounds + 4` round keys.
+///
+/// Parameters:
+///
+/// - `W`: The word type (e.g., `u16`, `u32`, `u64`), which must implement the [`Word`] trait.
+///
+/// Like [RC5ControlBlock](crate::RC5ControlBlock), the expanded key is wiped on drop
+/// and [Debug](fmt::Debug) only shows the parametric version and the key fingerprint.
+#[derive(Clone)]
+pub struct RC6ControlBlock<W: Word> {
+    /// RC6 parametric version
+    version: Version,
+
+    /// RC6 key, which holds the raw key and its
+    /// expanded `S-Table`
+    key: RC5Key<W>,
+
+    /// Defines the number of iterations during
+    /// encryption.
+    rounds: usize,
+}
+
+impl<W: Word> RC6ControlBlock<W> {
+    /// RC6 constructor method
+    ///
+    /// It creates an instance of an RC6 control block from
+    /// raw key and rounds.
+    ///
+    /// Returns a result type containing the control block.
+    pub fn new<K>(key: K, rounds: usize) -> Result<Self, Reason>
+    where
+        K: AsRef<[u8]>,
+    {
+        let key = RC5Key::with_table_size(key, rounds, 2 * rounds + 4)?;
+        Ok(Self {
+            rounds,
+            version: Version::of_word::<W>("RC6", rounds, key.raw_len()),
+            key,
+        })
+    }
+
+    /// Returns a reference to the expanded S table used for round keys.
+    #[inline]
+    pub fn s_table(&self) -> &[W] {
+        self.key.s_table()
+    }
+
+    /// Returns the configured number of RC6 rounds.
+    #[inline]
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Returns a string describing the parametric RC6 version,
+    /// e.g. `"RC6-v1/32/20/16"`.
+    #[inline]
+    pub fn parametric_version(&self) -> String {
+        self.version.version()
+    }
+
+    /// Base-two logarithm of the word-size in bits, the fixed
+    /// rotation applied to the quadratic function output.
+    #[inline]
+    fn lg_w() -> W {
+        W::from_u8((W::BYTES * 8).ilog2() as u8)
+    }
+
+    /// RC6 quadratic function `f(x) = (x * (2x + 1)) <<< lg w`.
+    #[inline]
+    fn quadratic(x: W) -> W {
+        x.wrapping_mul(x.wrapping_add(x).wrapping_add(W::from_u8(1)))
+            .rotate_left(Self::lg_w())
+    }
+}
+
+impl<W: Word> fmt::Debug for RC6ControlBlock<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RC6ControlBlock")
+            .field("version", &self.parametric_version())
+            .field("fingerprint", &self.key_check_value())
+            .finish()
+    }
+}
+
+impl<W: Word> BlockCipher<W, 4> for RC6ControlBlock<W> {
+    fn encrypt(&self, pt: [W; 4]) -> [W; 4] {
+        let expanded_key = self.s_table();
+        let rounds = self.rounds();
+        let [mut word_a, mut word_b, mut word_c, mut word_d] = pt;
+
+        word_b = word_b.wrapping_add(expanded_key[0]);
+        word_d = word_d.wrapping_add(expanded_key[1]);
+
+        for r in 1..=rounds {
+            let t = Self::quadratic(word_b);
+            let u = Self::quadratic(word_d);
+
+            word_a = (word_a ^ t)
+                .rotate_left(u)
+                .wrapping_add(expanded_key[2 * r]);
+            word_c = (word_c ^ u)
+                .rotate_left(t)
+                .wrapping_add(expanded_key[2 * r + 1]);
+
+            (word_a, word_b, word_c, word_d) = (word_b, word_c, word_d, word_a);
+        }
+
+        word_a = word_a.wrapping_add(expanded_key[2 * rounds + 2]);
+        word_c = word_c.wrapping_add(expanded_key[2 * rounds + 3]);
+
+        [word_a, word_b, word_c, word_d]
+    }
+
+    fn decrypt(&self, ct: [W; 4]) -> [W; 4] {
+        let expanded_key = self.s_table();
+        let rounds = self.rounds();
+        let [mut word_a, mut word_b, mut word_c, mut word_d] = ct;
+
+        word_c = word_c.wrapping_sub(expanded_key[2 * rounds + 3]);
+        word_a = word_a.wrapping_sub(expanded_key[2 * rounds + 2]);
+
+        for r in (1..=rounds).rev() {
+            (word_a, word_b, word_c, word_d) = (word_d, word_a, word_b, word_c);
+
+            let u = Self::quadratic(word_d);
+            let t = Self::quadratic(word_b);
+
+            word_c = word_c.wrapping_sub(expanded_key[2 * r + 1]).rotate_right(t) ^ u;
+            word_a = word_a.wrapping_sub(expanded_key[2 * r]).rotate_right(u) ^ t;
+        }
+
+        word_d = word_d.wrapping_sub(expanded_key[1]);
+        word_b = word_b.wrapping_sub(expanded_key[0]);
+
+        [word_a, word_b, word_c, word_d]
+    }
+
+    fn control_block_version(&self) -> String {
+        self.parametric_version()
+    }
+
+    fn block_size(&self) -> usize {
+        W::BYTES * 4
+    }
+
+    fn word_size(&self) -> usize {
+        W::BYTES
+    }
+}
@@ -0,0 +1,222 @@
+//! Interoperability with the [RustCrypto](https://github.com/RustCrypto) `cipher`
+//! traits, enabled with the `cipher` feature.
+//!
+//! - [RC5ControlBlock] implements the RustCrypto traits, which allows composing
+//!   RC5-16/32/64 with the block modes, MACs and AEADs of the RustCrypto ecosystem
+//!   (e.g. `cbc`, `ctr`, `cfb-mode`, `cmac` or `eax`).
+//! - [BlockCipherAdapter] goes the other way around and exposes any RustCrypto
+//!   block cipher as a [BlockCipher], so it can be driven by [Cipher](crate::Cipher).
+//!
+//! Blocks are exchanged as little-endian bytes, the same layout produced by
+//! [BlockCipher::generate_bytes_stream].
+use alloc::string::{String, ToString};
+use core::{fmt, marker::PhantomData};
+
+use cipher::{
+    AlgorithmName, Block, BlockBackend, BlockClosure, BlockDecrypt, BlockEncrypt, BlockSizeUser,
+    InvalidLength, Key, KeyInit, KeySizeUser, ParBlocksSizeUser,
+    consts::{U1, U4, U8, U16},
+    generic_array::ArrayLength,
+    inout::InOut,
+    typenum::Unsigned,
+};
+
+use crate::{
+    BlockCipher, RC5ControlBlock, Reason, Word, bail,
+    rc5::RC5Key,
+    utils::{block_from_bytes, block_to_bytes},
+};
+
+/// Single block backend driving one direction of an RC5 control block.
+struct RC5Backend<'a, W: Word, B> {
+    control_block: &'a RC5ControlBlock<W>,
+    encrypt: bool,
+    _size: PhantomData<B>,
+}
+
+impl<W: Word, B: ArrayLength<u8>> BlockSizeUser for RC5Backend<'_, W, B> {
+    type BlockSize = B;
+}
+
+impl<W: Word, B: ArrayLength<u8>> ParBlocksSizeUser for RC5Backend<'_, W, B> {
+    type ParBlocksSize = U1;
+}
+
+impl<W: Word, B: ArrayLength<u8>> BlockBackend for RC5Backend<'_, W, B> {
+    #[inline]
+    fn proc_block(&mut self, mut block: InOut<'_, '_, Block<Self>>) {
+        let input = block.get_in();
+        let words = block_from_bytes(input);
+
+        let processed = match self.encrypt {
+            true => self.control_block.encrypt(words),
+            false => self.control_block.decrypt(words),
+        };
+
+        let output = block.get_out();
+        block_to_bytes(&processed, output);
+    }
+}
+
+/// Implements the RustCrypto traits for an RC5 word size.
+///
+/// `KeyInit` uses the nominal 16-byte key size with the given
+/// default rounds, `KeyInit::new_from_slice` accepts any key
+/// length supported by [RC5ControlBlock::new]. Both are infallible
+/// with respect to the `mlock` feature, a schedule that cannot be
+/// locked is kept in ordinary memory, still wiped on drop.
+macro_rules! impl_rustcrypto_for_rc5 {
+    ($($w:ty => ($block_size:ty, $rounds:expr)),* $(,)?) => {
+        $(
+            impl BlockSizeUser for RC5ControlBlock<$w> {
+                type BlockSize = $block_size;
+            }
+
+            impl KeySizeUser for RC5ControlBlock<$w> {
+                type KeySize = U16;
+            }
+
+            impl cipher::BlockCipher for RC5ControlBlock<$w> {}
+
+            impl KeyInit for RC5ControlBlock<$w> {
+                fn new(key: &Key<Self>) -> Self {
+                    let key = RC5Key::expand_or_unlocked(key, 2 * ($rounds + 1));
+                    Self::from_key(key, $rounds)
+                }
+
+                fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+                    match Self::new(key, $rounds) {
+                        Ok(control_block) => Ok(control_block),
+                        Err(Reason::MemoryLock(_)) => {
+                            let key = RC5Key::expand_or_unlocked(key, 2 * ($rounds + 1));
+                            Ok(Self::from_key(key, $rounds))
+                        }
+                        Err(_) => Err(InvalidLength),
+                    }
+                }
+            }
+
+            impl AlgorithmName for RC5ControlBlock<$w> {
+                fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "RC5-{}/{}", <$w>::BITS, $rounds)
+                }
+            }
+
+            impl BlockEncrypt for RC5ControlBlock<$w> {
+                fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = $block_size>) {
+                    f.call(&mut RC5Backend {
+                        control_block: self,
+                        encrypt: true,
+                        _size: PhantomData::<$block_size>,
+                    })
+                }
+            }
+
+            impl BlockDecrypt for RC5ControlBlock<$w> {
+                fn decrypt_with_backend(&self, f: impl BlockClosure<BlockSize = $block_size>) {
+                    f.call(&mut RC5Backend {
+                        control_block: self,
+                        encrypt: false,
+                        _size: PhantomData::<$block_size>,
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_rustcrypto_for_rc5! {
+    u16 => (U4, 12),
+    u32 => (U8, 12),
+    u64 => (U16, 16),
+}
+
+/// # Block cipher adapter
+///
+/// Wraps any RustCrypto `BlockEncrypt + BlockDecrypt` implementation and exposes
+/// it as a [BlockCipher] of `N` words, so the [Cipher](crate::Cipher) modes and
+/// padding can run on foreign block ciphers, e.g. AES or Blowfish.
+///
+/// The block size of the wrapped cipher must be exactly `N * W::BYTES`, words are
+/// read from the block bytes in little-endian order.
+///
+/// ## Example
+///
+/// ```rust
+/// use aes::{Aes128, cipher::KeyInit};
+/// use rc5_block::{BlockCipherAdapter, Cipher, OperationMode};
+///
+/// let aes = Aes128::new(&[0u8; 16].into());
+/// let cipher = Cipher::new(BlockCipherAdapter::<_, u32, 4>::new(aes).unwrap());
+///
+/// let ct = cipher.encrypt(b"Secret message", OperationMode::ECB).unwrap();
+/// assert_eq!(cipher.decrypt(&ct, OperationMode::ECB).unwrap(), b"Secret message");
+/// ```
+#[derive(Clone)]
+pub struct BlockCipherAdapter<C, W, const N: usize> {
+    cipher: C,
+    _marker: PhantomData<W>,
+}
+
+impl<C, W, const N: usize> BlockCipherAdapter<C, W, N>
+where
+    C: BlockEncrypt + BlockDecrypt,
+    W: Word,
+{
+    /// Wraps a RustCrypto block cipher instance.
+    ///
+    /// Fails with [Reason::WordSize] if the block size of the wrapped
+    /// cipher is not `N` words of `W`.
+    pub fn new(cipher: C) -> Result<Self, Reason> {
+        bail!(C::BlockSize::USIZE != N * W::BYTES, Reason::WordSize);
+
+        Ok(Self {
+            cipher,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns an immutable access to the wrapped cipher.
+    pub fn inner(&self) -> &C {
+        &self.cipher
+    }
+
+    /// Runs a single `[W; N]` block through the wrapped cipher.
+    fn process(&self, block: [W; N], encrypt: bool) -> [W; N] {
+        let mut bytes = Block::<C>::default();
+        block_to_bytes(&block, &mut bytes);
+
+        match encrypt {
+            true => self.cipher.encrypt_block(&mut bytes),
+            false => self.cipher.decrypt_block(&mut bytes),
+        }
+
+        block_from_bytes(&bytes)
+    }
+}
+
+impl<C, W, const N: usize> BlockCipher<W, N> for BlockCipherAdapter<C, W, N>
+where
+    C: BlockEncrypt + BlockDecrypt,
+    W: Word,
+{
+    fn control_block_version(&self) -> String {
+        core::any::type_name::<C>().to_string()
+    }
+
+    fn block_size(&self) -> usize {
+        N * W::BYTES
+    }
+
+    fn word_size(&self) -> usize {
+        W::BYTES
+    }
+
+    fn encrypt(&self, pt: [W; N]) -> [W; N] {
+        self.process(pt, true)
+    }
+
+    fn decrypt(&self, ct: [W; N]) -> [W; N] {
+        self.process(ct, false)
+    }
+}
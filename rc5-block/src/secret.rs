@@ -0,0 +1,431 @@
+//! Heap storage of expanded key schedules.
+//!
+//! The words are wiped with [Zeroize](zeroize::Zeroize) when the table is dropped. With the
+//! `mlock` feature on Linux, the tables are carved out of a pool of pages
+//! locked into RAM with `mlock(2)`, so the schedule is never written to
+//! swap and many schedules fit within `RLIMIT_MEMLOCK`. A table is wiped
+//! before its memory goes back to the pool.
+use crate::Word;
+
+#[cfg(not(all(feature = "mlock", target_os = "linux")))]
+use {
+    crate::Reason,
+    alloc::{vec, vec::Vec},
+    core::ops::{Deref, DerefMut},
+};
+
+/// # KeyTable
+///
+/// A fixed-length table of key words, wiped on drop.
+#[cfg(not(all(feature = "mlock", target_os = "linux")))]
+#[derive(Clone)]
+pub(crate) struct KeyTable<W: Word> {
+    words: Vec<W>,
+}
+
+#[cfg(not(all(feature = "mlock", target_os = "linux")))]
+impl<W: Word> KeyTable<W> {
+    /// Allocates a table of `len` zero words.
+    pub(crate) fn zeroed(len: usize) -> Result<Self, Reason> {
+        Ok(Self::zeroed_or_unlocked(len))
+    }
+
+    /// Allocates a table of `len` zero words, which never fails without
+    /// the `mlock` feature.
+    pub(crate) fn zeroed_or_unlocked(len: usize) -> Self {
+        Self {
+            words: vec![W::ZERO; len],
+        }
+    }
+}
+
+#[cfg(not(all(feature = "mlock", target_os = "linux")))]
+impl<W: Word> Deref for KeyTable<W> {
+    type Target = [W];
+
+    fn deref(&self) -> &[W] {
+        &self.words
+    }
+}
+
+#[cfg(not(all(feature = "mlock", target_os = "linux")))]
+impl<W: Word> DerefMut for KeyTable<W> {
+    fn deref_mut(&mut self) -> &mut [W] {
+        &mut self.words
+    }
+}
+
+#[cfg(all(feature = "mlock", target_os = "linux"))]
+pub(crate) use locked::KeyTable;
+
+#[cfg(all(feature = "mlock", target_os = "linux"))]
+mod locked {
+    use alloc::{
+        alloc::{Layout, alloc, dealloc, handle_alloc_error},
+        vec,
+        vec::Vec,
+    };
+    use core::{
+        mem::{align_of, size_of},
+        ptr::NonNull,
+        slice,
+    };
+    use std::sync::{Mutex, PoisonError};
+
+    use crate::{Reason, Word};
+
+    /// Size of the slots the locked regions are carved into, also the
+    /// largest word alignment the pool can serve.
+    const SLOT: usize = 64;
+
+    /// Locked regions shared by every key schedule of the process.
+    static POOL: Mutex<Vec<Region>> = Mutex::new(Vec::new());
+
+    /// # Region
+    ///
+    /// Whole pages locked with `mlock(2)`, handed out in [SLOT] sized
+    /// pieces, so many schedules share the pages counted against
+    /// `RLIMIT_MEMLOCK`.
+    struct Region {
+        base: NonNull<u8>,
+        layout: Layout,
+        used: Vec<bool>,
+    }
+
+    // SAFETY: the region is only reached through the pool mutex.
+    unsafe impl Send for Region {}
+
+    impl Region {
+        /// Allocates and locks a region of at least `size` bytes.
+        fn lock(size: usize) -> Result<Self, Reason> {
+            // SAFETY: `sysconf` has no preconditions.
+            let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+            let size = size.max(page).next_multiple_of(page);
+            let layout = Layout::from_size_align(size, page).expect("page sized layout");
+
+            // SAFETY: `layout` has a non-zero size.
+            let base = unsafe { alloc(layout) };
+            let Some(base) = NonNull::new(base) else {
+                handle_alloc_error(layout)
+            };
+
+            // SAFETY: `base` points to `size` bytes owned by this region.
+            if unsafe { libc::mlock(base.as_ptr().cast(), size) } != 0 {
+                let errno = std::io::Error::last_os_error().raw_os_error();
+                // SAFETY: `base` was allocated above with `layout`.
+                unsafe { dealloc(base.as_ptr(), layout) };
+                return Err(Reason::MemoryLock(errno.unwrap_or_default()));
+            }
+
+            Ok(Self {
+                base,
+                layout,
+                used: vec![false; size / SLOT],
+            })
+        }
+
+        /// Reserves `slots` contiguous free slots, first fit.
+        fn reserve(&mut self, slots: usize) -> Option<NonNull<u8>> {
+            let mut run = 0;
+            for ix in 0..self.used.len() {
+                run = if self.used[ix] { 0 } else { run + 1 };
+                if run == slots {
+                    let start = ix + 1 - slots;
+                    self.used[start..=ix].fill(true);
+                    // SAFETY: the slot lies within the region.
+                    return Some(unsafe { self.base.add(start * SLOT) });
+                }
+            }
+            None
+        }
+
+        fn contains(&self, ptr: NonNull<u8>) -> bool {
+            let offset = (ptr.as_ptr() as usize).wrapping_sub(self.base.as_ptr() as usize);
+            offset < self.layout.size()
+        }
+
+        fn release(&mut self, ptr: NonNull<u8>, slots: usize) {
+            let start = (ptr.as_ptr() as usize - self.base.as_ptr() as usize) / SLOT;
+            self.used[start..start + slots].fill(false);
+        }
+    }
+
+    impl Drop for Region {
+        fn drop(&mut self) {
+            // SAFETY: the pages were locked and allocated with `layout` in
+            // `Region::lock`, and every table carved out of them is gone.
+            unsafe {
+                libc::munlock(self.base.as_ptr().cast(), self.layout.size());
+                dealloc(self.base.as_ptr(), self.layout);
+            }
+        }
+    }
+
+    /// Reserves `slots` slots of locked memory, locking a new region
+    /// when none of the existing ones has room.
+    fn reserve(slots: usize) -> Result<NonNull<u8>, Reason> {
+        let mut pool = POOL.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(ptr) = pool.iter_mut().find_map(|region| region.reserve(slots)) {
+            return Ok(ptr);
+        }
+
+        let mut region = Region::lock(slots * SLOT)?;
+        let ptr = region.reserve(slots).expect("fresh region has room");
+        pool.push(region);
+
+        Ok(ptr)
+    }
+
+    /// Gives `slots` slots back to their region, unlocking the region
+    /// once it is empty.
+    fn release(ptr: NonNull<u8>, slots: usize) {
+        let mut pool = POOL.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let ix = pool
+            .iter()
+            .position(|region| region.contains(ptr))
+            .expect("slots of a pooled region");
+
+        pool[ix].release(ptr, slots);
+        if pool[ix].used.iter().all(|used| !used) {
+            pool.swap_remove(ix);
+        }
+    }
+
+    /// Where the words of a [KeyTable] live.
+    enum Storage<W: Word> {
+        /// `slots` slots of a locked region of the pool.
+        Locked { ptr: NonNull<W>, slots: usize },
+        /// Ordinary heap memory, only used when a copy cannot be locked.
+        Unlocked(Vec<W>),
+    }
+
+    /// # KeyTable
+    ///
+    /// A fixed-length table of key words in memory locked with `mlock(2)`,
+    /// wiped on drop before its slots are given back.
+    ///
+    /// The locked pages are shared between tables, a page is unlocked once
+    /// the last table within it is dropped.
+    pub(crate) struct KeyTable<W: Word> {
+        storage: Storage<W>,
+        len: usize,
+    }
+
+    // SAFETY: the table uniquely owns its slots, like a `Vec<W>`.
+    unsafe impl<W: Word> Send for KeyTable<W> {}
+    unsafe impl<W: Word> Sync for KeyTable<W> {}
+
+    impl<W: Word> KeyTable<W> {
+        /// Allocates a table of `len` zero words in locked memory.
+        ///
+        /// Fails with [Reason::MemoryLock] if no more pages can be locked,
+        /// e.g. once `RLIMIT_MEMLOCK` is exhausted.
+        pub(crate) fn zeroed(len: usize) -> Result<Self, Reason> {
+            const { assert!(align_of::<W>() <= SLOT) };
+
+            let slots = (len * size_of::<W>()).div_ceil(SLOT).max(1);
+            let ptr = reserve(slots)?.cast::<W>();
+
+            for ix in 0..len {
+                // SAFETY: the slots are aligned for and hold `len` words.
+                unsafe { ptr.add(ix).write(W::ZERO) };
+            }
+
+            Ok(Self {
+                storage: Storage::Locked { ptr, slots },
+                len,
+            })
+        }
+
+        /// Allocates a table of `len` zero words, in ordinary heap memory
+        /// if it cannot be locked. The words are wiped on drop either way.
+        pub(crate) fn zeroed_or_unlocked(len: usize) -> Self {
+            Self::zeroed(len).unwrap_or_else(|_| Self {
+                storage: Storage::Unlocked(vec![W::ZERO; len]),
+                len,
+            })
+        }
+    }
+
+    impl<W: Word> core::ops::Deref for KeyTable<W> {
+        type Target = [W];
+
+        fn deref(&self) -> &[W] {
+            match &self.storage {
+                // SAFETY: the first `len` words were initialised in `zeroed`.
+                Storage::Locked { ptr, .. } => unsafe {
+                    slice::from_raw_parts(ptr.as_ptr(), self.len)
+                },
+                Storage::Unlocked(words) => words,
+            }
+        }
+    }
+
+    impl<W: Word> core::ops::DerefMut for KeyTable<W> {
+        fn deref_mut(&mut self) -> &mut [W] {
+            match &mut self.storage {
+                // SAFETY: the first `len` words were initialised in `zeroed`,
+                // and the table is borrowed mutably.
+                Storage::Locked { ptr, .. } => unsafe {
+                    slice::from_raw_parts_mut(ptr.as_ptr(), self.len)
+                },
+                Storage::Unlocked(words) => words,
+            }
+        }
+    }
+
+    /// Clones the table into locked memory, or into ordinary heap memory
+    /// once no more pages can be locked, so cloning never fails.
+    impl<W: Word> Clone for KeyTable<W> {
+        fn clone(&self) -> Self {
+            let mut table = Self::zeroed_or_unlocked(self.len);
+            table.copy_from_slice(self);
+            table
+        }
+    }
+
+    impl<W: Word> Drop for KeyTable<W> {
+        fn drop(&mut self) {
+            super::wipe(self);
+
+            if let Storage::Locked { ptr, slots } = self.storage {
+                release(ptr.cast(), slots);
+            }
+        }
+    }
+}
+
+#[cfg(not(all(feature = "mlock", target_os = "linux")))]
+impl<W: Word> Drop for KeyTable<W> {
+    fn drop(&mut self) {
+        wipe(self);
+    }
+}
+
+/// Overwrites every word of the table with zero.
+fn wipe<W: Word>(words: &mut [W]) {
+    for word in words {
+        word.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyTable;
+
+    #[test]
+    fn tables_start_zeroed_and_clone_their_words() {
+        let mut table = KeyTable::<u32>::zeroed(26).unwrap();
+        assert_eq!(*table, [0; 26]);
+
+        table[3] = 0xdeadbeef;
+        let copy = table.clone();
+        assert_eq!(copy[3], 0xdeadbeef);
+        assert_eq!(copy.len(), 26);
+    }
+
+    #[test]
+    fn empty_tables() {
+        let table = KeyTable::<u64>::zeroed(0).unwrap();
+        assert!(table.is_empty());
+    }
+
+    /// Runs the test named `name` of this module again in a child process,
+    /// limited to the usual 64 KiB of `RLIMIT_MEMLOCK` and stripped of
+    /// `CAP_IPC_LOCK`, which would let it lock past the limit.
+    #[cfg(all(feature = "mlock", target_os = "linux"))]
+    fn run_under_memlock_limit(name: &str) {
+        use std::{os::unix::process::CommandExt, process::Command};
+
+        let mut child = Command::new(std::env::current_exe().unwrap());
+        child
+            .args([
+                "--exact",
+                &format!("secret::tests::{name}"),
+                "--test-threads=1",
+            ])
+            .env(LIMITED, "1");
+
+        // SAFETY: only async-signal-safe calls between fork and exec.
+        unsafe {
+            child.pre_exec(|| {
+                let limit = libc::rlimit {
+                    rlim_cur: 64 * 1024,
+                    rlim_max: 64 * 1024,
+                };
+                if libc::setrlimit(libc::RLIMIT_MEMLOCK, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                // Only succeeds as root, where it keeps `CAP_IPC_LOCK` (14)
+                // from being granted again on exec.
+                libc::prctl(libc::PR_CAPBSET_DROP, 14);
+                Ok(())
+            });
+        }
+
+        let output = child.output().unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{stdout}");
+        assert!(stdout.contains("1 passed"), "{stdout}");
+    }
+
+    #[cfg(all(feature = "mlock", target_os = "linux"))]
+    const LIMITED: &str = "RC5_BLOCK_MEMLOCK_LIMITED";
+
+    #[cfg(all(feature = "mlock", target_os = "linux"))]
+    #[test]
+    fn schedules_share_pages_past_the_memlock_limit() {
+        use crate::{BlockCipher, RC5ControlBlock, Reason};
+
+        if std::env::var_os(LIMITED).is_none() {
+            return run_under_memlock_limit("schedules_share_pages_past_the_memlock_limit");
+        }
+
+        // One page each would exhaust 64 KiB at the 16th schedule.
+        let blocks = (0..256u32)
+            .map(|ix| RC5ControlBlock::<u32>::new(ix.to_le_bytes(), 12))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        // Lock schedules until the limit is reached, when it is enforced.
+        let mut hoard = Vec::new();
+        let mut exhausted = false;
+        for ix in 0..100_000u32 {
+            match RC5ControlBlock::<u64>::new(ix.to_le_bytes(), 255) {
+                Ok(block) => hoard.push(block),
+                Err(Reason::MemoryLock(_)) => {
+                    exhausted = true;
+                    break;
+                }
+                Err(err) => panic!("{err}"),
+            }
+        }
+        assert!(exhausted || unsafe { libc::geteuid() } == 0);
+
+        // Copies past the limit fall back to unlocked memory.
+        for block in &blocks {
+            let copy = block.clone();
+            assert_eq!(copy.encrypt([1, 2]), block.encrypt([1, 2]));
+        }
+
+        // The fixed-round control blocks keep their schedule inline.
+        let fixed = crate::RC5_32_12::new(0u32.to_le_bytes()).unwrap();
+        assert_eq!(fixed.encrypt([1, 2]), blocks[0].encrypt([1, 2]));
+
+        // The infallible RustCrypto constructors fall back too.
+        #[cfg(feature = "cipher")]
+        {
+            use cipher::KeyInit;
+
+            // RC5-32/12/16 avr vector 1.
+            let key = 0x80000000000000000000000000000000u128.to_be_bytes();
+            let block = <RC5ControlBlock<u32> as KeyInit>::new(&key.into());
+            assert_eq!(block.key_check_value(), "8F681D");
+
+            let block = <RC5ControlBlock<u32> as KeyInit>::new_from_slice(&0u32.to_le_bytes());
+            assert_eq!(block.unwrap().encrypt([1, 2]), blocks[0].encrypt([1, 2]));
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Multi-lane RC5 kernels encrypting several independent blocks at once.
+//!
+//! On `x86_64` (with `std`, for runtime feature detection) the data-dependent
+//! rotations map to AVX-512 `vprolvd`/`vprolvq`, or are emulated with AVX2
+//! variable shifts. The widest kernel supported by the running CPU is selected
+//! through `is_x86_feature_detected!`, every other target processes no block
+//! here and falls back to the scalar [BlockCipher](crate::BlockCipher) path.
+//!
+//! Each kernel returns the number of leading blocks it processed, which is
+//! always a multiple of its lane count.
+
+macro_rules! dispatch {
+    ($($fn_name:ident: $w:ty => ($avx512:ident, $avx2:ident)),* $(,)?) => {
+        $(
+            pub(crate) fn $fn_name(s_table: &[$w], blocks: &mut [[$w; 2]]) -> usize {
+                #[cfg(all(target_arch = "x86_64", feature = "std"))]
+                {
+                    if std::is_x86_feature_detected!("avx512f") {
+                        // SAFETY: `avx512f` support was detected at runtime.
+                        return unsafe { x86_64::$avx512(s_table, blocks) };
+                    }
+                    if std::is_x86_feature_detected!("avx2") {
+                        // SAFETY: `avx2` support was detected at runtime.
+                        return unsafe { x86_64::$avx2(s_table, blocks) };
+                    }
+                }
+
+                let _ = (s_table, blocks);
+                0
+            }
+        )*
+    };
+}
+
+dispatch! {
+    encrypt_u32: u32 => (encrypt_u32_avx512, encrypt_u32_avx2),
+    decrypt_u32: u32 => (decrypt_u32_avx512, decrypt_u32_avx2),
+    encrypt_u64: u64 => (encrypt_u64_avx512, encrypt_u64_avx2),
+    decrypt_u64: u64 => (decrypt_u64_avx512, decrypt_u64_avx2),
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+pub(crate) mod x86_64 {
+    use core::{arch::x86_64::*, array::from_fn};
+
+    /// Variable left rotation of 32-bit lanes with AVX2 shifts, the
+    /// amount is taken modulo 32 (a shift by 32 yields zero).
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn rolv_epi32(x: __m256i, n: __m256i) -> __m256i {
+        let n = _mm256_and_si256(n, _mm256_set1_epi32(31));
+        let m = _mm256_sub_epi32(_mm256_set1_epi32(32), n);
+        _mm256_or_si256(_mm256_sllv_epi32(x, n), _mm256_srlv_epi32(x, m))
+    }
+
+    /// Variable right rotation of 32-bit lanes with AVX2 shifts.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn rorv_epi32(x: __m256i, n: __m256i) -> __m256i {
+        let n = _mm256_and_si256(n, _mm256_set1_epi32(31));
+        let m = _mm256_sub_epi32(_mm256_set1_epi32(32), n);
+        _mm256_or_si256(_mm256_srlv_epi32(x, n), _mm256_sllv_epi32(x, m))
+    }
+
+    /// Variable left rotation of 64-bit lanes with AVX2 shifts.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn rolv_epi64(x: __m256i, n: __m256i) -> __m256i {
+        let n = _mm256_and_si256(n, _mm256_set1_epi64x(63));
+        let m = _mm256_sub_epi64(_mm256_set1_epi64x(64), n);
+        _mm256_or_si256(_mm256_sllv_epi64(x, n), _mm256_srlv_epi64(x, m))
+    }
+
+    /// Variable right rotation of 64-bit lanes with AVX2 shifts.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn rorv_epi64(x: __m256i, n: __m256i) -> __m256i {
+        let n = _mm256_and_si256(n, _mm256_set1_epi64x(63));
+        let m = _mm256_sub_epi64(_mm256_set1_epi64x(64), n);
+        _mm256_or_si256(_mm256_srlv_epi64(x, n), _mm256_sllv_epi64(x, m))
+    }
+
+    /// Generates an encryption and a decryption kernel processing `$lanes`
+    /// blocks per iteration, words `A` and `B` of each block being gathered
+    /// into one vector each.
+    macro_rules! rc5_kernels {
+        (
+            $feature:literal, $w:ty as $int:ty, $lanes:literal, $vec:ty,
+            $load:ident, $store:ident, $splat:ident,
+            $add:ident, $sub:ident, $xor:ident, $rotl:ident, $rotr:ident,
+            $encrypt:ident, $decrypt:ident
+        ) => {
+            /// Gathers the `A` and `B` words of `$lanes` blocks.
+            #[inline]
+            #[target_feature(enable = $feature)]
+            fn load(chunk: &[[$w; 2]]) -> ($vec, $vec) {
+                let words_a: [$w; $lanes] = from_fn(|ix| chunk[ix][0]);
+                let words_b: [$w; $lanes] = from_fn(|ix| chunk[ix][1]);
+
+                // SAFETY: both arrays are exactly one vector wide and
+                // unaligned loads are used.
+                unsafe {
+                    (
+                        $load(words_a.as_ptr().cast()),
+                        $load(words_b.as_ptr().cast()),
+                    )
+                }
+            }
+
+            /// Scatters the `A` and `B` vectors back into `$lanes` blocks.
+            #[inline]
+            #[target_feature(enable = $feature)]
+            fn store(chunk: &mut [[$w; 2]], word_a: $vec, word_b: $vec) {
+                let mut words_a = [0 as $w; $lanes];
+                let mut words_b = [0 as $w; $lanes];
+
+                // SAFETY: both arrays are exactly one vector wide and
+                // unaligned stores are used.
+                unsafe {
+                    $store(words_a.as_mut_ptr().cast(), word_a);
+                    $store(words_b.as_mut_ptr().cast(), word_b);
+                }
+
+                for (ix, block) in chunk.iter_mut().enumerate() {
+                    *block = [words_a[ix], words_b[ix]];
+                }
+            }
+
+            #[target_feature(enable = $feature)]
+            pub(crate) fn $encrypt(s_table: &[$w], blocks: &mut [[$w; 2]]) -> usize {
+                let mut chunks = blocks.chunks_exact_mut($lanes);
+
+                for chunk in &mut chunks {
+                    let (mut word_a, mut word_b) = load(chunk);
+
+                    word_a = $add(word_a, $splat(s_table[0] as $int));
+                    word_b = $add(word_b, $splat(s_table[1] as $int));
+
+                    for keys in s_table[2..].chunks_exact(2) {
+                        word_a = $add($rotl($xor(word_a, word_b), word_b), $splat(keys[0] as $int));
+                        word_b = $add($rotl($xor(word_b, word_a), word_a), $splat(keys[1] as $int));
+                    }
+
+                    store(chunk, word_a, word_b);
+                }
+
+                blocks.len() - blocks.len() % $lanes
+            }
+
+            #[target_feature(enable = $feature)]
+            pub(crate) fn $decrypt(s_table: &[$w], blocks: &mut [[$w; 2]]) -> usize {
+                let mut chunks = blocks.chunks_exact_mut($lanes);
+
+                for chunk in &mut chunks {
+                    let (mut word_a, mut word_b) = load(chunk);
+
+                    for keys in s_table[2..].rchunks_exact(2) {
+                        word_b = $xor($rotr($sub(word_b, $splat(keys[1] as $int)), word_a), word_a);
+                        word_a = $xor($rotr($sub(word_a, $splat(keys[0] as $int)), word_b), word_b);
+                    }
+
+                    word_b = $sub(word_b, $splat(s_table[1] as $int));
+                    word_a = $sub(word_a, $splat(s_table[0] as $int));
+
+                    store(chunk, word_a, word_b);
+                }
+
+                blocks.len() - blocks.len() % $lanes
+            }
+        };
+    }
+
+    mod u32_avx2 {
+        use super::*;
+
+        rc5_kernels!(
+            "avx2",
+            u32 as i32,
+            8,
+            __m256i,
+            _mm256_loadu_si256,
+            _mm256_storeu_si256,
+            _mm256_set1_epi32,
+            _mm256_add_epi32,
+            _mm256_sub_epi32,
+            _mm256_xor_si256,
+            rolv_epi32,
+            rorv_epi32,
+            encrypt_u32_avx2,
+            decrypt_u32_avx2
+        );
+    }
+
+    mod u64_avx2 {
+        use super::*;
+
+        rc5_kernels!(
+            "avx2",
+            u64 as i64,
+            4,
+            __m256i,
+            _mm256_loadu_si256,
+            _mm256_storeu_si256,
+            _mm256_set1_epi64x,
+            _mm256_add_epi64,
+            _mm256_sub_epi64,
+            _mm256_xor_si256,
+            rolv_epi64,
+            rorv_epi64,
+            encrypt_u64_avx2,
+            decrypt_u64_avx2
+        );
+    }
+
+    mod u32_avx512 {
+        use super::*;
+
+        rc5_kernels!(
+            "avx512f",
+            u32 as i32,
+            16,
+            __m512i,
+            _mm512_loadu_si512,
+            _mm512_storeu_si512,
+            _mm512_set1_epi32,
+            _mm512_add_epi32,
+            _mm512_sub_epi32,
+            _mm512_xor_si512,
+            _mm512_rolv_epi32,
+            _mm512_rorv_epi32,
+            encrypt_u32_avx512,
+            decrypt_u32_avx512
+        );
+    }
+
+    mod u64_avx512 {
+        use super::*;
+
+        rc5_kernels!(
+            "avx512f",
+            u64 as i64,
+            8,
+            __m512i,
+            _mm512_loadu_si512,
+            _mm512_storeu_si512,
+            _mm512_set1_epi64,
+            _mm512_add_epi64,
+            _mm512_sub_epi64,
+            _mm512_xor_si512,
+            _mm512_rolv_epi64,
+            _mm512_rorv_epi64,
+            encrypt_u64_avx512,
+            decrypt_u64_avx512
+        );
+    }
+
+    pub(crate) use self::{
+        u32_avx2::{decrypt_u32_avx2, encrypt_u32_avx2},
+        u32_avx512::{decrypt_u32_avx512, encrypt_u32_avx512},
+        u64_avx2::{decrypt_u64_avx2, encrypt_u64_avx2},
+        u64_avx512::{decrypt_u64_avx512, encrypt_u64_avx512},
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Debug;
+
+    use crate::{BlockCipher, RC5ControlBlock, Word, tests::random_iv};
+
+    /// Cross-checks a batch kernel against the scalar block encryption, over
+    /// round counts and a batch length which is not a multiple of any lane count.
+    fn assert_matches_scalar<W: Word + PartialEq + Debug>(
+        encrypt: impl Fn(&[W], &mut [[W; 2]]) -> usize,
+        decrypt: impl Fn(&[W], &mut [[W; 2]]) -> usize,
+    ) {
+        for rounds in [0, 1, 12, 255] {
+            let control_block = RC5ControlBlock::<W>::new(b"multi-lane", rounds).unwrap();
+            let plain_blocks: Vec<[W; 2]> = (0..37).map(|_| random_iv()).collect();
+            let expected: Vec<[W; 2]> = plain_blocks
+                .iter()
+                .map(|block| control_block.encrypt(*block))
+                .collect();
+
+            let mut blocks = plain_blocks.clone();
+            let processed = encrypt(control_block.s_table(), &mut blocks);
+            assert_eq!(expected[..processed], blocks[..processed]);
+            assert_eq!(plain_blocks[processed..], blocks[processed..]);
+
+            let mut blocks = expected.clone();
+            let processed = decrypt(control_block.s_table(), &mut blocks);
+            assert_eq!(plain_blocks[..processed], blocks[..processed]);
+            assert_eq!(expected[processed..], blocks[processed..]);
+        }
+    }
+
+    #[test]
+    fn dispatched_kernels_match_scalar() {
+        assert_matches_scalar::<u32>(super::encrypt_u32, super::decrypt_u32);
+        assert_matches_scalar::<u64>(super::encrypt_u64, super::decrypt_u64);
+    }
+
+    #[test]
+    fn encrypt_blocks_matches_scalar() {
+        fn check<W: Word + PartialEq + Debug>() {
+            let control_block = RC5ControlBlock::<W>::new(b"multi-lane", 12).unwrap();
+            let plain_blocks: Vec<[W; 2]> = (0..41).map(|_| random_iv()).collect();
+
+            let mut blocks = plain_blocks.clone();
+            control_block.encrypt_blocks(&mut blocks);
+            for (block, plain_block) in blocks.iter().zip(&plain_blocks) {
+                assert_eq!(*block, control_block.encrypt(*plain_block));
+            }
+
+            control_block.decrypt_blocks(&mut blocks);
+            assert_eq!(plain_blocks, blocks);
+        }
+
+        check::<u16>();
+        check::<u32>();
+        check::<u64>();
+        check::<u128>();
+    }
+
+    #[cfg(all(target_arch = "x86_64", feature = "std"))]
+    #[test]
+    fn x86_64_kernels_match_scalar() {
+        use crate::simd::x86_64::*;
+
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: `avx2` support was detected at runtime.
+            assert_matches_scalar::<u32>(
+                |s_table, blocks| unsafe { encrypt_u32_avx2(s_table, blocks) },
+                |s_table, blocks| unsafe { decrypt_u32_avx2(s_table, blocks) },
+            );
+            assert_matches_scalar::<u64>(
+                |s_table, blocks| unsafe { encrypt_u64_avx2(s_table, blocks) },
+                |s_table, blocks| unsafe { decrypt_u64_avx2(s_table, blocks) },
+            );
+        }
+
+        if std::is_x86_feature_detected!("avx512f") {
+            // SAFETY: `avx512f` support was detected at runtime.
+            assert_matches_scalar::<u32>(
+                |s_table, blocks| unsafe { encrypt_u32_avx512(s_table, blocks) },
+                |s_table, blocks| unsafe { decrypt_u32_avx512(s_table, blocks) },
+            );
+            assert_matches_scalar::<u64>(
+                |s_table, blocks| unsafe { encrypt_u64_avx512(s_table, blocks) },
+                |s_table, blocks| unsafe { decrypt_u64_avx512(s_table, blocks) },
+            );
+        }
+    }
+}
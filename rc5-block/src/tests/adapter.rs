@@ -0,0 +1,475 @@
+use aes::Aes128;
+use blowfish::Blowfish;
+use cbc::cipher::KeyInit;
+
+use crate::{BlockCipher, BlockCipherAdapter, Cipher, OperationMode, Reason};
+
+type Aes128Cipher = Cipher<BlockCipherAdapter<Aes128, u32, 4>, u32, 4>;
+
+fn aes_128(key: &str) -> Aes128Cipher {
+    let aes = Aes128::new_from_slice(&hex::decode(key).unwrap()).unwrap();
+    Cipher::new(BlockCipherAdapter::new(aes).unwrap())
+}
+
+#[test]
+fn block_size_mismatch_is_rejected() {
+    let aes = Aes128::new(&[0u8; 16].into());
+    assert!(matches!(
+        BlockCipherAdapter::<_, u32, 2>::new(aes),
+        Err(Reason::WordSize)
+    ));
+}
+
+// FIPS-197 Appendix C.1, the second block is the PKCS#7 padding block.
+#[test]
+fn aes_128_ecb_vector() -> Result<(), Reason> {
+    let cipher = aes_128("000102030405060708090a0b0c0d0e0f");
+    let plain_text = hex::decode("00112233445566778899aabbccddeeff").unwrap();
+
+    let ct_bytes = cipher.encrypt(&plain_text, OperationMode::ECB)?;
+    assert_eq!(
+        "69c4e0d86a7b0430d8cdb78070b4c55a",
+        hex::encode(&ct_bytes[..16])
+    );
+    assert_eq!(plain_text, cipher.decrypt(&ct_bytes, OperationMode::ECB)?);
+
+    Ok(())
+}
+
+// NIST SP 800-38A F.2.1 CBC-AES128.Encrypt
+#[test]
+fn aes_128_cbc_vector() -> Result<(), Reason> {
+    let cipher = aes_128("2b7e151628aed2a6abf7158809cf4f3c");
+    let iv = cipher.parse_iv_from_hex("000102030405060708090a0b0c0d0e0f")?;
+    let plain_text =
+        hex::decode("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51").unwrap();
+
+    let ct_bytes = cipher.encrypt(&plain_text, OperationMode::CBC { iv })?;
+    assert_eq!(
+        "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2",
+        hex::encode(&ct_bytes[..32])
+    );
+    assert_eq!(
+        plain_text,
+        cipher.decrypt(&ct_bytes, OperationMode::CBC { iv })?
+    );
+
+    Ok(())
+}
+
+// OpenSSL `igetest.c` AES-128-IGE vectors, the last block is the PKCS#7
+// padding block.
+#[test]
+fn aes_128_ige_vectors() -> Result<(), Reason> {
+    for (key, iv, plain_text, cipher_text) in [
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "1a8519a6557be652e9da8e43da4ef4453cf456b4ca488aa383c79c98b34797cb",
+        ),
+        (
+            "5468697320697320616e20696d706c65",
+            "6d656e746174696f6e206f6620494745206d6f646520666f72204f70656e5353",
+            "99706487a1cde613bc6de0b6f24b1c7aa448c8b9c3403e3467a8cad89340f53b",
+            "4c2e204c6574277320686f70652042656e20676f74206974207269676874210a",
+        ),
+    ] {
+        let cipher = aes_128(key);
+        let iv1 = cipher.parse_iv_from_hex(&iv[..32])?;
+        let iv2 = cipher.parse_iv_from_hex(&iv[32..])?;
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = cipher.encrypt(&plain_text, OperationMode::IGE { iv1, iv2 })?;
+        assert_eq!(cipher_text, hex::encode(&ct_bytes[..32]));
+        assert_eq!(
+            plain_text,
+            cipher.decrypt(&ct_bytes, OperationMode::IGE { iv1, iv2 })?
+        );
+    }
+
+    Ok(())
+}
+
+// RFC 3962 Appendix B AES-128 CTS vectors, Kerberos uses the cipher-text
+// stealing of RFC 2040 with a zero IV.
+#[test]
+fn aes_128_cts_vectors() -> Result<(), Reason> {
+    let cipher = aes_128("636869636b656e207465726979616b69");
+    let iv = [0; 4];
+    let message = b"I would like the General Gau's Chicken, please, and wonton soup.";
+
+    for (len, cipher_text) in [
+        (17, "c6353568f2bf8cb4d8a580362da7ff7f97"),
+        (
+            31,
+            "fc00783e0efdb2c1d445d4c8eff7ed2297687268d6ecccc0c07b25e25ecfe5",
+        ),
+        (
+            32,
+            "39312523a78662d5be7fcbcc98ebf5a897687268d6ecccc0c07b25e25ecfe584",
+        ),
+        (
+            47,
+            "97687268d6ecccc0c07b25e25ecfe584b3fffd940c16a18c1b5549d2f838029e39312523a78662d5be7fcbcc98ebf5",
+        ),
+        (
+            48,
+            "97687268d6ecccc0c07b25e25ecfe5849dad8bbb96c4cdc03bc103e1a194bbd839312523a78662d5be7fcbcc98ebf5a8",
+        ),
+        (
+            64,
+            "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a84807efe836ee89a526730dbc2f7bc8409dad8bbb96c4cdc03bc103e1a194bbd8",
+        ),
+    ] {
+        let ct_bytes = cipher.encrypt(&message[..len], OperationMode::CBC_CTS { iv })?;
+        assert_eq!(cipher_text, hex::encode(&ct_bytes));
+        assert_eq!(
+            message[..len],
+            cipher.decrypt(&ct_bytes, OperationMode::CBC_CTS { iv })?
+        );
+    }
+
+    Ok(())
+}
+
+// AES-128 EAX vectors of the EAX paper (Bellare, Rogaway and Wagner).
+#[test]
+fn aes_128_eax_vectors() -> Result<(), Reason> {
+    for (key, nonce, header, plain_text, cipher_text) in [
+        (
+            "233952dee4d5ed5f9b9c6d6ff80ff478",
+            "62ec67f9c3a4a407fcb2a8c49031a8b3",
+            "6bfb914fd07eae6b",
+            "",
+            "e037830e8389f27b025a2d6527e79d01",
+        ),
+        (
+            "91945d3f4dcbee0bf45ef52255f095a4",
+            "becaf043b0a23d843194ba972c66debd",
+            "fa3bfd4806eb53fa",
+            "f7fb",
+            "19dd5c4c9331049d0bdab0277408f67967e5",
+        ),
+        (
+            "01f74ad64077f2e704c0f60ada3dd523",
+            "70c3db4f0d26368400a10ed05d2bff5e",
+            "234a3463c1264ac6",
+            "1a47cb4933",
+            "d851d5bae03a59f238a23e39199dc9266626c40f80",
+        ),
+    ] {
+        let cipher = aes_128(key);
+        let mode = OperationMode::EAX {
+            nonce: cipher.parse_iv_from_hex(nonce)?,
+            tag_len: 16,
+        };
+        let header = hex::decode(header).unwrap();
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = cipher.encrypt_aead(&plain_text, &header, mode)?;
+        assert_eq!(cipher_text, hex::encode(&ct_bytes));
+        assert_eq!(plain_text, cipher.decrypt_aead(&ct_bytes, &header, mode)?);
+    }
+
+    Ok(())
+}
+
+// AES-128 CCM packet vectors #1, #2 and #7 of RFC 3610, with the 8 bytes
+// header 0001..07 as associated data.
+#[test]
+fn aes_128_ccm_vectors() -> Result<(), Reason> {
+    let cipher = aes_128("c0c1c2c3c4c5c6c7c8c9cacbcccdcecf");
+    let header = hex::decode("0001020304050607").unwrap();
+
+    for (nonce, tag_len, plain_text, cipher_text) in [
+        (
+            "00000003020100a0a1a2a3a4a5",
+            8,
+            "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+            "588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0",
+        ),
+        (
+            "00000004030201a0a1a2a3a4a5",
+            8,
+            "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "72c91a36e135f8cf291ca894085c87e3cc15c439c9e43a3ba091d56e10400916",
+        ),
+        (
+            "00000009080706a0a1a2a3a4a5",
+            10,
+            "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+            "0135d1b2c95f41d5d1d4fec185d166b8094e999dfed96c048c56602c97acbb7490",
+        ),
+    ] {
+        let mode = OperationMode::CCM {
+            nonce: cipher.nonce_from_bytes(hex::decode(nonce).unwrap())?,
+            length_size: 2,
+            tag_len,
+        };
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = cipher.encrypt_aead(&plain_text, &header, mode)?;
+        assert_eq!(cipher_text, hex::encode(&ct_bytes));
+        assert_eq!(plain_text, cipher.decrypt_aead(&ct_bytes, &header, mode)?);
+    }
+
+    Ok(())
+}
+
+// AES-128 OCB vectors of RFC 7253 appendix A, the 128-bit tag samples
+// under the key 000102..0f and the 96-bit tag sample under 0f0e..00.
+#[test]
+fn aes_128_ocb_vectors() -> Result<(), Reason> {
+    let sample = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f2021222324252627";
+
+    for (key, nonce, tag_len, ad, plain_text, cipher_text) in [
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "bbaa99887766554433221100",
+            16,
+            "",
+            "",
+            "785407bfffc8ad9edcc5520ac9111ee6",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "bbaa99887766554433221101",
+            16,
+            "0001020304050607",
+            "0001020304050607",
+            "6820b3657b6f615a5725bda0d3b4eb3a257c9af1f8f03009",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "bbaa99887766554433221102",
+            16,
+            "0001020304050607",
+            "",
+            "81017f8203f081277152fade694a0a00",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "bbaa99887766554433221103",
+            16,
+            "",
+            "0001020304050607",
+            "45dd69f8f5aae72414054cd1f35d82760b2cd00d2f99bfa9",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "bbaa9988776655443322110f",
+            16,
+            sample,
+            sample,
+            "4412923493c57d5de0d700f753cce0d1d2d95060122e9f15a5ddbfc5787e50b5cc55ee507bcb084e240a353649432ac6c1bda9acba93f56d",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "bbaa99887766554433221111",
+            16,
+            "",
+            sample,
+            "163896d79c0334d0d11546a0384518288785a656d8ebb91003eacc21d6f18bccb1077dd2d22121558f77f47680d48eca57240ec0c8e51f7e",
+        ),
+        (
+            "0f0e0d0c0b0a09080706050403020100",
+            "bbaa9988776655443322110d",
+            12,
+            sample,
+            sample,
+            "1792a4e31e0755fb03e31b22116e6c2ddf9efd6e33d536f1a0124b0a55bae884ed93481529c76b6ad0c515f4d1cdd4fdac4f02aa",
+        ),
+    ] {
+        let cipher = aes_128(key);
+        let nonce = hex::decode(nonce).unwrap();
+        let mode = OperationMode::OCB {
+            nonce: cipher.nonce_from_bytes(&nonce)?,
+            nonce_len: nonce.len(),
+            tag_len,
+        };
+        let ad = hex::decode(ad).unwrap();
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = cipher.encrypt_aead(&plain_text, &ad, mode)?;
+        assert_eq!(cipher_text, hex::encode(&ct_bytes));
+        assert_eq!(plain_text, cipher.decrypt_aead(&ct_bytes, &ad, mode)?);
+    }
+
+    Ok(())
+}
+
+// Blowfish OCB vectors of the 64-bit block variant under the 16 bytes key
+// 000102..0f, printed by `tests/vectors/generate.py` over the Blowfish of
+// the `cryptography` package. Blowfish keeps them apart from the RC5 ones.
+#[test]
+fn blowfish_ocb_vectors() -> Result<(), Reason> {
+    let blowfish =
+        <Blowfish>::new_from_slice(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap())
+            .unwrap();
+    let cipher = Cipher::new(BlockCipherAdapter::<_, u32, 2>::new(blowfish)?);
+    let sample = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f2021222324252627";
+    let partial = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122";
+
+    for (nonce, tag_len, ad, plain_text, cipher_text) in [
+        ("00010203040506", 8, "", "", "7885169d552ec4fe"),
+        (
+            "00010203040506",
+            8,
+            "0001020304050607",
+            "0001020304050607",
+            "60e0466cc7dc03b20e38c3859d907772",
+        ),
+        (
+            "0a0b0c",
+            8,
+            sample,
+            sample,
+            "5e8fab671bd310f152c913d87c3c4de48fa0c7fa6eed99605becdac3e10cdbe6f18089b4355f330d0ac69a56248aabc5",
+        ),
+        (
+            "ff",
+            4,
+            "",
+            partial,
+            "e653c5ec8ef1ef5015e7ddf748a8d43a0bbb0b44009823b0a2981ead84f1405b652635e1e45fb4",
+        ),
+    ] {
+        let nonce = hex::decode(nonce).unwrap();
+        let mode = OperationMode::OCB {
+            nonce: cipher.nonce_from_bytes(&nonce)?,
+            nonce_len: nonce.len(),
+            tag_len,
+        };
+        let ad = hex::decode(ad).unwrap();
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = cipher.encrypt_aead(&plain_text, &ad, mode)?;
+        assert_eq!(cipher_text, hex::encode(&ct_bytes));
+        assert_eq!(plain_text, cipher.decrypt_aead(&ct_bytes, &ad, mode)?);
+    }
+
+    Ok(())
+}
+
+// AES-SIV vectors A.1 and A.2 of RFC 5297, the 256-bit keys split into the
+// S2V and the CTR halves.
+#[test]
+fn aes_128_siv_vectors() -> Result<(), Reason> {
+    let a2_ad = [
+        "00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100",
+        "102030405060708090a0",
+        "09f911029d74e35bd84156c5635688c0",
+    ];
+
+    for (mac_key, ctr_key, ad, plain_text, cipher_text) in [
+        (
+            "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0",
+            "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+            &["101112131415161718191a1b1c1d1e1f2021222324252627"][..],
+            "112233445566778899aabbccddee",
+            "85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5c",
+        ),
+        (
+            "7f7e7d7c7b7a79787776757473727170",
+            "404142434445464748494a4b4c4d4e4f",
+            &a2_ad[..],
+            "7468697320697320736f6d6520706c61696e7465787420746f20656e6372797074207573696e67205349562d414553",
+            "7bdb6e3b432667eb06f4d14bff2fbd0fcb900f2fddbe404326601965c889bf17dba77ceb094fa663b7a3f748ba8af829ea64ad544a272e9c485b62a3fd5c0d",
+        ),
+    ] {
+        let mac_block = Aes128::new_from_slice(&hex::decode(mac_key).unwrap()).unwrap();
+        let cipher = aes_128(ctr_key).with_siv_key(BlockCipherAdapter::new(mac_block).unwrap());
+
+        let ad: Vec<Vec<u8>> = ad.iter().map(|a| hex::decode(a).unwrap()).collect();
+        let ad: Vec<&[u8]> = ad.iter().map(Vec::as_slice).collect();
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = cipher.seal_deterministic(&ad, &plain_text)?;
+        assert_eq!(cipher_text, hex::encode(&ct_bytes));
+        assert_eq!(plain_text, cipher.open_deterministic(&ad, &ct_bytes)?);
+    }
+
+    Ok(())
+}
+
+// NIST SP 800-38A F.5.1 CTR-AES128.Encrypt, first block only since the
+// SP 800-38A counter is incremented big-endian.
+#[test]
+fn aes_128_ctr_vector() -> Result<(), Reason> {
+    let cipher = aes_128("2b7e151628aed2a6abf7158809cf4f3c");
+    let nonce_and_counter = cipher.parse_iv_from_hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff")?;
+    let plain_text = hex::decode("6bc1bee22e409f96e93d7e117393172a").unwrap();
+
+    let ct_bytes = cipher.encrypt(&plain_text, OperationMode::CTR { nonce_and_counter })?;
+    assert_eq!("874d6191b620e3261bef6864990db6ce", hex::encode(&ct_bytes));
+
+    Ok(())
+}
+
+// Blowfish with an all-zero key and plain-text (Eric Young's test vectors).
+#[test]
+fn blowfish_ecb_vector() -> Result<(), Reason> {
+    let blowfish = <Blowfish>::new_from_slice(&[0u8; 8]).unwrap();
+    let cipher = Cipher::new(BlockCipherAdapter::<_, u32, 2>::new(blowfish)?);
+
+    let ct_bytes = cipher.encrypt(&[0u8; 8], OperationMode::ECB)?;
+    assert_eq!("4ef997456198dd78", hex::encode(&ct_bytes[..8]));
+
+    Ok(())
+}
+
+macro_rules! adapter_round_trip {
+    ($( $fn_name:ident: ( $cipher:ty , $w:ty , $n:expr , $key:expr , $pt:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let block_cipher = <$cipher>::new_from_slice(&$key).unwrap();
+                let cipher = Cipher::new(BlockCipherAdapter::<_, $w, $n>::new(block_cipher)?);
+                let plain_text = $pt.as_bytes().to_vec();
+
+                for mode in [
+                    OperationMode::ECB,
+                    OperationMode::CBC { iv: super::random_iv() },
+                    OperationMode::CTR { nonce_and_counter: super::random_nonce_and_counter() },
+                ] {
+                    let ct_bytes = cipher.encrypt(&plain_text, mode)?;
+                    let dt_bytes = cipher.decrypt(&ct_bytes, mode)?;
+
+                    assert_eq!(
+                        plain_text,
+                        dt_bytes,
+                        "{}",
+                        format!("Round trip failed for {}", cipher.control_block().control_block_version())
+                    );
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+adapter_round_trip! {
+    aes_128_u32_round_trip: (
+        Aes128,
+        u32,
+        4,
+        [0u8; 16],
+        "This is AES-128 driven through the adapter."
+    ),
+    aes_128_u64_round_trip: (
+        Aes128,
+        u64,
+        2,
+        [0u8; 16],
+        "This is AES-128 driven through the adapter."
+    ),
+    blowfish_round_trip: (
+        Blowfish,
+        u32,
+        2,
+        [0u8; 16],
+        "This is Blowfish driven through the adapter."
+    ),
+}
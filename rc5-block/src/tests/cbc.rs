@@ -7,7 +7,7 @@ macro_rules! rc5_cbc_round_trip {
             fn $fn_name() -> Result<(), Reason> {
                 let cipher = rc5_cipher::<$w>(&$key, $rounds)?;
                 let plain_text = $pt.as_bytes().to_vec();
-                let random_nonce = crate::random_nonce_and_counter();
+                let random_nonce = super::random_nonce_and_counter();
 
                 let ct_bytes = cipher.encrypt(&plain_text, OperationMode::CTR {nonce_and_counter: random_nonce })?;
                 let dt_bytes = cipher.decrypt(&ct_bytes, OperationMode::CTR {nonce_and_counter: random_nonce })?;
@@ -26,6 +26,12 @@ macro_rules! rc5_cbc_round_trip {
 }
 
 rc5_cbc_round_trip! {
+    rc5_ctr_8_4_12:  (
+        u8,
+        [0u8; 4],
+        12,
+        "This is RC5-CTR 8-bit word size test."
+    ),
     rc5_ctr_16_8_8:  (
         u16,
         [0u8; 8],
@@ -50,4 +56,53 @@ rc5_cbc_round_trip! {
         20,
         "This is RC5-CTR 64-bit word size test."
     ),
+    rc5_ctr_24_12_4:  (
+        crate::U24,
+        [0u8; 12],
+        4,
+        "This is RC5-CTR 24-bit word size test."
+    ),
+    rc5_ctr_80_12_12:  (
+        crate::U80,
+        [0u8; 12],
+        12,
+        "This is RC5-CTR 80-bit word size test."
+    ),
+    rc5_ctr_256_32_20:  (
+        crate::U256,
+        [0u8; 32],
+        20,
+        "This is RC5-CTR 256-bit word size test."
+    ),
+}
+
+#[test]
+fn rc5_ctr_8_rejects_counter_reuse() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u8>(&[0u8; 16], 12)?;
+    let mode = OperationMode::CTR {
+        nonce_and_counter: [0x5a, 0xff],
+    };
+
+    let ct_bytes = cipher.encrypt(&[0u8; 512], mode)?;
+    assert_eq!(cipher.decrypt(&ct_bytes, mode)?, [0u8; 512]);
+    assert!(matches!(
+        cipher.encrypt(&[0u8; 513], mode),
+        Err(Reason::MessageTooLong(512))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn rc5_ctr_16_keeps_long_messages() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u16>(&[0u8; 16], 12)?;
+    let mode = OperationMode::CTR {
+        nonce_and_counter: [0x5a5a, 0xfff0],
+    };
+    let plain_text = vec![0x42u8; 300 * 1024];
+
+    let ct_bytes = cipher.encrypt(&plain_text, mode)?;
+    assert_eq!(cipher.decrypt(&ct_bytes, mode)?, plain_text);
+
+    Ok(())
 }
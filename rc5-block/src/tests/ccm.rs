@@ -0,0 +1,266 @@
+use crate::{BlockCipher, OperationMode, Reason, rc5_cipher, rc6_cipher};
+
+macro_rules! ccm_round_trip {
+    ($( $fn_name:ident: ( $cipher:expr , $w:ty , $n:expr ) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = $cipher;
+                let bs = cipher.control_block().block_size();
+                let plain_text = b"This is CCM authenticated encryption.";
+                let nonce = super::random_iv();
+
+                for length_size in 2..=(bs - 2).min(8) {
+                    for tag_len in (4..=bs.min(16)).step_by(2) {
+                        let mode = OperationMode::<$w, $n>::CCM { nonce, length_size, tag_len };
+
+                        for len in [0, 1, bs, plain_text.len()] {
+                            for ad in [&b""[..], b"header"] {
+                                let ct_bytes = cipher.encrypt_aead(&plain_text[..len], ad, mode)?;
+                                assert_eq!(len + tag_len, ct_bytes.len());
+                                assert_eq!(plain_text[..len], cipher.decrypt_aead(&ct_bytes, ad, mode)?);
+                            }
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+ccm_round_trip! {
+    rc5_ccm_16_8_12: (rc5_cipher::<u16>([0u8; 8], 12)?, u16, 2),
+    rc5_ccm_32_16_12: (rc5_cipher::<u32>([0u8; 16], 12)?, u32, 2),
+    rc5_ccm_64_24_20: (rc5_cipher::<u64>([0u8; 24], 20)?, u64, 2),
+    rc5_ccm_80_12_12: (rc5_cipher::<crate::U80>([0u8; 12], 12)?, crate::U80, 2),
+    rc6_ccm_32_16_20: (rc6_cipher::<u32>([0u8; 16], 20)?, u32, 4),
+}
+
+// RC5-32/12 CCM vectors under the 16 bytes key 000102..0f, printed by
+// `tests/vectors/generate.py`, whose RFC 3610 formatting of the 64-bit
+// blocks matches `AESCCM` of the `cryptography` package for 128-bit ones.
+#[test]
+fn rc5_32_ccm_vectors() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>(hex::decode("000102030405060708090a0b0c0d0e0f").unwrap(), 12)?;
+
+    for (nonce, length_size, tag_len, ad, plain_text, cipher_text, tag) in [
+        ("0001020304", 2, 8, "", "", "", "a20a6e9e3464e2e6"),
+        (
+            "0001020304",
+            2,
+            8,
+            "0a0b0c",
+            "48656c6c6f",
+            "21e434567a",
+            "14dadc571138039d",
+        ),
+        (
+            "a0a1a2a3",
+            3,
+            6,
+            "0001020304050607",
+            "000102030405060708090a0b0c0d0e0f1011121314",
+            "dfb897a95cbe422e5ce02e7a8894bc72f25ad26b2e",
+            "885745c6858c",
+        ),
+        (
+            "10111213",
+            3,
+            4,
+            "",
+            "000000000000000000000000000000000000000000000000",
+            "8f6cf8e5e2e82260432d2598622e040fe3aeb86901d40656",
+            "63f3a933",
+        ),
+    ] {
+        let mode = OperationMode::CCM {
+            nonce: cipher.nonce_from_bytes(hex::decode(nonce).unwrap())?,
+            length_size,
+            tag_len,
+        };
+        let ad = hex::decode(ad).unwrap();
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = cipher.encrypt_aead(&plain_text, &ad, mode)?;
+        assert_eq!(format!("{cipher_text}{tag}"), hex::encode(&ct_bytes));
+        assert_eq!(plain_text, cipher.decrypt_aead(&ct_bytes, &ad, mode)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn ccm_rejects_altered_messages() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let nonce = super::random_iv();
+    let mode = OperationMode::CCM {
+        nonce,
+        length_size: 3,
+        tag_len: 8,
+    };
+    let ct_bytes = cipher.encrypt_aead(b"Authenticated message", b"header", mode)?;
+
+    for ix in 0..ct_bytes.len() {
+        let mut altered = ct_bytes.clone();
+        altered[ix] ^= 0x01;
+        assert!(matches!(
+            cipher.decrypt_in_place_aead(&mut altered, b"header", mode),
+            Err(Reason::AuthenticationFailed)
+        ));
+
+        // The plain-text is encrypted back when the tag does not match.
+        altered[ix] ^= 0x01;
+        assert_eq!(ct_bytes, altered);
+    }
+
+    for ad in [&b"Header"[..], b"", b"header!"] {
+        assert!(matches!(
+            cipher.decrypt_aead(&ct_bytes, ad, mode),
+            Err(Reason::AuthenticationFailed)
+        ));
+    }
+
+    // The tag length is authenticated, a truncated tag does not verify.
+    let truncated = OperationMode::CCM {
+        nonce,
+        length_size: 3,
+        tag_len: 6,
+    };
+    let ct_len = ct_bytes.len();
+    assert!(matches!(
+        cipher.decrypt_aead(&ct_bytes[..ct_len - 2], b"header", truncated),
+        Err(Reason::AuthenticationFailed)
+    ));
+    assert!(matches!(
+        cipher.decrypt_aead(&ct_bytes[..5], b"header", mode),
+        Err(Reason::AuthenticationFailed)
+    ));
+
+    Ok(())
+}
+
+// A tampered tag leaves the cipher-text in the buffer, not the plain-text
+// decrypted before the tag was checked.
+#[test]
+fn ccm_restores_cipher_text_on_tag_mismatch() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u64>([0u8; 24], 16)?;
+    let mode = OperationMode::CCM {
+        nonce: cipher.nonce_from_bytes(b"13-byte nonce")?,
+        length_size: 2,
+        tag_len: 16,
+    };
+    let ct_bytes = cipher.encrypt_aead(b"A message spanning a few blocks", b"header", mode)?;
+    let ct_len = ct_bytes.len() - 16;
+
+    for ix in ct_len..ct_bytes.len() {
+        let mut buf = ct_bytes.clone();
+        buf[ix] ^= 0x80;
+        assert!(matches!(
+            cipher.decrypt_in_place_aead(&mut buf, b"header", mode),
+            Err(Reason::AuthenticationFailed)
+        ));
+        assert_eq!(ct_bytes[..ct_len], buf[..ct_len]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn ccm_in_place() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let mode = OperationMode::CCM {
+        nonce: super::random_iv(),
+        length_size: 2,
+        tag_len: 4,
+    };
+    let plain_text = b"In place CCM";
+    let expected = cipher.encrypt_aead(plain_text, b"ad", mode)?;
+
+    let mut buf = [0u8; 32];
+    buf[..plain_text.len()].copy_from_slice(plain_text);
+    let ct_bytes = cipher.encrypt_in_place_aead(&mut buf, plain_text.len(), b"ad", mode)?;
+    assert_eq!(expected, ct_bytes);
+    assert_eq!(cipher.encrypted_len(plain_text.len(), mode), ct_bytes.len());
+
+    let ct_len = ct_bytes.len();
+    let dt_bytes = cipher.decrypt_in_place_aead(&mut buf[..ct_len], b"ad", mode)?;
+    assert_eq!(plain_text, dt_bytes);
+
+    Ok(())
+}
+
+#[test]
+fn ccm_rejects_invalid_parameters() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let nonce = super::random_iv();
+
+    for tag_len in [0, 2, 5, 10, 16] {
+        let mode = OperationMode::CCM {
+            nonce,
+            length_size: 2,
+            tag_len,
+        };
+        assert!(matches!(
+            cipher.encrypt(b"message", mode),
+            Err(Reason::InvalidTagLength(len)) if len == tag_len
+        ));
+    }
+
+    for length_size in [0, 1, 8, 9] {
+        let mode = OperationMode::CCM {
+            nonce,
+            length_size,
+            tag_len: 8,
+        };
+        assert!(matches!(
+            cipher.encrypt(b"message", mode),
+            Err(Reason::InvalidLengthField(size)) if size == length_size
+        ));
+    }
+
+    // The length field must leave at least a 1 byte nonce in the block.
+    let rc5_16 = rc5_cipher::<u16>([0u8; 8], 12)?;
+    let mode = OperationMode::CCM {
+        nonce: [0; 2],
+        length_size: 3,
+        tag_len: 4,
+    };
+    assert!(matches!(
+        rc5_16.encrypt_aead(b"message", b"", mode),
+        Err(Reason::InvalidLengthField(3))
+    ));
+    let mode = OperationMode::CCM {
+        nonce,
+        length_size: 7,
+        tag_len: 8,
+    };
+    assert!(matches!(
+        cipher.encrypt_aead(b"message", b"", mode),
+        Err(Reason::InvalidLengthField(7))
+    ));
+    assert!(matches!(
+        cipher.decrypt_aead(&[0u8; 16], b"", mode),
+        Err(Reason::InvalidLengthField(7))
+    ));
+
+    // A 2 bytes length field holds messages up to 65535 bytes.
+    let mode = OperationMode::CCM {
+        nonce,
+        length_size: 2,
+        tag_len: 8,
+    };
+    assert!(cipher.encrypt(&[0u8; 0xffff], mode).is_ok());
+    assert!(matches!(
+        cipher.encrypt(&[0u8; 0x10000], mode),
+        Err(Reason::LengthFieldOverflow(2))
+    ));
+
+    assert!(matches!(
+        cipher.nonce_from_bytes([0u8; 9]),
+        Err(Reason::InvalidNonceLength { max: 8, current: 9 })
+    ));
+
+    Ok(())
+}
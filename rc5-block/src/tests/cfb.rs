@@ -0,0 +1,161 @@
+use crate::{BlockCipher, OperationMode, Reason, rc5_cipher, rc6_cipher};
+
+macro_rules! rc5_cfb_round_trip {
+    ($( $fn_name:ident: ( $w:ty , $key:expr , $rounds:expr , $pt:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = rc5_cipher::<$w>(&$key, $rounds)?;
+                let plain_text = $pt.as_bytes().to_vec();
+                let random_iv = super::random_iv();
+                let block_bits = cipher.control_block().block_size() * 8;
+
+                for segment_bits in [8, block_bits] {
+                    let mode = OperationMode::CFB { iv: random_iv, segment_bits };
+                    let ct_bytes = cipher.encrypt(&plain_text, mode)?;
+                    assert_eq!(plain_text.len(), ct_bytes.len());
+
+                    let dt_bytes = cipher.decrypt(&ct_bytes, mode)?;
+                    assert_eq!(
+                        plain_text,
+                        dt_bytes,
+                        "{}",
+                        format!(
+                            "Round trip failed for {} CFB-{segment_bits}",
+                            cipher.control_block().control_block_version()
+                        )
+                    );
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+rc5_cfb_round_trip! {
+    rc5_cfb_8_4_12:  (
+        u8,
+        [0u8; 4],
+        12,
+        "This is RC5-CFB 8-bit word size test."
+    ),
+    rc5_cfb_16_8_12:  (
+        u16,
+        [0u8; 8],
+        12,
+        "This is RC5-CFB 16-bit word size test."
+    ),
+    rc5_cfb_32_16_12:  (
+        u32,
+        [0u8; 16],
+        12,
+        "This is RC5-CFB 32-bit word size test."
+    ),
+    rc5_cfb_64_24_20:  (
+        u64,
+        [0u8; 24],
+        20,
+        "This is RC5-CFB 64-bit word size test."
+    ),
+    rc5_cfb_128_32_20:  (
+        u128,
+        [0u8; 32],
+        20,
+        "This is RC5-CFB 128-bit word size test."
+    ),
+    rc5_cfb_24_12_4:  (
+        crate::U24,
+        [0u8; 12],
+        4,
+        "This is RC5-CFB 24-bit word size test."
+    ),
+    rc5_cfb_80_12_12:  (
+        crate::U80,
+        [0u8; 12],
+        12,
+        "This is RC5-CFB 80-bit word size test."
+    ),
+}
+
+#[test]
+fn cfb_segments_feed_back_cipher_text() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let control_block = cipher.control_block();
+    let iv = [0x03020100, 0x07060504];
+    let plain_text = b"CFB feedback";
+
+    // CFB-8: the register is shifted one cipher-text byte at a time.
+    let ct_bytes = cipher.encrypt(
+        plain_text,
+        OperationMode::CFB {
+            iv,
+            segment_bits: 8,
+        },
+    )?;
+    let mut register = control_block.generate_bytes_stream(&[iv]);
+    for (pt, ct) in plain_text.iter().zip(&ct_bytes) {
+        let block = control_block.generate_blocks(&register)[0];
+        let key_stream = control_block.generate_bytes_stream(&[control_block.encrypt(block)]);
+        assert_eq!(*ct, pt ^ key_stream[0]);
+
+        register.remove(0);
+        register.push(*ct);
+    }
+
+    // Full-block CFB: each cipher-text block is the next register.
+    let ct_bytes = cipher.encrypt(
+        plain_text,
+        OperationMode::CFB {
+            iv,
+            segment_bits: 64,
+        },
+    )?;
+    let mut register = iv;
+    for (pt, ct) in plain_text.chunks(8).zip(ct_bytes.chunks(8)) {
+        let key_stream = control_block.generate_bytes_stream(&[control_block.encrypt(register)]);
+        for (jx, byte) in pt.iter().enumerate() {
+            assert_eq!(ct[jx], byte ^ key_stream[jx]);
+        }
+        if ct.len() == 8 {
+            register = control_block.generate_blocks(ct)[0];
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn cfb_intermediate_segments() -> Result<(), Reason> {
+    let cipher = rc6_cipher::<u32>([0u8; 16], 20)?;
+    let plain_text = b"This is RC6-CFB with 32-bit segments, not block aligned.";
+    let iv = super::random_iv();
+
+    for segment_bits in [8, 16, 32, 64, 128] {
+        let mode = OperationMode::CFB { iv, segment_bits };
+        let ct_bytes = cipher.encrypt(plain_text, mode)?;
+        assert_eq!(plain_text.as_slice(), cipher.decrypt(&ct_bytes, mode)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn cfb_invalid_segments() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let iv = super::random_iv();
+
+    for segment_bits in [0, 1, 12, 72, 128] {
+        let mode = OperationMode::CFB { iv, segment_bits };
+        assert!(matches!(
+            cipher.encrypt(b"segments", mode),
+            Err(Reason::InvalidSegment(bits)) if bits == segment_bits
+        ));
+        assert!(matches!(
+            cipher.decrypt(b"segments", mode),
+            Err(Reason::InvalidSegment(bits)) if bits == segment_bits
+        ));
+    }
+
+    Ok(())
+}
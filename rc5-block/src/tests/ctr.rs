@@ -7,7 +7,7 @@ macro_rules! rc5_cbc_round_trip {
             fn $fn_name() -> Result<(), Reason> {
                 let cipher = rc5_cipher::<$w>(&$key, $rounds)?;
                 let plain_text = $pt.as_bytes().to_vec();
-                let random_iv = crate::random_iv();
+                let random_iv = super::random_iv();
 
                 let ct_bytes = cipher.encrypt(&plain_text, OperationMode::CBC { iv: random_iv })?;
                 let dt_bytes = cipher.decrypt(&ct_bytes, OperationMode::CBC { iv: random_iv })?;
@@ -26,6 +26,12 @@ macro_rules! rc5_cbc_round_trip {
 }
 
 rc5_cbc_round_trip! {
+    rc5_cbc_8_4_12:  (
+        u8,
+        [0u8; 4],
+        12,
+        "This is RC5-CBC 8-bit word size test."
+    ),
     rc5_cbc_16_8_8:  (
         u16,
         [0u8; 8],
@@ -50,4 +56,22 @@ rc5_cbc_round_trip! {
         20,
         "This is RC5-CBC 64-bit word size test."
     ),
+    rc5_cbc_24_12_4:  (
+        crate::U24,
+        [0u8; 12],
+        4,
+        "This is RC5-CBC 24-bit word size test."
+    ),
+    rc5_cbc_80_12_12:  (
+        crate::U80,
+        [0u8; 12],
+        12,
+        "This is RC5-CBC 80-bit word size test."
+    ),
+    rc5_cbc_256_32_20:  (
+        crate::U256,
+        [0u8; 32],
+        20,
+        "This is RC5-CBC 256-bit word size test."
+    ),
 }
@@ -0,0 +1,146 @@
+use crate::{BlockCipher, OperationMode, Reason, rc5_cipher, rc6_cipher};
+
+macro_rules! cts_round_trip {
+    ($( $fn_name:ident: ( $cipher:expr , $w:ty , $n:expr ) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = $cipher;
+                let bs = cipher.control_block().block_size();
+                let plain_text: Vec<u8> = (0..4 * bs as u8).collect();
+
+                for mode in [
+                    OperationMode::<$w, $n>::CBC_CTS { iv: super::random_iv() },
+                    OperationMode::ECB_CTS,
+                ] {
+                    for len in bs..=plain_text.len() {
+                        let ct_bytes = cipher.encrypt(&plain_text[..len], mode)?;
+                        assert_eq!(len, ct_bytes.len());
+                        assert_eq!(plain_text[..len], cipher.decrypt(&ct_bytes, mode)?);
+                    }
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+cts_round_trip! {
+    rc5_cts_8_4_12: (rc5_cipher::<u8>([0u8; 4], 12)?, u8, 2),
+    rc5_cts_16_8_12: (rc5_cipher::<u16>([0u8; 8], 12)?, u16, 2),
+    rc5_cts_32_16_12: (rc5_cipher::<u32>([0u8; 16], 12)?, u32, 2),
+    rc5_cts_64_24_20: (rc5_cipher::<u64>([0u8; 24], 20)?, u64, 2),
+    rc5_cts_80_12_12: (rc5_cipher::<crate::U80>([0u8; 12], 12)?, crate::U80, 2),
+    rc6_cts_32_16_20: (rc6_cipher::<u32>([0u8; 16], 20)?, u32, 4),
+}
+
+// RFC 2040 publishes no RC5-CTS vectors, these reuse the key, IV and first
+// plain-text block of its RC5-32/12 CBC vector (921f12485373b4f7), and
+// come from `tests/vectors/generate.py`, which swaps the last two blocks
+// like the AES-CTS vectors of RFC 3962 it is checked against.
+#[test]
+fn rfc_2040_cts_vectors() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>(hex::decode("0102030405060708").unwrap(), 12)?;
+    let iv = cipher.parse_iv_from_hex("0102030405060708")?;
+
+    for (plain_text, cbc_cts, ecb_cts) in [
+        ("1020304050607080", "921f12485373b4f7", "b6e28703f9c0b32a"),
+        (
+            "1020304050607080ff",
+            "dfe821281496d18692",
+            "ef69004f42f9f9bcb6",
+        ),
+        (
+            "1020304050607080ffffffffffff",
+            "07c6716deb32593d921f12485373",
+            "defade498b607d1eb6e28703f9c0",
+        ),
+        (
+            "1020304050607080ffffffffffffffff",
+            "77f406b248ac9029921f12485373b4f7",
+            "b6e28703f9c0b32ae493f1c1bb4d6e8c",
+        ),
+        (
+            "1020304050607080ffffffffffffffff11223344556677",
+            "921f12485373b4f7dd1b720d3342c68377f406b248ac90",
+            "b6e28703f9c0b32a7afa4b851cb99194e493f1c1bb4d6e",
+        ),
+    ] {
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        for (mode, expected) in [
+            (OperationMode::CBC_CTS { iv }, cbc_cts),
+            (OperationMode::ECB_CTS, ecb_cts),
+        ] {
+            let ct_bytes = cipher.encrypt(&plain_text, mode)?;
+            assert_eq!(expected, hex::encode(&ct_bytes));
+            assert_eq!(plain_text, cipher.decrypt(&ct_bytes, mode)?);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn aligned_cts_swaps_the_last_cbc_blocks() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let iv = super::random_iv();
+    let plain_text = b"24 bytes aligned message";
+
+    let cbc = cipher.encrypt(plain_text, OperationMode::CBC { iv })?;
+    let cts = cipher.encrypt(plain_text, OperationMode::CBC_CTS { iv })?;
+
+    assert_eq!(cbc[..8], cts[..8]);
+    assert_eq!(cbc[8..16], cts[16..24]);
+    assert_eq!(cbc[16..24], cts[8..16]);
+
+    Ok(())
+}
+
+#[test]
+fn cts_in_place() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let plain_text = b"This is RC5-CTS in place.";
+
+    for mode in [
+        OperationMode::CBC_CTS {
+            iv: super::random_iv(),
+        },
+        OperationMode::ECB_CTS,
+    ] {
+        let expected = cipher.encrypt(plain_text, mode)?;
+
+        let mut buf = *plain_text;
+        let ct_bytes = cipher.encrypt_in_place(&mut buf, plain_text.len(), mode)?;
+        assert_eq!(expected, ct_bytes);
+        assert_eq!(plain_text, cipher.decrypt_in_place(&mut buf, mode)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn cts_rejects_less_than_one_block() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+
+    for mode in [
+        OperationMode::CBC_CTS {
+            iv: super::random_iv(),
+        },
+        OperationMode::ECB_CTS,
+    ] {
+        for text in [&b""[..], b"7 bytes"] {
+            assert!(matches!(
+                cipher.encrypt(text, mode),
+                Err(Reason::StealingTooShort { required: 8, current }) if current == text.len()
+            ));
+            assert!(matches!(
+                cipher.decrypt(text, mode),
+                Err(Reason::StealingTooShort { required: 8, current }) if current == text.len()
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use crate::{BlockCipher, OperationMode, Reason, rc5_cipher, rc6_cipher};
+
+macro_rules! eax_round_trip {
+    ($( $fn_name:ident: ( $cipher:expr , $w:ty , $n:expr ) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = $cipher;
+                let bs = cipher.control_block().block_size();
+                let plain_text = b"This is EAX authenticated encryption.";
+                let nonce = super::random_iv();
+
+                for tag_len in [1, bs / 2, bs] {
+                    let mode = OperationMode::<$w, $n>::EAX { nonce, tag_len };
+
+                    for len in [0, 1, bs, plain_text.len()] {
+                        let ct_bytes = cipher.encrypt_aead(&plain_text[..len], b"header", mode)?;
+                        assert_eq!(len + tag_len, ct_bytes.len());
+                        assert_eq!(plain_text[..len], cipher.decrypt_aead(&ct_bytes, b"header", mode)?);
+                    }
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+eax_round_trip! {
+    rc5_eax_8_4_12: (rc5_cipher::<u8>([0u8; 4], 12)?, u8, 2),
+    rc5_eax_16_8_12: (rc5_cipher::<u16>([0u8; 8], 12)?, u16, 2),
+    rc5_eax_32_16_12: (rc5_cipher::<u32>([0u8; 16], 12)?, u32, 2),
+    rc5_eax_64_24_20: (rc5_cipher::<u64>([0u8; 24], 20)?, u64, 2),
+    rc5_eax_80_12_12: (rc5_cipher::<crate::U80>([0u8; 12], 12)?, crate::U80, 2),
+    rc6_eax_32_16_20: (rc6_cipher::<u32>([0u8; 16], 20)?, u32, 4),
+}
+
+// RC5-32/12 EAX vectors under the 16 bytes key 000102..0f, with the 64-bit
+// CMAC polynomial 0x1b, printed by `tests/vectors/generate.py`, whose EAX
+// reproduces the first AES-128 vector of the EAX paper.
+#[test]
+fn rc5_32_eax_vectors() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>(hex::decode("000102030405060708090a0b0c0d0e0f").unwrap(), 12)?;
+
+    for (nonce, ad, plain_text, cipher_text, tag) in [
+        ("0001020304050607", "", "", "", "2f3d54f6a6bdb21a"),
+        (
+            "0001020304050607",
+            "0a0b0c",
+            "48656c6c6f",
+            "4acd4bcf40",
+            "02080c9d0b27413d",
+        ),
+        (
+            "f0f1f2f3f4f5f6f7",
+            "0001020304050607",
+            "000102030405060708090a0b0c0d0e0f1011121314",
+            "541c41dd4ecd0b2ceb30e64cd1b62f9870fff98df4",
+            "86f8493abb5b6462",
+        ),
+        // The counter wraps around the whole block.
+        (
+            "ffffffffffffffff",
+            "",
+            "000000000000000000000000000000000000000000000000",
+            "02485a361606fda17bc51d6a6d885cce837b719af8e21ee0",
+            "ee5e7221120d8e8e",
+        ),
+    ] {
+        let mode = OperationMode::EAX {
+            nonce: cipher.parse_iv_from_hex(nonce)?,
+            tag_len: 8,
+        };
+        let ad = hex::decode(ad).unwrap();
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = cipher.encrypt_aead(&plain_text, &ad, mode)?;
+        assert_eq!(format!("{cipher_text}{tag}"), hex::encode(&ct_bytes));
+        assert_eq!(plain_text, cipher.decrypt_aead(&ct_bytes, &ad, mode)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn truncated_tags_are_prefixes() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let nonce = super::random_iv();
+    let plain_text = b"Truncated tags";
+
+    let full = cipher.encrypt_aead(plain_text, b"", OperationMode::EAX { nonce, tag_len: 8 })?;
+    let short = cipher.encrypt_aead(plain_text, b"", OperationMode::EAX { nonce, tag_len: 4 })?;
+    assert_eq!(full[..plain_text.len() + 4], short);
+
+    Ok(())
+}
+
+#[test]
+fn eax_rejects_altered_messages() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let mode = OperationMode::EAX {
+        nonce: super::random_iv(),
+        tag_len: 8,
+    };
+    let ct_bytes = cipher.encrypt_aead(b"Authenticated message", b"header", mode)?;
+
+    for ix in 0..ct_bytes.len() {
+        let mut altered = ct_bytes.clone();
+        altered[ix] ^= 0x01;
+        assert!(matches!(
+            cipher.decrypt_in_place_aead(&mut altered, b"header", mode),
+            Err(Reason::AuthenticationFailed)
+        ));
+
+        // Nothing is decrypted before the tag is verified.
+        altered[ix] ^= 0x01;
+        assert_eq!(ct_bytes, altered);
+    }
+
+    for ad in [&b"Header"[..], b"", b"header!"] {
+        assert!(matches!(
+            cipher.decrypt_aead(&ct_bytes, ad, mode),
+            Err(Reason::AuthenticationFailed)
+        ));
+    }
+    assert!(matches!(
+        cipher.decrypt_aead(&ct_bytes[..7], b"header", mode),
+        Err(Reason::AuthenticationFailed)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn eax_in_place() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let mode = OperationMode::EAX {
+        nonce: super::random_iv(),
+        tag_len: 6,
+    };
+    let plain_text = b"In place EAX";
+    let expected = cipher.encrypt_aead(plain_text, b"ad", mode)?;
+
+    let mut buf = [0u8; 32];
+    buf[..plain_text.len()].copy_from_slice(plain_text);
+    let ct_bytes = cipher.encrypt_in_place_aead(&mut buf, plain_text.len(), b"ad", mode)?;
+    assert_eq!(expected, ct_bytes);
+    assert_eq!(cipher.encrypted_len(plain_text.len(), mode), ct_bytes.len());
+
+    let ct_len = ct_bytes.len();
+    let dt_bytes = cipher.decrypt_in_place_aead(&mut buf[..ct_len], b"ad", mode)?;
+    assert_eq!(plain_text, dt_bytes);
+
+    // Without associated data the plain Cipher API applies.
+    let ct_bytes = cipher.encrypt(plain_text, mode)?;
+    assert_eq!(plain_text, &cipher.decrypt(&ct_bytes, mode)?[..]);
+
+    Ok(())
+}
+
+#[test]
+fn eax_rejects_invalid_parameters() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let nonce = super::random_iv();
+
+    for tag_len in [0, 9, usize::MAX] {
+        assert!(matches!(
+            cipher.encrypt(b"message", OperationMode::EAX { nonce, tag_len }),
+            Err(Reason::InvalidTagLength(len)) if len == tag_len
+        ));
+    }
+
+    assert!(matches!(
+        cipher.encrypt_aead(b"message", b"header", OperationMode::CBC { iv: nonce }),
+        Err(Reason::UnauthenticatedMode)
+    ));
+    assert!(matches!(
+        cipher.decrypt_aead(&[0u8; 16], b"header", OperationMode::ECB),
+        Err(Reason::UnauthenticatedMode)
+    ));
+
+    // Not even without associated data, nothing would be authenticated.
+    let ctr = OperationMode::CTR {
+        nonce_and_counter: super::random_nonce_and_counter(),
+    };
+    for mode in [OperationMode::ECB, ctr] {
+        assert!(matches!(
+            cipher.encrypt_aead(b"message", b"", mode),
+            Err(Reason::UnauthenticatedMode)
+        ));
+    }
+    let mode = OperationMode::CBC { iv: nonce };
+    let mut ct_bytes = cipher.encrypt(b"message", mode)?;
+    ct_bytes[0] ^= 1;
+    assert!(matches!(
+        cipher.decrypt_aead(&ct_bytes, b"", mode),
+        Err(Reason::UnauthenticatedMode)
+    ));
+    assert!(matches!(
+        cipher.decrypt_in_place_aead(&mut ct_bytes, b"", mode),
+        Err(Reason::UnauthenticatedMode)
+    ));
+
+    Ok(())
+}
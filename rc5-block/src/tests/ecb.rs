@@ -1,4 +1,4 @@
-use crate::{BlockCipher, OperationMode, Reason, rc5_cipher};
+use crate::{BlockCipher, NoPadding, OperationMode, Reason, rc5_cipher};
 
 macro_rules! rc5_ecb_round_trip {
     ($( $fn_name:ident: ( $w:ty , $key:expr , $rounds:expr , $pt:expr) ),*$(,)?) => {
@@ -25,6 +25,12 @@ macro_rules! rc5_ecb_round_trip {
 }
 
 rc5_ecb_round_trip! {
+    rc5_ecb_8_4_12:  (
+        u8,
+        [0u8; 4],
+        12,
+        "This is RC5-ECB 8-bit word size test."
+    ),
     rc5_ecb_16_8_8:  (
         u16,
         [0u8; 8],
@@ -49,4 +55,73 @@ rc5_ecb_round_trip! {
         20,
         "This is RC5-ECB 64-bit word size test."
     ),
+    rc5_ecb_24_12_4:  (
+        crate::U24,
+        [0u8; 12],
+        4,
+        "This is RC5-ECB 24-bit word size test."
+    ),
+    rc5_ecb_80_12_12:  (
+        crate::U80,
+        [0u8; 12],
+        12,
+        "This is RC5-ECB 80-bit word size test."
+    ),
+    rc5_ecb_256_32_20:  (
+        crate::U256,
+        [0u8; 32],
+        20,
+        "This is RC5-ECB 256-bit word size test."
+    ),
+}
+
+macro_rules! rc5_ecb_vectors {
+    ($( $fn_name:ident: ( $key:expr , $pt:expr , $exp_cipher:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = rc5_cipher::<u32>(hex::decode($key)?, 12)?;
+                let plain_text = hex::decode($pt)?;
+
+                let ct_bytes = cipher.encrypt_with_padding(&plain_text, OperationMode::ECB, &NoPadding)?;
+                assert_eq!($exp_cipher, hex::encode_upper(&ct_bytes));
+
+                let dt_bytes = cipher.decrypt_with_padding(&ct_bytes, OperationMode::ECB, &NoPadding)?;
+                assert_eq!(plain_text, dt_bytes);
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+// RC5-32/12/16 vectors of OpenSSL's `test/rc5test.c` (from the RC5 paper), in
+// the byte order of OpenSSL's `rc5-ecb`, which the other RC5 modes of OpenSSL
+// share, e.g. `rc5-cbc` and `rc5-ofb`.
+rc5_ecb_vectors! {
+    rc5_ecb_openssl_vector_1: (
+        "00000000000000000000000000000000",
+        "0000000000000000",
+        "21A5DBEE154B8F6D"
+    ),
+    rc5_ecb_openssl_vector_2: (
+        "915F4619BE41B2516355A50110A9CE91",
+        "21A5DBEE154B8F6D",
+        "F7C013AC5B2B8952"
+    ),
+    rc5_ecb_openssl_vector_3: (
+        "783348E75AEB0F2FD7B169BB8DC16787",
+        "F7C013AC5B2B8952",
+        "2F42B3B70369FC92"
+    ),
+    rc5_ecb_openssl_vector_4: (
+        "DC49DB1375A5584F6485B413B5F12BAF",
+        "2F42B3B70369FC92",
+        "65C178B284D197CC"
+    ),
+    rc5_ecb_openssl_vector_5: (
+        "5269F149D41BA0152497574D7F153125",
+        "65C178B284D197CC",
+        "EB44E415DA319824"
+    ),
 }
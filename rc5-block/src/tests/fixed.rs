@@ -0,0 +1,67 @@
+use crate::{
+    BlockCipher, Cipher, OperationMode, RC5_32_12, RC5_64_16, RC5ControlBlock, RC5Fixed, Reason,
+};
+
+macro_rules! rc5_fixed_matches_control_block {
+    ($( $fn_name:ident: ( $fixed:ty , $w:ty , $rounds:expr , $key:expr ) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let fixed_block = <$fixed>::new($key)?;
+                let rc5_block = RC5ControlBlock::<$w>::new($key, $rounds)?;
+
+                assert_eq!(
+                    rc5_block.control_block_version(),
+                    fixed_block.control_block_version()
+                );
+
+                let mut block = [<$w>::MAX, 0x5a as $w];
+                for _ in 0..64 {
+                    let cipher_text = rc5_block.encrypt(block);
+                    assert_eq!(cipher_text, fixed_block.encrypt(block));
+                    assert_eq!(block, fixed_block.decrypt(cipher_text));
+                    block = cipher_text;
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+rc5_fixed_matches_control_block! {
+    rc5_32_12_matches_control_block: (RC5_32_12, u32, 12, b"0123456789abcdef"),
+    rc5_64_16_matches_control_block: (RC5_64_16, u64, 16, b"0123456789abcdef"),
+    rc5_16_0_matches_control_block: (RC5Fixed<u16, 0>, u16, 0, b"key"),
+    rc5_128_255_matches_control_block: (RC5Fixed<u128, 255>, u128, 255, [0x42u8; 255]),
+}
+
+#[test]
+fn rc5_fixed_modes_round_trip() -> Result<(), Reason> {
+    let cipher = Cipher::new(RC5_64_16::new(b"0123456789abcdef")?);
+    let plain_text = b"This is RC5-64/16 with a compile-time round count.";
+
+    for mode in [
+        OperationMode::ECB,
+        OperationMode::CBC {
+            iv: super::random_iv(),
+        },
+        OperationMode::CTR {
+            nonce_and_counter: super::random_nonce_and_counter(),
+        },
+    ] {
+        let ct_bytes = cipher.encrypt(plain_text, mode)?;
+        assert_eq!(plain_text.as_slice(), cipher.decrypt(&ct_bytes, mode)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn rc5_fixed_rejects_invalid_parameters() {
+    assert!(matches!(
+        RC5Fixed::<u32, 256>::new(b"key"),
+        Err(Reason::InvalidRounds(256))
+    ));
+    assert!(matches!(RC5_32_12::new(b""), Err(Reason::InvalidKey)));
+}
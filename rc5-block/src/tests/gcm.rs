@@ -0,0 +1,268 @@
+use crate::{GcmMode, Reason, rc5_cipher};
+
+const KEY: &str = "000102030405060708090a0b0c0d0e0f";
+
+// RC5-64/16 GCM vectors under the 16 bytes key 000102..0f, along with the
+// GMAC one below, printed by `tests/vectors/generate.py`, which checks its
+// GHASH and counters against `AESGCM` for both 96-bit and hashed nonces.
+#[test]
+fn rc5_64_gcm_vectors() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u64>(hex::decode(KEY).unwrap(), 16)?;
+
+    for (nonce, ad, plain_text, cipher_text, tag) in [
+        (
+            "000102030405060708090a0b",
+            "",
+            "",
+            "",
+            "02140617897af1b003b36d41e0ff5148",
+        ),
+        (
+            "000102030405060708090a0b",
+            "0a0b0c",
+            "48656c6c6f",
+            "1394faff96",
+            "37e620abee7199c732815068c1e355e7",
+        ),
+        (
+            "cafebabefacedbaddecaf888",
+            "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+            "c76345f1352ab86808377feb5769618b31bab9196ad4d42da2755788502b3f3d48c2fec8a2ae2b2af8e9107919515ad26aa643706622e58df2098ac2",
+            "b1c6aedf1cbd6d789314061e1df32d2f",
+        ),
+        // Nonces other than 96-bit ones are hashed into the counter block.
+        (
+            "cafebabefacedbad",
+            "feedfacedeadbeef",
+            "000000000000000000000000000000000000000000000000000000000000000000",
+            "713969fc37f69cc2c0eac262af80656b89a8c5549d9c1f5636ad7b6c1f8352f9f6",
+            "4d098233df368fd00cef5052b3503a67",
+        ),
+        (
+            "9313225df88406e555909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39b",
+            "",
+            "000102030405060708090a0b0c0d0e0f",
+            "0d7becf00048919c6b5a4884dc0008c3",
+            "d3bb6a72f5cfc29c83ed89674162d065",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffff",
+            "",
+            "0000000000000000000000000000000000000000",
+            "73b49bfc793c0ea4f93bea251270252eab2d178a",
+            "a7a76f7e883a22d7aaa8a04d2b6ffd25",
+        ),
+    ] {
+        let nonce = cipher.gcm_nonce(hex::decode(nonce).unwrap())?;
+        let ad = hex::decode(ad).unwrap();
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = cipher.gcm_seal(nonce, &plain_text, &ad, 16)?;
+        assert_eq!(format!("{cipher_text}{tag}"), hex::encode(&ct_bytes));
+        assert_eq!(plain_text, cipher.gcm_open(nonce, &ct_bytes, &ad, 16)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn rc5_64_gmac_vector() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u64>(hex::decode(KEY).unwrap(), 16)?;
+    let nonce = cipher.gcm_nonce(hex::decode("000102030405060708090a0b").unwrap())?;
+    let ad = b"GMAC authenticated data";
+
+    let tag = cipher.gmac(nonce, ad);
+    assert_eq!("9752e62d5d9fa9b4b244443d1e7ac22b", hex::encode(tag));
+
+    // GMAC is GCM over an empty message.
+    assert_eq!(tag[..], cipher.gcm_seal(nonce, b"", ad, 16)?);
+
+    for tag_len in [4, 8, 12, 16] {
+        cipher.verify_gmac(nonce, ad, &tag[..tag_len])?;
+    }
+
+    let mut altered = tag;
+    altered[15] ^= 0x80;
+    assert!(matches!(
+        cipher.verify_gmac(nonce, ad, &altered),
+        Err(Reason::AuthenticationFailed)
+    ));
+    assert!(matches!(
+        cipher.verify_gmac(nonce, b"GMAC authenticated date", &tag),
+        Err(Reason::AuthenticationFailed)
+    ));
+    assert!(matches!(
+        cipher.verify_gmac(nonce, ad, &tag[..10]),
+        Err(Reason::InvalidTagLength(10))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn gcm_round_trip() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u64>([0u8; 24], 20)?;
+    let plain_text = b"This is GCM authenticated encryption of RC5-64.";
+
+    for nonce_bytes in [&b"96-bit nonce"[..], b"n", b"a nonce longer than one block"] {
+        let nonce = cipher.gcm_nonce(nonce_bytes)?;
+
+        for tag_len in [4, 8, 12, 13, 14, 15, 16] {
+            for len in [0, 1, 16, plain_text.len()] {
+                for ad in [&b""[..], b"header"] {
+                    let ct_bytes = cipher.gcm_seal(nonce, &plain_text[..len], ad, tag_len)?;
+                    assert_eq!(len + tag_len, ct_bytes.len());
+                    assert_eq!(
+                        plain_text[..len],
+                        cipher.gcm_open(nonce, &ct_bytes, ad, tag_len)?
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn truncated_tags_are_prefixes() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u64>([0u8; 16], 16)?;
+    let nonce = cipher.gcm_nonce(b"96-bit nonce")?;
+    let plain_text = b"Truncated tags";
+
+    let full = cipher.gcm_seal(nonce, plain_text, b"", 16)?;
+    for tag_len in [4, 8, 12] {
+        let short = cipher.gcm_seal(nonce, plain_text, b"", tag_len)?;
+        assert_eq!(full[..plain_text.len() + tag_len], short);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn gcm_rejects_altered_messages() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u64>([0u8; 16], 16)?;
+    let nonce = cipher.gcm_nonce(b"96-bit nonce")?;
+    let ct_bytes = cipher.gcm_seal(nonce, b"Authenticated message", b"header", 16)?;
+
+    for ix in 0..ct_bytes.len() {
+        let mut altered = ct_bytes.clone();
+        altered[ix] ^= 0x01;
+        assert!(matches!(
+            cipher.gcm_open_in_place(nonce, &mut altered, b"header", 16),
+            Err(Reason::AuthenticationFailed)
+        ));
+
+        // Nothing is decrypted before the tag is verified.
+        altered[ix] ^= 0x01;
+        assert_eq!(ct_bytes, altered);
+    }
+
+    for ad in [&b"Header"[..], b"", b"header!"] {
+        assert!(matches!(
+            cipher.gcm_open(nonce, &ct_bytes, ad, 16),
+            Err(Reason::AuthenticationFailed)
+        ));
+    }
+    assert!(matches!(
+        cipher.gcm_open(nonce, &ct_bytes[..15], b"header", 16),
+        Err(Reason::AuthenticationFailed)
+    ));
+
+    // The nonce is authenticated through the key-stream block of the tag.
+    let other = cipher.gcm_nonce(b"96-bit nonc3")?;
+    assert!(matches!(
+        cipher.gcm_open(other, &ct_bytes, b"header", 16),
+        Err(Reason::AuthenticationFailed)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn gcm_in_place() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u64>([0u8; 16], 16)?;
+    let nonce = cipher.gcm_nonce(b"96-bit nonce")?;
+    let plain_text = b"In place GCM";
+    let expected = cipher.gcm_seal(nonce, plain_text, b"ad", 12)?;
+
+    let mut buf = [0u8; 32];
+    buf[..plain_text.len()].copy_from_slice(plain_text);
+    let ct_bytes = cipher.gcm_seal_in_place(nonce, &mut buf, plain_text.len(), b"ad", 12)?;
+    assert_eq!(expected, ct_bytes);
+
+    let ct_len = ct_bytes.len();
+    let dt_bytes = cipher.gcm_open_in_place(nonce, &mut buf[..ct_len], b"ad", 12)?;
+    assert_eq!(plain_text, dt_bytes);
+
+    assert!(matches!(
+        cipher.gcm_seal_in_place(nonce, &mut buf[..20], plain_text.len(), b"ad", 12),
+        Err(Reason::BufferTooSmall {
+            required: 24,
+            available: 20
+        })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn gcm_through_the_aead_methods() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u64>([0u8; 16], 16)?;
+    let nonce = cipher.gcm_nonce(b"96-bit nonce")?;
+    let mode = GcmMode { nonce, tag_len: 13 };
+    let plain_text = b"GCM as an AEAD mode";
+
+    let ct_bytes = cipher.encrypt_aead(plain_text, b"ad", mode)?;
+    assert_eq!(cipher.gcm_seal(nonce, plain_text, b"ad", 13)?, ct_bytes);
+    assert_eq!(
+        plain_text,
+        &cipher.decrypt_aead(&ct_bytes, b"ad", mode)?[..]
+    );
+
+    let mut buf = [0u8; 32];
+    buf[..plain_text.len()].copy_from_slice(plain_text);
+    let ct_len = cipher
+        .encrypt_in_place_aead(&mut buf, plain_text.len(), b"ad", mode)?
+        .len();
+    assert_eq!(ct_bytes, buf[..ct_len]);
+
+    buf[0] ^= 0x01;
+    assert!(matches!(
+        cipher.decrypt_in_place_aead(&mut buf[..ct_len], b"ad", mode),
+        Err(Reason::AuthenticationFailed)
+    ));
+    buf[0] ^= 0x01;
+
+    let dt_bytes = cipher.decrypt_in_place_aead(&mut buf[..ct_len], b"ad", mode)?;
+    assert_eq!(plain_text, dt_bytes);
+
+    assert!(matches!(
+        cipher.encrypt_aead(plain_text, b"ad", GcmMode { nonce, tag_len: 10 }),
+        Err(Reason::InvalidTagLength(10))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn gcm_rejects_invalid_parameters() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u64>([0u8; 16], 16)?;
+    let nonce = cipher.gcm_nonce(b"96-bit nonce")?;
+
+    for tag_len in [0, 1, 5, 10, 11, 17] {
+        assert!(matches!(
+            cipher.gcm_seal(nonce, b"message", b"", tag_len),
+            Err(Reason::InvalidTagLength(len)) if len == tag_len
+        ));
+        assert!(matches!(
+            cipher.gcm_open(nonce, &[0u8; 32], b"", tag_len),
+            Err(Reason::InvalidTagLength(len)) if len == tag_len
+        ));
+    }
+
+    assert!(matches!(cipher.gcm_nonce(b""), Err(Reason::EmptyNonce)));
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::{BlockCipher, OperationMode, Reason, rc5_cipher};
+
+macro_rules! rc5_ige_round_trip {
+    ($( $fn_name:ident: ( $w:ty , $key:expr , $rounds:expr , $pt:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = rc5_cipher::<$w>(&$key, $rounds)?;
+                let plain_text = $pt.as_bytes().to_vec();
+                let (iv1, iv2) = (super::random_iv(), super::random_iv());
+
+                let ct_bytes = cipher.encrypt(&plain_text, OperationMode::IGE { iv1, iv2 })?;
+                let dt_bytes = cipher.decrypt(&ct_bytes, OperationMode::IGE { iv1, iv2 })?;
+
+                assert_eq!(
+                    plain_text,
+                    dt_bytes,
+                    "{}",
+                    format!("Round trip failed for {}", cipher.control_block().control_block_version())
+                );
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+rc5_ige_round_trip! {
+    rc5_ige_16_8_12:  (
+        u16,
+        [0u8; 8],
+        12,
+        "This is RC5-IGE 16-bit word size test."
+    ),
+    rc5_ige_32_16_12:  (
+        u32,
+        [0u8; 16],
+        12,
+        "This is RC5-IGE 32-bit word size test."
+    ),
+    rc5_ige_64_24_20:  (
+        u64,
+        [0u8; 24],
+        20,
+        "This is RC5-IGE 64-bit word size test."
+    ),
+    rc5_ige_80_12_12:  (
+        crate::U80,
+        [0u8; 12],
+        12,
+        "This is RC5-IGE 80-bit word size test."
+    ),
+}
+
+#[test]
+fn ige_chains_previous_blocks() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let control_block = cipher.control_block();
+    let (iv1, iv2) = (super::random_iv(), super::random_iv());
+    let plain_text = b"16 bytes aligned";
+
+    let ct_bytes = cipher.encrypt(plain_text, OperationMode::IGE { iv1, iv2 })?;
+    let blocks = control_block.generate_blocks(plain_text);
+    let [c0, c1, _] = control_block.generate_blocks(&ct_bytes)[..] else {
+        panic!("three cipher-text blocks expected");
+    };
+
+    let xor = |a: [u32; 2], b: [u32; 2]| [a[0] ^ b[0], a[1] ^ b[1]];
+    assert_eq!(c0, xor(control_block.encrypt(xor(blocks[0], iv1)), iv2));
+    assert_eq!(
+        c1,
+        xor(control_block.encrypt(xor(blocks[1], c0)), blocks[0])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ige_error_garbles_following_blocks() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let (iv1, iv2) = (super::random_iv(), super::random_iv());
+    let plain_text = [0x5au8; 64];
+
+    let mut ct_bytes = cipher.encrypt(&plain_text, OperationMode::IGE { iv1, iv2 })?;
+    ct_bytes[8] ^= 1;
+
+    // The corrupted padding is rejected, decrypt the blocks directly.
+    crate::modes::ige_decrypt(cipher.control_block(), iv1, iv2, &mut ct_bytes);
+    assert_eq!(plain_text[..8], ct_bytes[..8]);
+    for (pt, dt) in plain_text[8..].chunks(8).zip(ct_bytes[8..].chunks(8)) {
+        assert_ne!(pt, dt);
+    }
+
+    Ok(())
+}
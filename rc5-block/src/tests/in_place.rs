@@ -0,0 +1,118 @@
+use crate::{OperationMode, Reason, rc5_cipher, rc6_cipher};
+
+macro_rules! in_place_round_trip {
+    ($( $fn_name:ident: ( $cipher:expr , $w:ty , $n:expr , $pt:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = $cipher;
+                let plain_text: &[u8] = $pt;
+
+                for mode in [
+                    OperationMode::<$w, $n>::ECB,
+                    OperationMode::CBC { iv: super::random_iv() },
+                    OperationMode::CTR { nonce_and_counter: super::random_nonce_and_counter() },
+                    OperationMode::CFB { iv: super::random_iv(), segment_bits: 8 },
+                    OperationMode::OFB { iv: super::random_iv() },
+                    OperationMode::PCBC { iv: super::random_iv() },
+                    OperationMode::IGE { iv1: super::random_iv(), iv2: super::random_iv() },
+                ] {
+                    let expected = cipher.encrypt(plain_text, mode)?;
+
+                    let mut buf = [0u8; 256];
+                    buf[..plain_text.len()].copy_from_slice(plain_text);
+                    let ct_bytes = cipher.encrypt_in_place(&mut buf, plain_text.len(), mode)?;
+                    assert_eq!(expected, ct_bytes);
+                    assert_eq!(cipher.encrypted_len(plain_text.len(), mode), ct_bytes.len());
+
+                    let ct_len = ct_bytes.len();
+                    let dt_bytes = cipher.decrypt_in_place(&mut buf[..ct_len], mode)?;
+                    assert_eq!(plain_text, dt_bytes);
+
+                    let mut out = [0u8; 256];
+                    let ct_bytes = cipher.encrypt_into(plain_text, &mut out, mode)?;
+                    assert_eq!(expected, ct_bytes);
+
+                    let mut dt_out = [0u8; 256];
+                    let dt_bytes = cipher.decrypt_into(&expected, &mut dt_out, mode)?;
+                    assert_eq!(plain_text, dt_bytes);
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+in_place_round_trip! {
+    rc5_8_in_place: (rc5_cipher::<u8>([0u8; 4], 12)?, u8, 2, b"This is RC5 8-bit in place."),
+    rc5_16_in_place: (rc5_cipher::<u16>([0u8; 8], 12)?, u16, 2, b"This is RC5 16-bit in place."),
+    rc5_32_in_place: (rc5_cipher::<u32>([0u8; 16], 12)?, u32, 2, b"This is RC5 32-bit in place."),
+    rc5_64_in_place: (rc5_cipher::<u64>([0u8; 24], 20)?, u64, 2, b"This is RC5 64-bit in place."),
+    rc5_80_in_place: (rc5_cipher::<crate::U80>([0u8; 12], 12)?, crate::U80, 2, b"This is RC5 80-bit in place."),
+    rc5_32_aligned_in_place: (rc5_cipher::<u32>([0u8; 16], 12)?, u32, 2, b"16 bytes aligned"),
+    rc5_32_empty_in_place: (rc5_cipher::<u32>([0u8; 16], 12)?, u32, 2, b""),
+    rc6_32_in_place: (rc6_cipher::<u32>([0u8; 16], 20)?, u32, 4, b"This is RC6 32-bit in place."),
+}
+
+#[test]
+fn encrypt_in_place_requires_padding_slack() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let mut buf = *b"8 bytes!";
+
+    assert!(matches!(
+        cipher.encrypt_in_place(&mut buf, 8, OperationMode::ECB),
+        Err(Reason::BufferTooSmall {
+            required: 16,
+            available: 8
+        })
+    ));
+
+    // CTR does not pad, the plain-text length is enough.
+    let mode = OperationMode::CTR {
+        nonce_and_counter: [7, 0],
+    };
+    assert_eq!(cipher.encrypt_in_place(&mut buf, 8, mode)?.len(), 8);
+
+    Ok(())
+}
+
+#[test]
+fn into_requires_output_capacity() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let mut out = [0u8; 8];
+
+    assert!(matches!(
+        cipher.encrypt_into(b"HELLO", &mut out[..5], OperationMode::ECB),
+        Err(Reason::BufferTooSmall {
+            required: 8,
+            available: 5
+        })
+    ));
+    assert!(matches!(
+        cipher.decrypt_into(&[0u8; 16], &mut out, OperationMode::ECB),
+        Err(Reason::BufferTooSmall {
+            required: 16,
+            available: 8
+        })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn decrypt_in_place_rejects_partial_blocks() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let mut buf = [0u8; 12];
+
+    assert!(matches!(
+        cipher.decrypt_in_place(&mut buf, OperationMode::ECB),
+        Err(Reason::Padding)
+    ));
+    assert!(matches!(
+        cipher.decrypt_in_place(&mut [], OperationMode::CBC { iv: [0, 0] }),
+        Err(Reason::Padding)
+    ));
+
+    Ok(())
+}
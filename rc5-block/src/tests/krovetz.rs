@@ -0,0 +1,104 @@
+use crate::{BlockCipher, RC5ControlBlock, RC5Fixed, Reason, U24, U80};
+
+macro_rules! rc5_krovetz_vectors {
+    ($( $fn_name:ident: ( $w:ty , $rounds:expr , $key:expr , $pt:expr , $exp_cipher:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let key = hex::decode($key).unwrap();
+                let rc5_block = RC5ControlBlock::<$w>::new(&key, $rounds)?;
+
+                let plain_text = rc5_block.generate_blocks(&hex::decode($pt).unwrap())[0];
+                let cipher_text = rc5_block.encrypt(plain_text);
+
+                let bytes = rc5_block.generate_bytes_stream(&[cipher_text]);
+                assert_eq!($exp_cipher, hex::encode_upper(bytes));
+
+                let decipher_text = rc5_block.decrypt(cipher_text);
+                assert_eq!(
+                    rc5_block.generate_bytes_stream(&[plain_text]),
+                    rc5_block.generate_bytes_stream(&[decipher_text])
+                );
+
+                let fixed_block = RC5Fixed::<$w, $rounds>::new(&key)?;
+                assert_eq!(
+                    rc5_block.generate_bytes_stream(&[cipher_text]),
+                    fixed_block.generate_bytes_stream(&[fixed_block.encrypt(plain_text)])
+                );
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+// Known-answer test-vectors for RC5-w/r/b
+// see more: https://datatracker.ietf.org/doc/html/draft-krovetz-rc6-rc5-vectors-00
+rc5_krovetz_vectors! {
+    rc5_8_12_4: (
+        u8,
+        12,
+        "00010203",
+        "0001",
+        "212A"
+    ),
+    rc5_16_16_8: (
+        u16,
+        16,
+        "0001020304050607",
+        "00010203",
+        "23A8D72E"
+    ),
+    rc5_32_20_16: (
+        u32,
+        20,
+        "000102030405060708090A0B0C0D0E0F",
+        "0001020304050607",
+        "2A0EDC0E9431FF73"
+    ),
+    rc5_64_24_24: (
+        u64,
+        24,
+        "000102030405060708090A0B0C0D0E0F1011121314151617",
+        "000102030405060708090A0B0C0D0E0F",
+        "A46772820EDBCE0235ABEA32AE7178DA"
+    ),
+    rc5_128_28_32: (
+        u128,
+        28,
+        "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F",
+        "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F",
+        "ECA5910921A4F4CFDD7AD7AD20A1FCBA068EC7A7CD752D68FE914B7FE180B440"
+    ),
+}
+
+// The non-native widths of the draft rotate by the low `floor(lg(w))` bits.
+rc5_krovetz_vectors! {
+    rc5_80_4_12: (
+        U80,
+        4,
+        "000102030405060708090A0B",
+        "000102030405060708090A0B0C0D0E0F10111213",
+        "9CB59ECBA4EA84568A4278B0E132D5FC9D5819D6"
+    ),
+}
+
+// RC5-24/4/0 uses an empty key, which `RC5ControlBlock::new` rejects.
+#[test]
+fn rc5_24_4_0() -> Result<(), Reason> {
+    let rc5_block = RC5ControlBlock::<U24>::with_empty_key(4)?;
+    assert_eq!("RC5-v1/24/4/0", rc5_block.parametric_version());
+    assert!(matches!(
+        RC5ControlBlock::<U24>::new([], 4),
+        Err(Reason::InvalidKey)
+    ));
+
+    let plain_text = rc5_block.generate_blocks(&hex::decode("000102030405").unwrap())[0];
+    let cipher_text = rc5_block.encrypt(plain_text);
+
+    let bytes = rc5_block.generate_bytes_stream(&[cipher_text]);
+    assert_eq!("89CBDCC9525A", hex::encode_upper(bytes));
+    assert_eq!(plain_text, rc5_block.decrypt(cipher_text));
+
+    Ok(())
+}
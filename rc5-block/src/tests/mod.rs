@@ -1,8 +1,82 @@
-use crate::{BlockCipher, RC5ControlBlock, Reason};
+use crate::{BlockCipher, RC5ControlBlock, RC5Fixed, Reason};
 
+#[cfg(feature = "std")]
+pub(crate) use crate::{random_iv, random_nonce_and_counter};
+
+#[cfg(feature = "cipher")]
+mod adapter;
 mod cbc;
+mod ccm;
+mod cfb;
 mod ctr;
+mod cts;
+mod eax;
 mod ecb;
+mod fixed;
+mod gcm;
+mod ige;
+mod in_place;
+mod krovetz;
+mod ocb;
+mod ofb;
+mod padding;
+#[cfg(feature = "std")]
+mod parallel;
+mod pcbc;
+mod rc6;
+mod redaction;
+mod rfc2040;
+#[cfg(feature = "cipher")]
+mod rustcrypto;
+mod schedule;
+mod siv;
+
+/// Stands in for [crate::random_iv] without `std`, every call still
+/// returns a different block, but the sequence is the same on every run.
+#[cfg(not(feature = "std"))]
+pub(crate) fn random_iv<W: crate::Word, const N: usize>() -> [W; N] {
+    use core::sync::atomic::{AtomicU64, Ordering};
+    use rand::rngs::mock::StepRng;
+
+    const GOLDEN: u64 = 0x9e37_79b9_7f4a_7c15;
+    static CALLS: AtomicU64 = AtomicU64::new(1);
+
+    let seed = CALLS.fetch_add(1, Ordering::Relaxed).wrapping_mul(GOLDEN);
+    let mut rng = StepRng::new(seed, GOLDEN);
+    core::array::from_fn(|_| W::random(&mut rng))
+}
+
+/// Stands in for [crate::random_nonce_and_counter] without `std`.
+#[cfg(not(feature = "std"))]
+pub(crate) fn random_nonce_and_counter<W: crate::Word, const N: usize>() -> [W; N] {
+    let mut nonce_and_counter = random_iv();
+    nonce_and_counter[N - 1] = W::ZERO;
+    nonce_and_counter
+}
+
+/// Checks a control block against a known-answer vector, either the
+/// [RC5ControlBlock] or the fixed-round [RC5Fixed] of the same key.
+fn check_control_block_vector<B>(
+    rc5_block: B,
+    plain_text: [u32; 2],
+    exp_cipher: &str,
+    exp_dec: [u32; 2],
+) where
+    B: BlockCipher<u32, 2>,
+{
+    let cipher_text = rc5_block.encrypt(plain_text);
+
+    let bytes = cipher_text
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .collect::<Vec<u8>>();
+
+    assert_eq!(exp_cipher, hex::encode_upper(bytes));
+
+    let decipher_text = rc5_block.decrypt(cipher_text);
+
+    assert_eq!(exp_dec, decipher_text);
+}
 
 macro_rules! rc5_control_block_vectors {
     ($( $fn_name:ident: ( $key:expr , $rounds:expr , $exp_cipher:expr , $exp_dec:expr) ),*$(,)?) => {
@@ -11,20 +85,19 @@ macro_rules! rc5_control_block_vectors {
             fn $fn_name() -> Result<(), Reason> {
                 let key = ($key as u128).to_be_bytes();
                 let plain_text = [0_u32;2];
-                let rc5_block = RC5ControlBlock::<u32>::new(key, $rounds)?;
-
-                let cipher_text = rc5_block.encrypt(plain_text);
-
-                let bytes = cipher_text
-                            .iter()
-                            .flat_map(|word| word.to_le_bytes())
-                            .collect::<Vec<u8>>();
-
-                assert_eq!($exp_cipher, hex::encode_upper(bytes));
-
-                let decipher_text = rc5_block.decrypt(cipher_text);
 
-                assert_eq!($exp_dec, decipher_text);
+                check_control_block_vector(
+                    RC5ControlBlock::<u32>::new(key, $rounds)?,
+                    plain_text,
+                    &$exp_cipher,
+                    $exp_dec,
+                );
+                check_control_block_vector(
+                    RC5Fixed::<u32, $rounds>::new(key)?,
+                    plain_text,
+                    &$exp_cipher,
+                    $exp_dec,
+                );
 
                 Ok(())
             }
@@ -0,0 +1,342 @@
+use crate::{BlockCipher, OperationMode, Reason, rc5_cipher, rc6_cipher};
+
+const KEY: &str = "000102030405060708090a0b0c0d0e0f";
+
+macro_rules! ocb_round_trip {
+    ($( $fn_name:ident: ( $cipher:expr , $w:ty , $n:expr ) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = $cipher;
+                let bs = cipher.control_block().block_size();
+                let plain_text = b"This is OCB authenticated encryption, in a single pass.";
+                let nonce = super::random_iv();
+
+                for nonce_len in 1..bs {
+                    for tag_len in 1..=bs {
+                        let mode = OperationMode::<$w, $n>::OCB { nonce, nonce_len, tag_len };
+
+                        for len in [0, 1, bs, plain_text.len()] {
+                            for ad in [&b""[..], b"header", &plain_text[..]] {
+                                let ct_bytes = cipher.encrypt_aead(&plain_text[..len], ad, mode)?;
+                                assert_eq!(len + tag_len, ct_bytes.len());
+                                assert_eq!(plain_text[..len], cipher.decrypt_aead(&ct_bytes, ad, mode)?);
+                            }
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+ocb_round_trip! {
+    rc5_ocb_32_16_12: (rc5_cipher::<u32>([0u8; 16], 12)?, u32, 2),
+    rc5_ocb_64_24_20: (rc5_cipher::<u64>([0u8; 24], 20)?, u64, 2),
+    rc6_ocb_32_16_20: (rc6_cipher::<u32>([0u8; 16], 20)?, u32, 4),
+}
+
+// RC5-32/12 and RC5-64/16 OCB vectors under the 16 bytes key 000102..0f,
+// printed by `tests/vectors/generate.py`, whose RFC 7253 offsets match
+// `AESOCB3`. The 64-bit blocks use the stretch shift of 25 and the 5
+// bottom bits of the 64-bit variant.
+#[test]
+fn rc5_ocb_vectors() -> Result<(), Reason> {
+    let rc5_32 = rc5_cipher::<u32>(hex::decode(KEY).unwrap(), 12)?;
+    let rc5_64 = rc5_cipher::<u64>(hex::decode(KEY).unwrap(), 16)?;
+    let sample = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f2021222324252627";
+    let partial = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122";
+
+    for (nonce, tag_len, ad, plain_text, cipher_text) in [
+        ("00010203040506", 8, "", "", "c00c1d4341460f7a"),
+        (
+            "00010203040506",
+            8,
+            "0001020304050607",
+            "0001020304050607",
+            "5b62dc6354b428f2d6c3d302bed5b744",
+        ),
+        (
+            "0a0b0c",
+            8,
+            sample,
+            sample,
+            "61d31d538f7161ad310c765bd6e0b338083824ecf7fdaabdc58b9cd4ed176300eeace00217e51efd61fff5aee8e3774d",
+        ),
+        (
+            "ff",
+            4,
+            "",
+            partial,
+            "146275a155fff843b458c012dd037f12d1c675f030450f15dbabb174e9a34ee1847b0ba7fe0040",
+        ),
+    ] {
+        let nonce = hex::decode(nonce).unwrap();
+        let mode = OperationMode::OCB {
+            nonce: rc5_32.nonce_from_bytes(&nonce)?,
+            nonce_len: nonce.len(),
+            tag_len,
+        };
+        let ad = hex::decode(ad).unwrap();
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = rc5_32.encrypt_aead(&plain_text, &ad, mode)?;
+        assert_eq!(cipher_text, hex::encode(&ct_bytes));
+        assert_eq!(plain_text, rc5_32.decrypt_aead(&ct_bytes, &ad, mode)?);
+    }
+
+    for (nonce, tag_len, ad, plain_text, cipher_text) in [
+        (
+            "bbaa99887766554433221100",
+            16,
+            "",
+            "",
+            "77c56b783a7c7f31c958ea4d496b1c99",
+        ),
+        (
+            "bbaa99887766554433221101",
+            16,
+            "0001020304050607",
+            "0001020304050607",
+            "3897a1096f3e91abd70477613a571d7205f8a57e8747892e",
+        ),
+        (
+            "bbaa9988776655443322110d",
+            12,
+            sample,
+            sample,
+            "71c7708a52d3bb24b7434f3a958c422f55844de506c75e0d5c1c97b2b46757c97083f8fee6760e94873644297d221889ca12799c",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e",
+            16,
+            "",
+            partial,
+            "047bedd5d0841a7969d8208315d74f51c030ffd66d8e42b15551505408761ade7e45e119a52ccc5eda8ae12ef6817a8f4d8857",
+        ),
+    ] {
+        let nonce = hex::decode(nonce).unwrap();
+        let mode = OperationMode::OCB {
+            nonce: rc5_64.nonce_from_bytes(&nonce)?,
+            nonce_len: nonce.len(),
+            tag_len,
+        };
+        let ad = hex::decode(ad).unwrap();
+        let plain_text = hex::decode(plain_text).unwrap();
+
+        let ct_bytes = rc5_64.encrypt_aead(&plain_text, &ad, mode)?;
+        assert_eq!(cipher_text, hex::encode(&ct_bytes));
+        assert_eq!(plain_text, rc5_64.decrypt_aead(&ct_bytes, &ad, mode)?);
+    }
+
+    Ok(())
+}
+
+// Long messages reach the higher offsets `L_i` of the precomputed table,
+// only the tags of the 1000 bytes message `i mod 251` are checked, as
+// printed by `tests/vectors/generate.py`.
+#[test]
+fn rc5_ocb_long_message_tags() -> Result<(), Reason> {
+    let message: Vec<u8> = (0..1000).map(|ix| (ix % 251) as u8).collect();
+
+    let rc5_32 = rc5_cipher::<u32>(hex::decode(KEY).unwrap(), 12)?;
+    let mode = OperationMode::OCB {
+        nonce: rc5_32.nonce_from_bytes(hex::decode("00010203040506").unwrap())?,
+        nonce_len: 7,
+        tag_len: 8,
+    };
+    let ct_bytes = rc5_32.encrypt_aead(&message, &message, mode)?;
+    assert_eq!("cd72d223ac35bae2", hex::encode(&ct_bytes[1000..]));
+    assert_eq!(message, rc5_32.decrypt_aead(&ct_bytes, &message, mode)?);
+
+    let rc5_64 = rc5_cipher::<u64>(hex::decode(KEY).unwrap(), 16)?;
+    let mode = OperationMode::OCB {
+        nonce: rc5_64.nonce_from_bytes(hex::decode("bbaa99887766554433221100").unwrap())?,
+        nonce_len: 12,
+        tag_len: 16,
+    };
+    let ct_bytes = rc5_64.encrypt_aead(&message, &message, mode)?;
+    assert_eq!(
+        "7148e2342cadebc0820231e4102ea54d",
+        hex::encode(&ct_bytes[1000..])
+    );
+    assert_eq!(message, rc5_64.decrypt_aead(&ct_bytes, &message, mode)?);
+
+    Ok(())
+}
+
+#[test]
+fn ocb_rejects_altered_messages() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let nonce = super::random_iv();
+    let mode = OperationMode::OCB {
+        nonce,
+        nonce_len: 7,
+        tag_len: 8,
+    };
+    let ct_bytes = cipher.encrypt_aead(b"Authenticated message", b"header", mode)?;
+
+    for ix in 0..ct_bytes.len() {
+        let mut altered = ct_bytes.clone();
+        altered[ix] ^= 0x01;
+        assert!(matches!(
+            cipher.decrypt_in_place_aead(&mut altered, b"header", mode),
+            Err(Reason::AuthenticationFailed)
+        ));
+
+        // The plain-text is encrypted back when the tag does not match.
+        altered[ix] ^= 0x01;
+        assert_eq!(ct_bytes, altered);
+    }
+
+    for ad in [&b"Header"[..], b"", b"header!"] {
+        assert!(matches!(
+            cipher.decrypt_aead(&ct_bytes, ad, mode),
+            Err(Reason::AuthenticationFailed)
+        ));
+    }
+
+    // The tag length is part of the nonce, a truncated tag does not verify.
+    let truncated = OperationMode::OCB {
+        nonce,
+        nonce_len: 7,
+        tag_len: 6,
+    };
+    let ct_len = ct_bytes.len();
+    assert!(matches!(
+        cipher.decrypt_aead(&ct_bytes[..ct_len - 2], b"header", truncated),
+        Err(Reason::AuthenticationFailed)
+    ));
+    assert!(matches!(
+        cipher.decrypt_aead(&ct_bytes[..5], b"header", mode),
+        Err(Reason::AuthenticationFailed)
+    ));
+
+    Ok(())
+}
+
+// A tampered tag leaves the cipher-text in the buffer, not the plain-text
+// decrypted before the tag was checked.
+#[test]
+fn ocb_restores_cipher_text_on_tag_mismatch() -> Result<(), Reason> {
+    let message = b"A message spanning a few blocks";
+
+    let rc5_32 = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let mode = OperationMode::OCB {
+        nonce: rc5_32.nonce_from_bytes(b"nonce")?,
+        nonce_len: 5,
+        tag_len: 8,
+    };
+    let ct_bytes = rc5_32.encrypt_aead(message, b"header", mode)?;
+
+    for ix in message.len()..ct_bytes.len() {
+        let mut buf = ct_bytes.clone();
+        buf[ix] ^= 0x80;
+        assert!(matches!(
+            rc5_32.decrypt_in_place_aead(&mut buf, b"header", mode),
+            Err(Reason::AuthenticationFailed)
+        ));
+        assert_eq!(ct_bytes[..message.len()], buf[..message.len()]);
+    }
+
+    let rc5_64 = rc5_cipher::<u64>([0u8; 24], 16)?;
+    let mode = OperationMode::OCB {
+        nonce: rc5_64.nonce_from_bytes(b"96-bit nonce")?,
+        nonce_len: 12,
+        tag_len: 16,
+    };
+    let ct_bytes = rc5_64.encrypt_aead(message, b"header", mode)?;
+
+    for ix in message.len()..ct_bytes.len() {
+        let mut buf = ct_bytes.clone();
+        buf[ix] ^= 0x80;
+        assert!(matches!(
+            rc5_64.decrypt_in_place_aead(&mut buf, b"header", mode),
+            Err(Reason::AuthenticationFailed)
+        ));
+        assert_eq!(ct_bytes[..message.len()], buf[..message.len()]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn ocb_in_place() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u64>([0u8; 16], 16)?;
+    let mode = OperationMode::OCB {
+        nonce: cipher.nonce_from_bytes(b"96-bit nonce")?,
+        nonce_len: 12,
+        tag_len: 16,
+    };
+    let plain_text = b"In place OCB";
+    let expected = cipher.encrypt_aead(plain_text, b"ad", mode)?;
+
+    let mut buf = [0u8; 32];
+    buf[..plain_text.len()].copy_from_slice(plain_text);
+    let ct_bytes = cipher.encrypt_in_place_aead(&mut buf, plain_text.len(), b"ad", mode)?;
+    assert_eq!(expected, ct_bytes);
+    assert_eq!(cipher.encrypted_len(plain_text.len(), mode), ct_bytes.len());
+
+    let ct_len = ct_bytes.len();
+    let dt_bytes = cipher.decrypt_in_place_aead(&mut buf[..ct_len], b"ad", mode)?;
+    assert_eq!(plain_text, dt_bytes);
+
+    Ok(())
+}
+
+#[test]
+fn ocb_rejects_invalid_parameters() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let nonce = super::random_iv();
+
+    for tag_len in [0, 9, 16] {
+        let mode = OperationMode::OCB {
+            nonce,
+            nonce_len: 7,
+            tag_len,
+        };
+        assert!(matches!(
+            cipher.encrypt(b"message", mode),
+            Err(Reason::InvalidTagLength(len)) if len == tag_len
+        ));
+    }
+
+    for nonce_len in [0, 8, 9] {
+        let mode = OperationMode::OCB {
+            nonce,
+            nonce_len,
+            tag_len: 8,
+        };
+        assert!(matches!(
+            cipher.encrypt(b"message", mode),
+            Err(Reason::InvalidNonceLength { max: 7, current }) if current == nonce_len
+        ));
+    }
+
+    // Only 64 and 128-bit blocks have OCB offsets.
+    let rc5_16 = rc5_cipher::<u16>([0u8; 8], 12)?;
+    let mode = OperationMode::OCB {
+        nonce: super::random_iv(),
+        nonce_len: 3,
+        tag_len: 4,
+    };
+    assert!(matches!(
+        rc5_16.encrypt(b"message", mode),
+        Err(Reason::UnsupportedBlockSize(4))
+    ));
+
+    let rc5_80 = rc5_cipher::<crate::U80>([0u8; 12], 12)?;
+    let mode = OperationMode::OCB {
+        nonce: super::random_iv(),
+        nonce_len: 12,
+        tag_len: 16,
+    };
+    assert!(matches!(
+        rc5_80.encrypt(b"message", mode),
+        Err(Reason::UnsupportedBlockSize(20))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+#[cfg(feature = "std")]
+use crate::Iso10126;
+use crate::{
+    AnsiX923, BlockCipher, Iso7816, NoPadding, OperationMode, Padding, Pkcs7, Reason, ZeroPadding,
+    rc5_cipher, rc6_cipher,
+};
+
+macro_rules! padding_round_trip {
+    ($( $fn_name:ident: ( $cipher:expr , $w:ty , $n:expr , $pt:expr ) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = $cipher;
+                let plain_text: &[u8] = $pt;
+                let paddings: [&dyn Padding; 4] = [&Pkcs7, &AnsiX923, &Iso7816, &ZeroPadding];
+                #[cfg(feature = "std")]
+                let paddings = [&paddings[..], &[&Iso10126]].concat();
+
+                for mode in [
+                    OperationMode::<$w, $n>::ECB,
+                    OperationMode::CBC { iv: super::random_iv() },
+                    OperationMode::PCBC { iv: super::random_iv() },
+                    OperationMode::IGE { iv1: super::random_iv(), iv2: super::random_iv() },
+                ] {
+                    for &padding in &paddings {
+                        let ct_bytes = cipher.encrypt_with_padding(plain_text, mode, padding)?;
+                        assert_eq!(
+                            cipher.encrypted_len_with_padding(plain_text.len(), mode, padding),
+                            ct_bytes.len()
+                        );
+                        assert_eq!(plain_text, cipher.decrypt_with_padding(&ct_bytes, mode, padding)?);
+                    }
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+padding_round_trip! {
+    rc5_16_padding: (rc5_cipher::<u16>([0u8; 8], 12)?, u16, 2, b"This is RC5 16-bit padded."),
+    rc5_32_padding: (rc5_cipher::<u32>([0u8; 16], 12)?, u32, 2, b"This is RC5 32-bit padded."),
+    rc5_64_padding: (rc5_cipher::<u64>([0u8; 24], 20)?, u64, 2, b"This is RC5 64-bit padded."),
+    rc5_32_aligned_padding: (rc5_cipher::<u32>([0u8; 16], 12)?, u32, 2, b"16 bytes aligned"),
+    rc6_32_padding: (rc6_cipher::<u32>([0u8; 16], 20)?, u32, 4, b"This is RC6 32-bit padded."),
+}
+
+#[test]
+fn pkcs7_is_the_default_padding() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let iv = super::random_iv();
+    let plain_text = b"This is the default padding.";
+
+    assert_eq!(
+        cipher.encrypt(plain_text, OperationMode::CBC { iv })?,
+        cipher.encrypt_with_padding(plain_text, OperationMode::CBC { iv }, &Pkcs7)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn padding_bytes_are_encrypted() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let control_block = cipher.control_block();
+
+    for (padding, last_block) in [
+        (&AnsiX923 as &dyn Padding, *b"HELLO\x00\x00\x03"),
+        (&Iso7816, *b"HELLO\x80\x00\x00"),
+        (&ZeroPadding, *b"HELLO\x00\x00\x00"),
+    ] {
+        let ct_bytes = cipher.encrypt_with_padding(b"HELLO", OperationMode::ECB, padding)?;
+        assert_eq!(
+            control_block.generate_bytes_stream(&[
+                control_block.decrypt(control_block.generate_blocks(&ct_bytes)[0])
+            ]),
+            last_block
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn unpadded_records_keep_their_length() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let iv = super::random_iv();
+    let record = b"two blocks of 16";
+
+    for padding in [&NoPadding as &dyn Padding, &ZeroPadding] {
+        for mode in [OperationMode::ECB, OperationMode::CBC { iv }] {
+            let ct_bytes = cipher.encrypt_with_padding(record, mode, padding)?;
+            assert_eq!(record.len(), ct_bytes.len());
+            assert_eq!(
+                record,
+                &cipher.decrypt_with_padding(&ct_bytes, mode, padding)?[..]
+            );
+
+            assert!(cipher.encrypt_with_padding(b"", mode, padding)?.is_empty());
+            assert!(cipher.decrypt_with_padding(b"", mode, padding)?.is_empty());
+        }
+    }
+
+    assert!(matches!(
+        cipher.encrypt_with_padding(b"HELLO", OperationMode::ECB, &NoPadding),
+        Err(Reason::Padding)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn padding_in_place() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let mode = OperationMode::CBC {
+        iv: super::random_iv(),
+    };
+
+    let mut buf = [0u8; 8];
+    buf[..5].copy_from_slice(b"HELLO");
+    let ct_len = cipher
+        .encrypt_in_place_with_padding(&mut buf, 5, mode, &Iso7816)?
+        .len();
+    assert_eq!(8, ct_len);
+    assert_eq!(
+        b"HELLO",
+        cipher.decrypt_in_place_with_padding(&mut buf, mode, &Iso7816)?
+    );
+
+    // A whole extra block is required once the message is block aligned.
+    assert!(matches!(
+        cipher.encrypt_in_place_with_padding(&mut buf, 8, mode, &AnsiX923),
+        Err(Reason::BufferTooSmall {
+            required: 16,
+            available: 8
+        })
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::{cell::Cell, marker::PhantomData};
+
+use crate::{
+    BlockCipher, Cipher, OperationMode, Parallelism, RC5ControlBlock, Reason, U80, Word,
+    rc5_cipher, rc6_cipher,
+};
+
+macro_rules! parallel_matches_serial {
+    ($( $fn_name:ident: ( $cipher:expr , $w:ty , $n:expr , $counter:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let serial = $cipher;
+                let bs = $n * <$w as Word>::BYTES;
+
+                // spans several runs of 4096 blocks with a partial last block
+                let plain_text: Vec<u8> = (0..20_000 * bs + 5).map(|ix| ix as u8).collect();
+
+                let mut nonce_and_counter = crate::random_nonce_and_counter::<$w, $n>();
+                nonce_and_counter[$n - 1] = $counter;
+
+                for mode in [
+                    OperationMode::<$w, $n>::ECB,
+                    OperationMode::CBC { iv: crate::random_iv() },
+                    OperationMode::CTR { nonce_and_counter },
+                ] {
+                    let expected = serial.encrypt(&plain_text, mode)?;
+
+                    for parallelism in [
+                        Parallelism::Threads(2.try_into().unwrap()),
+                        Parallelism::Threads(3.try_into().unwrap()),
+                        Parallelism::Threads(16.try_into().unwrap()),
+                        Parallelism::Auto,
+                    ] {
+                        let cipher = $cipher.with_parallelism(parallelism);
+                        assert_eq!(cipher.parallelism(), parallelism);
+
+                        let cipher_text = cipher.encrypt(&plain_text, mode)?;
+                        assert_eq!(expected, cipher_text);
+
+                        let decipher_text = cipher.decrypt(&cipher_text, mode)?;
+                        assert_eq!(plain_text, decipher_text);
+                    }
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+parallel_matches_serial! {
+    rc5_16_parallel: (rc5_cipher::<u16>([0u8; 8], 12)?, u16, 2, 0),
+    rc5_16_parallel_counter_wrap: (rc5_cipher::<u16>([0u8; 8], 12)?, u16, 2, 0xfff0),
+    rc5_32_parallel: (rc5_cipher::<u32>([0u8; 16], 12)?, u32, 2, 0),
+    rc5_64_parallel: (rc5_cipher::<u64>([0u8; 24], 16)?, u64, 2, u64::MAX - 8),
+    rc5_80_parallel: (rc5_cipher::<U80>([0u8; 12], 12)?, U80, 2, U80::ZERO),
+    rc6_32_parallel: (rc6_cipher::<u32>([0u8; 16], 20)?, u32, 4, u32::MAX),
+}
+
+/// A control block that cannot be shared between threads.
+struct Unshared {
+    inner: RC5ControlBlock<u32>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl BlockCipher<u32, 2> for Unshared {
+    fn control_block_version(&self) -> String {
+        self.inner.control_block_version()
+    }
+
+    fn block_size(&self) -> usize {
+        self.inner.block_size()
+    }
+
+    fn word_size(&self) -> usize {
+        self.inner.word_size()
+    }
+
+    fn encrypt(&self, pt: [u32; 2]) -> [u32; 2] {
+        self.inner.encrypt(pt)
+    }
+
+    fn decrypt(&self, ct: [u32; 2]) -> [u32; 2] {
+        self.inner.decrypt(ct)
+    }
+}
+
+#[test]
+fn non_sync_control_blocks_run_serially() -> Result<(), Reason> {
+    let cipher = Cipher::new(Unshared {
+        inner: RC5ControlBlock::new([0u8; 16], 12)?,
+        _not_sync: PhantomData,
+    });
+    let expected = rc5_cipher::<u32>([0u8; 16], 12)?;
+    assert_eq!(cipher.parallelism(), Parallelism::Serial);
+
+    let plain_text: Vec<u8> = (0..10_000).map(|ix| ix as u8).collect();
+    for mode in [
+        OperationMode::ECB,
+        OperationMode::CBC {
+            iv: crate::random_iv(),
+        },
+        OperationMode::CTR {
+            nonce_and_counter: crate::random_nonce_and_counter(),
+        },
+    ] {
+        let cipher_text = cipher.encrypt(&plain_text, mode)?;
+        assert_eq!(expected.encrypt(&plain_text, mode)?, cipher_text);
+        assert_eq!(plain_text, cipher.decrypt(&cipher_text, mode)?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::{BlockCipher, OperationMode, Reason, rc5_cipher};
+
+macro_rules! rc5_pcbc_round_trip {
+    ($( $fn_name:ident: ( $w:ty , $key:expr , $rounds:expr , $pt:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = rc5_cipher::<$w>(&$key, $rounds)?;
+                let plain_text = $pt.as_bytes().to_vec();
+                let random_iv = super::random_iv();
+
+                let ct_bytes = cipher.encrypt(&plain_text, OperationMode::PCBC { iv: random_iv })?;
+                let dt_bytes = cipher.decrypt(&ct_bytes, OperationMode::PCBC { iv: random_iv })?;
+
+                assert_eq!(
+                    plain_text,
+                    dt_bytes,
+                    "{}",
+                    format!("Round trip failed for {}", cipher.control_block().control_block_version())
+                );
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+rc5_pcbc_round_trip! {
+    rc5_pcbc_16_8_12:  (
+        u16,
+        [0u8; 8],
+        12,
+        "This is RC5-PCBC 16-bit word size test."
+    ),
+    rc5_pcbc_32_16_12:  (
+        u32,
+        [0u8; 16],
+        12,
+        "This is RC5-PCBC 32-bit word size test."
+    ),
+    rc5_pcbc_64_24_20:  (
+        u64,
+        [0u8; 24],
+        20,
+        "This is RC5-PCBC 64-bit word size test."
+    ),
+    rc5_pcbc_80_12_12:  (
+        crate::U80,
+        [0u8; 12],
+        12,
+        "This is RC5-PCBC 80-bit word size test."
+    ),
+}
+
+#[test]
+fn pcbc_chains_plain_and_cipher_text() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let control_block = cipher.control_block();
+    let iv = super::random_iv();
+    let plain_text = b"16 bytes aligned";
+
+    let ct_bytes = cipher.encrypt(plain_text, OperationMode::PCBC { iv })?;
+    let blocks = control_block.generate_blocks(plain_text);
+    let [c0, c1, _] = control_block.generate_blocks(&ct_bytes)[..] else {
+        panic!("three cipher-text blocks expected");
+    };
+
+    assert_eq!(
+        c0,
+        control_block.encrypt([blocks[0][0] ^ iv[0], blocks[0][1] ^ iv[1]])
+    );
+    assert_eq!(
+        c1,
+        control_block.encrypt([
+            blocks[1][0] ^ blocks[0][0] ^ c0[0],
+            blocks[1][1] ^ blocks[0][1] ^ c0[1]
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn pcbc_error_garbles_following_blocks() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let iv = super::random_iv();
+    let plain_text = [0x5au8; 64];
+
+    let mut ct_bytes = cipher.encrypt(&plain_text, OperationMode::PCBC { iv })?;
+    ct_bytes[8] ^= 1;
+
+    // The corrupted padding is rejected, decrypt the blocks directly.
+    crate::modes::pcbc_decrypt(cipher.control_block(), iv, &mut ct_bytes);
+    assert_eq!(plain_text[..8], ct_bytes[..8]);
+    for (pt, dt) in plain_text[8..].chunks(8).zip(ct_bytes[8..].chunks(8)) {
+        assert_ne!(pt, dt);
+    }
+
+    Ok(())
+}
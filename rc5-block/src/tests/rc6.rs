@@ -0,0 +1,111 @@
+use crate::{BlockCipher, OperationMode, RC6ControlBlock, Reason, rc6_cipher};
+
+macro_rules! rc6_control_block_vectors {
+    ($( $fn_name:ident: ( $key:expr , $pt:expr , $exp_cipher:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let key = hex::decode($key).unwrap();
+                let rc6_block = RC6ControlBlock::<u32>::new(key, 20)?;
+
+                let plain_text = rc6_block.generate_blocks(&hex::decode($pt).unwrap())[0];
+                let cipher_text = rc6_block.encrypt(plain_text);
+
+                let bytes = rc6_block.generate_bytes_stream(&[cipher_text]);
+                assert_eq!($exp_cipher, hex::encode(bytes));
+
+                let decipher_text = rc6_block.decrypt(cipher_text);
+                assert_eq!(plain_text, decipher_text);
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+// Standard RC6-32/20/b test-vectors published with the RC6 proposal.
+rc6_control_block_vectors! {
+    rc6_32_20_16_vector_1: (
+        "00000000000000000000000000000000",
+        "00000000000000000000000000000000",
+        "8fc3a53656b1f778c129df4e9848a41e"
+    ),
+    rc6_32_20_16_vector_2: (
+        "0123456789abcdef0112233445566778",
+        "02132435465768798a9bacbdcedfe0f1",
+        "524e192f4715c6231f51f6367ea43f18"
+    ),
+    rc6_32_20_24_vector_1: (
+        "000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000",
+        "6cd61bcb190b30384e8a3f168690ae82"
+    ),
+    rc6_32_20_24_vector_2: (
+        "0123456789abcdef0112233445566778899aabbccddeeff0",
+        "02132435465768798a9bacbdcedfe0f1",
+        "688329d019e505041e52e92af95291d4"
+    ),
+    rc6_32_20_32_vector_1: (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000",
+        "8f5fbd0510d15fa893fa3fda6e857ec2"
+    ),
+    rc6_32_20_32_vector_2: (
+        "0123456789abcdef0112233445566778899aabbccddeeff01032547698badcfe",
+        "02132435465768798a9bacbdcedfe0f1",
+        "c8241816f0d7e48920ad16a1674e5d48"
+    ),
+}
+
+macro_rules! rc6_round_trip {
+    ($( $fn_name:ident: ( $w:ty , $key:expr , $rounds:expr , $pt:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = rc6_cipher::<$w>(&$key, $rounds)?;
+                let plain_text = $pt.as_bytes().to_vec();
+                let random_iv = super::random_iv();
+                let random_nonce = super::random_nonce_and_counter();
+
+                for mode in [
+                    OperationMode::ECB,
+                    OperationMode::CBC { iv: random_iv },
+                    OperationMode::CTR { nonce_and_counter: random_nonce },
+                ] {
+                    let ct_bytes = cipher.encrypt(&plain_text, mode)?;
+                    let dt_bytes = cipher.decrypt(&ct_bytes, mode)?;
+
+                    assert_eq!(
+                        plain_text,
+                        dt_bytes,
+                        "{}",
+                        format!("Round trip failed for {}", cipher.control_block().control_block_version())
+                    );
+                }
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+rc6_round_trip! {
+    rc6_16_8_12:  (
+        u16,
+        [0u8; 8],
+        12,
+        "This is RC6 16-bit word size test."
+    ),
+    rc6_32_16_20:  (
+        u32,
+        [0u8; 16],
+        20,
+        "This is RC6 32-bit word size test."
+    ),
+    rc6_64_24_24:  (
+        u64,
+        [0u8; 24],
+        24,
+        "This is RC6 64-bit word size test."
+    ),
+}
@@ -0,0 +1,68 @@
+use crate::{
+    BlockCipher, RC5ControlBlock, RC5Fixed, RC6ControlBlock, Reason, rc5::RC5Key, rc5_cipher,
+    rc5_siv_cipher,
+};
+
+// RC5-32/12/16 avr vector 1, whose encrypted zero block starts with 8F681D.
+const KEY: u128 = 0x80000000000000000000000000000000;
+
+#[test]
+fn control_blocks_debug_version_and_fingerprint() -> Result<(), Reason> {
+    let key = KEY.to_be_bytes();
+
+    let control_block = RC5ControlBlock::<u32>::new(key, 12)?;
+    assert_eq!(control_block.key_check_value(), "8F681D");
+    assert_eq!(
+        format!("{control_block:?}"),
+        r#"RC5ControlBlock { version: "RC5-v1/32/12/16", fingerprint: "8F681D" }"#
+    );
+
+    let fixed_block = RC5Fixed::<u32, 12>::new(key)?;
+    assert_eq!(
+        format!("{fixed_block:?}"),
+        r#"RC5Fixed { version: "RC5-v1/32/12/16", fingerprint: "8F681D" }"#
+    );
+
+    let cipher = rc5_cipher::<u32>(key, 12)?;
+    assert_eq!(
+        format!("{cipher:?}"),
+        r#"Cipher { version: "RC5-v1/32/12/16", fingerprint: "8F681D" }"#
+    );
+
+    let siv_cipher = rc5_siv_cipher::<u32>([[0u8; 16], key].concat(), 12)?;
+    let siv_debug = format!("{siv_cipher:?}");
+    assert!(siv_debug.starts_with(
+        r#"SivCipher { version: "RC5-v1/32/12/16", fingerprint: "8F681D", siv_fingerprint: ""#
+    ));
+
+    let rc6_block = RC6ControlBlock::<u32>::new(key, 20)?;
+    let rc6_debug = format!("{rc6_block:?}");
+    assert!(
+        rc6_debug.starts_with(r#"RC6ControlBlock { version: "RC6-v1/32/20/16", fingerprint: ""#)
+    );
+    assert!(!rc6_debug.contains(&format!("{:?}", rc6_block.s_table()[0])));
+
+    Ok(())
+}
+
+#[test]
+fn keys_debug_only_their_length() -> Result<(), Reason> {
+    let key = RC5Key::<u32>::from_raw(KEY.to_be_bytes(), 12)?;
+    assert_eq!(format!("{key:?}"), "RC5Key { raw_len: 16, .. }");
+
+    Ok(())
+}
+
+#[test]
+fn fingerprint_of_short_blocks() -> Result<(), Reason> {
+    // RC5-8 has 2 bytes blocks, the whole encrypted block is the fingerprint.
+    let control_block = RC5ControlBlock::<u8>::new([0u8; 4], 12)?;
+    let zero_block = control_block.generate_bytes_stream(&[control_block.encrypt([0; 2])]);
+
+    assert_eq!(
+        control_block.key_check_value(),
+        hex::encode_upper(zero_block)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::{OperationMode, Reason, rc5_cipher};
+
+macro_rules! rfc2040_vectors {
+    ($( $fn_name:ident: ( $mode:ident , $rounds:expr , $key:expr , $iv:expr , $pt:expr , $ct:expr ) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let cipher = rc5_cipher::<u32>(hex::decode($key).unwrap(), $rounds)?;
+                let iv = cipher.iv_from_bytes(hex::decode($iv).unwrap())?;
+                let plain_text = hex::decode($pt).unwrap();
+
+                let ct_bytes = cipher.encrypt(&plain_text, OperationMode::$mode { iv })?;
+                assert_eq!($ct, hex::encode(&ct_bytes));
+
+                let dt_bytes = cipher.decrypt(&ct_bytes, OperationMode::$mode { iv })?;
+                assert_eq!(plain_text, dt_bytes);
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+// RFC 2040 section 9, RC5-32 vectors with 8 bytes blocks.
+// see more: https://www.rfc-editor.org/rfc/rfc2040#section-9
+rfc2040_vectors! {
+    rfc2040_cbc_1: (CBC_NOPAD, 0, "00", "0000000000000000", "0000000000000000", "7a7bba4d79111d1e"),
+    rfc2040_cbc_2: (CBC_NOPAD, 0, "00", "0000000000000000", "ffffffffffffffff", "797bba4d78111d1e"),
+    rfc2040_cbc_3: (CBC_NOPAD, 0, "00", "0000000000000001", "0000000000000000", "7a7bba4d79111d1f"),
+    rfc2040_cbc_4: (CBC_NOPAD, 0, "00", "0000000000000000", "0000000000000001", "7a7bba4d79111d1f"),
+    rfc2040_cbc_5: (CBC_NOPAD, 0, "00", "0102030405060708", "1020304050607080", "8b9ded91ce7794a6"),
+    rfc2040_cbc_6: (CBC_NOPAD, 1, "11", "0000000000000000", "0000000000000000", "2f759fe7ad86a378"),
+    rfc2040_cbc_7: (CBC_NOPAD, 2, "00", "0000000000000000", "0000000000000000", "dca2694bf40e0788"),
+    rfc2040_cbc_8: (CBC_NOPAD, 2, "00000000", "0000000000000000", "0000000000000000", "dca2694bf40e0788"),
+    rfc2040_cbc_9: (CBC_NOPAD, 8, "00", "0000000000000000", "0000000000000000", "dcfe098577eca5ff"),
+    rfc2040_cbc_10: (CBC_NOPAD, 8, "00", "0102030405060708", "1020304050607080", "9646fb77638f9ca8"),
+    rfc2040_cbc_11: (CBC_NOPAD, 12, "00", "0102030405060708", "1020304050607080", "b2b3209db6594da4"),
+    rfc2040_cbc_12: (CBC_NOPAD, 16, "00", "0102030405060708", "1020304050607080", "545f7f32a5fc3836"),
+    rfc2040_cbc_13: (CBC_NOPAD, 8, "01020304", "0000000000000000", "ffffffffffffffff", "8285e7c1b5bc7402"),
+    rfc2040_cbc_14: (CBC_NOPAD, 12, "01020304", "0000000000000000", "ffffffffffffffff", "fc586f92f7080934"),
+    rfc2040_cbc_15: (CBC_NOPAD, 16, "01020304", "0000000000000000", "ffffffffffffffff", "cf270ef9717ff7c4"),
+    rfc2040_cbc_16: (CBC_NOPAD, 12, "0102030405060708", "0000000000000000", "ffffffffffffffff", "e493f1c1bb4d6e8c"),
+    rfc2040_cbc_17: (CBC_NOPAD, 8, "0102030405060708", "0102030405060708", "1020304050607080", "5c4c041e0f217ac3"),
+    rfc2040_cbc_18: (CBC_NOPAD, 12, "0102030405060708", "0102030405060708", "1020304050607080", "921f12485373b4f7"),
+    rfc2040_cbc_19: (CBC_NOPAD, 16, "0102030405060708", "0102030405060708", "1020304050607080", "5ba0ca6bbe7f5fad"),
+    rfc2040_cbc_20: (CBC_NOPAD, 8, "01020304050607081020304050607080", "0102030405060708", "1020304050607080", "c533771cd0110e63"),
+    rfc2040_cbc_21: (CBC_NOPAD, 12, "01020304050607081020304050607080", "0102030405060708", "1020304050607080", "294ddb46b3278d60"),
+    rfc2040_cbc_22: (CBC_NOPAD, 16, "01020304050607081020304050607080", "0102030405060708", "1020304050607080", "dad6bda9dfe8f7e8"),
+    rfc2040_cbc_23: (CBC_NOPAD, 12, "0102030405", "0000000000000000", "ffffffffffffffff", "97e0787837ed317f"),
+    rfc2040_cbc_24: (CBC_NOPAD, 8, "0102030405", "0000000000000000", "ffffffffffffffff", "7875dbf6738c6478"),
+    rfc2040_cbc_25: (CBC_NOPAD, 8, "0102030405", "7875dbf6738c6478", "0808080808080808", "8f34c3c681c99695"),
+    rfc2040_cbc_26: (CBC_NOPAD, 8, "0102030405", "0000000000000000", "0000000000000000", "7cb3f1df34f94811"),
+    rfc2040_cbc_27: (CBC_NOPAD, 8, "0102030405", "7cb3f1df34f94811", "1122334455667701", "7fd1a023a5bba217"),
+    rfc2040_cbc_pad_1: (
+        CBC, 8, "0102030405", "0000000000000000",
+        "ffffffffffffffff",
+        "7875dbf6738c64788f34c3c681c99695"
+    ),
+    rfc2040_cbc_pad_2: (
+        CBC, 8, "0102030405", "0000000000000000",
+        "ffffffffffffffff7875dbf6738c647811223344556677",
+        "7875dbf6738c64787cb3f1df34f948117fd1a023a5bba217"
+    ),
+}
+
+#[test]
+fn cbc_nopad_rejects_partial_blocks() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+    let mode = OperationMode::CBC_NOPAD { iv: [0, 0] };
+
+    assert!(cipher.encrypt(b"", mode)?.is_empty());
+    assert!(matches!(
+        cipher.encrypt(b"9 bytes!!", mode),
+        Err(Reason::Padding)
+    ));
+    assert!(matches!(
+        cipher.decrypt(&[0u8; 12], mode),
+        Err(Reason::Padding)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn iv_from_bytes_matches_block_size() -> Result<(), Reason> {
+    let cipher = rc5_cipher::<u32>([0u8; 16], 12)?;
+
+    assert_eq!(
+        [0x04030201, 0x08070605],
+        cipher.iv_from_bytes([1, 2, 3, 4, 5, 6, 7, 8])?
+    );
+    assert!(matches!(
+        cipher.iv_from_bytes([0u8; 7]),
+        Err(Reason::IVinvalid(8))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,291 @@
+use aes_gcm::AesGcm;
+use cbc::cipher::{
+    AsyncStreamCipher, BlockDecryptMut, BlockEncryptMut, KeyInit, KeyIvInit, StreamCipher,
+    block_padding::Pkcs7,
+};
+use ccm::{
+    Ccm,
+    consts::{U10, U12, U13},
+};
+use cmac::{Cmac, Mac};
+use eax::{
+    Eax,
+    aead::{Aead, Payload},
+};
+
+use crate::{BlockCipher, OperationMode, RC5ControlBlock, Reason, rc5_cipher};
+
+const KEY: [u8; 16] = *b"0123456789abcdef";
+const PLAIN_TEXT: &[u8] = b"This is RC5 composed through the RustCrypto cipher traits.";
+
+macro_rules! rc5_rustcrypto_modes {
+    ($( $mod_name:ident: ( $w:ty , $rounds:expr , $ctr:ty ) ),*$(,)?) => {
+        $(
+            mod $mod_name {
+                use super::*;
+
+                #[test]
+                fn cbc_matches_cipher_cbc() -> Result<(), Reason> {
+                    let cipher = rc5_cipher::<$w>(KEY, $rounds)?;
+                    let iv = crate::tests::random_iv();
+                    let iv_bytes = cipher.control_block().generate_bytes_stream(&[iv]);
+
+                    let expected = cipher.encrypt(PLAIN_TEXT, OperationMode::CBC { iv })?;
+                    let ct_bytes = cbc::Encryptor::<RC5ControlBlock<$w>>::new_from_slices(&KEY, &iv_bytes)
+                        .unwrap()
+                        .encrypt_padded_vec_mut::<Pkcs7>(PLAIN_TEXT);
+                    assert_eq!(expected, ct_bytes);
+
+                    let dt_bytes = cbc::Decryptor::<RC5ControlBlock<$w>>::new_from_slices(&KEY, &iv_bytes)
+                        .unwrap()
+                        .decrypt_padded_vec_mut::<Pkcs7>(&ct_bytes)
+                        .unwrap();
+                    assert_eq!(PLAIN_TEXT, dt_bytes);
+
+                    Ok(())
+                }
+
+                #[test]
+                fn ctr_keystream_from_control_block() -> Result<(), Reason> {
+                    let control_block = RC5ControlBlock::<$w>::new(KEY, $rounds)?;
+                    let counter_block: [$w; 2] = crate::tests::random_iv();
+                    let counter_bytes = control_block.generate_bytes_stream(&[counter_block]);
+
+                    let mut ct_bytes = PLAIN_TEXT.to_vec();
+                    <$ctr>::new_from_slices(&KEY, &counter_bytes)
+                        .unwrap()
+                        .apply_keystream(&mut ct_bytes);
+
+                    // Little-endian RustCrypto flavors count in the first word.
+                    let bs = control_block.block_size();
+                    for (ix, chunk) in PLAIN_TEXT.chunks(bs).enumerate() {
+                        let [counter, nonce] = counter_block;
+                        let block = [counter.wrapping_add(ix as $w), nonce];
+                        let key_stream = control_block.generate_bytes_stream(&[control_block.encrypt(block)]);
+
+                        for (jx, byte) in chunk.iter().enumerate() {
+                            assert_eq!(ct_bytes[ix * bs + jx], byte ^ key_stream[jx]);
+                        }
+                    }
+
+                    Ok(())
+                }
+
+                #[test]
+                fn cfb_round_trip() -> Result<(), Reason> {
+                    let control_block = RC5ControlBlock::<$w>::new(KEY, $rounds)?;
+                    let iv = crate::tests::random_iv();
+                    let iv_bytes = control_block.generate_bytes_stream(&[iv]);
+
+                    let mut buffer = PLAIN_TEXT.to_vec();
+                    cfb_mode::Encryptor::<RC5ControlBlock<$w>>::new_from_slices(&KEY, &iv_bytes)
+                        .unwrap()
+                        .encrypt(&mut buffer);
+
+                    // First CFB segment is the plain-text masked with E(iv).
+                    let key_stream = control_block.generate_bytes_stream(&[control_block.encrypt(iv)]);
+                    for (ix, byte) in key_stream.iter().enumerate() {
+                        assert_eq!(buffer[ix], PLAIN_TEXT[ix] ^ byte);
+                    }
+
+                    cfb_mode::Decryptor::<RC5ControlBlock<$w>>::new_from_slices(&KEY, &iv_bytes)
+                        .unwrap()
+                        .decrypt(&mut buffer);
+                    assert_eq!(PLAIN_TEXT, buffer);
+
+                    Ok(())
+                }
+
+                #[test]
+                fn cfb_matches_cipher_cfb() -> Result<(), Reason> {
+                    let cipher = rc5_cipher::<$w>(KEY, $rounds)?;
+                    let iv = crate::tests::random_iv();
+                    let iv_bytes = cipher.control_block().generate_bytes_stream(&[iv]);
+                    let block_bits = cipher.control_block().block_size() * 8;
+
+                    let expected = cipher.encrypt(PLAIN_TEXT, OperationMode::CFB { iv, segment_bits: block_bits })?;
+                    let mut ct_bytes = PLAIN_TEXT.to_vec();
+                    cfb_mode::Encryptor::<RC5ControlBlock<$w>>::new_from_slices(&KEY, &iv_bytes)
+                        .unwrap()
+                        .encrypt(&mut ct_bytes);
+                    assert_eq!(expected, ct_bytes);
+
+                    let expected = cipher.encrypt(PLAIN_TEXT, OperationMode::CFB { iv, segment_bits: 8 })?;
+                    let mut ct_bytes = PLAIN_TEXT.to_vec();
+                    cfb8::Encryptor::<RC5ControlBlock<$w>>::new_from_slices(&KEY, &iv_bytes)
+                        .unwrap()
+                        .encrypt(&mut ct_bytes);
+                    assert_eq!(expected, ct_bytes);
+
+                    Ok(())
+                }
+
+                #[test]
+                fn cmac_single_block() -> Result<(), Reason> {
+                    let control_block = RC5ControlBlock::<$w>::new(KEY, $rounds)?;
+                    let bs = control_block.block_size();
+                    let message = &PLAIN_TEXT[..bs];
+
+                    // CMAC of a single complete block: E(M ^ dbl(E(0))).
+                    let l = control_block.encrypt([<$w>::MIN; 2]);
+                    let mut subkey = control_block.generate_bytes_stream(&[l]);
+                    let carry = subkey[0] >> 7;
+                    for ix in 0..bs {
+                        let next = subkey.get(ix + 1).map_or(0, |byte| byte >> 7);
+                        subkey[ix] = (subkey[ix] << 1) | next;
+                    }
+                    subkey[bs - 1] ^= carry * if bs == 8 { 0x1b } else { 0x87 };
+
+                    let masked = message
+                        .iter()
+                        .zip(subkey)
+                        .map(|(m, k)| m ^ k)
+                        .collect::<Vec<u8>>();
+                    let block = control_block.generate_blocks(&masked)[0];
+                    let expected = control_block.generate_bytes_stream(&[control_block.encrypt(block)]);
+
+                    let mut mac = <Cmac<RC5ControlBlock<$w>> as Mac>::new_from_slice(&KEY).unwrap();
+                    mac.update(message);
+                    assert_eq!(expected, mac.finalize().into_bytes().to_vec());
+
+                    Ok(())
+                }
+
+                #[test]
+                fn cmac_matches_cipher_cmac() -> Result<(), Reason> {
+                    let control_block = RC5ControlBlock::<$w>::new(KEY, $rounds)?;
+
+                    for len in 0..PLAIN_TEXT.len() {
+                        let mut mac = <Cmac<RC5ControlBlock<$w>> as Mac>::new_from_slice(&KEY).unwrap();
+                        mac.update(&PLAIN_TEXT[..len]);
+
+                        // Fed in two uneven parts, the last block is held back.
+                        let mut cmac = crate::mac::Cmac::new(&control_block);
+                        cmac.update(&PLAIN_TEXT[..len / 3]);
+                        cmac.update(&PLAIN_TEXT[len / 3..len]);
+                        assert_eq!(
+                            mac.finalize().into_bytes().to_vec(),
+                            control_block.generate_bytes_stream(&[cmac.finalize()])
+                        );
+                    }
+
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+rc5_rustcrypto_modes! {
+    rc5_32: (u32, 12, ctr::Ctr32LE::<RC5ControlBlock<u32>>),
+    rc5_64: (u64, 16, ctr::Ctr64LE::<RC5ControlBlock<u64>>),
+}
+
+#[test]
+fn key_init_accepts_variable_key_lengths() {
+    assert!(<RC5ControlBlock<u32> as KeyInit>::new_from_slice(b"short").is_ok());
+    assert!(<RC5ControlBlock<u32> as KeyInit>::new_from_slice(&[]).is_err());
+    assert!(<RC5ControlBlock<u16> as KeyInit>::new_from_slice(&[7u8; 256]).is_err());
+}
+
+#[test]
+fn eax_rc5_64_round_trip() {
+    let eax = Eax::<RC5ControlBlock<u64>>::new_from_slice(&KEY).unwrap();
+    let nonce = [0x42u8; 16].into();
+    let payload = Payload {
+        msg: PLAIN_TEXT,
+        aad: b"header",
+    };
+
+    let mut ct_bytes = eax.encrypt(&nonce, payload).unwrap();
+    assert_eq!(ct_bytes.len(), PLAIN_TEXT.len() + 16);
+
+    let payload = Payload {
+        msg: &ct_bytes,
+        aad: b"header",
+    };
+    assert_eq!(PLAIN_TEXT, eax.decrypt(&nonce, payload).unwrap());
+
+    ct_bytes[0] ^= 1;
+    let payload = Payload {
+        msg: &ct_bytes,
+        aad: b"header",
+    };
+    assert!(eax.decrypt(&nonce, payload).is_err());
+}
+
+#[test]
+fn eax_matches_eax_crate() -> Result<(), Reason> {
+    let eax = Eax::<RC5ControlBlock<u64>>::new_from_slice(&KEY).unwrap();
+    let cipher = rc5_cipher::<u64>(KEY, 16)?;
+    let nonce = super::random_iv();
+    let nonce_bytes = cipher.control_block().generate_bytes_stream(&[nonce]);
+
+    for len in [0, 1, 16, PLAIN_TEXT.len()] {
+        let payload = Payload {
+            msg: &PLAIN_TEXT[..len],
+            aad: b"header",
+        };
+        let expected = eax.encrypt(nonce_bytes.as_slice().into(), payload).unwrap();
+
+        let mode = OperationMode::EAX { nonce, tag_len: 16 };
+        assert_eq!(
+            expected,
+            cipher.encrypt_aead(&PLAIN_TEXT[..len], b"header", mode)?
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn ccm_matches_ccm_crate() -> Result<(), Reason> {
+    let ccm = Ccm::<RC5ControlBlock<u64>, U10, U13>::new_from_slice(&KEY).unwrap();
+    let cipher = rc5_cipher::<u64>(KEY, 16)?;
+    let nonce_bytes = *b"13 byte nonce";
+    let mode = OperationMode::CCM {
+        nonce: cipher.nonce_from_bytes(nonce_bytes)?,
+        length_size: 2,
+        tag_len: 10,
+    };
+
+    for len in [0, 1, 16, PLAIN_TEXT.len()] {
+        for aad in [&b""[..], b"header"] {
+            let payload = Payload {
+                msg: &PLAIN_TEXT[..len],
+                aad,
+            };
+            let expected = ccm.encrypt(&nonce_bytes.into(), payload).unwrap();
+            assert_eq!(
+                expected,
+                cipher.encrypt_aead(&PLAIN_TEXT[..len], aad, mode)?
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn gcm_matches_aes_gcm_crate() -> Result<(), Reason> {
+    let gcm = AesGcm::<RC5ControlBlock<u64>, U12>::new_from_slice(&KEY).unwrap();
+    let cipher = rc5_cipher::<u64>(KEY, 16)?;
+    let nonce_bytes = *b"96-bit nonce";
+    let nonce = cipher.gcm_nonce(nonce_bytes)?;
+
+    for len in [0, 1, 16, PLAIN_TEXT.len()] {
+        for aad in [&b""[..], b"header"] {
+            let payload = Payload {
+                msg: &PLAIN_TEXT[..len],
+                aad,
+            };
+            let expected = gcm.encrypt(&nonce_bytes.into(), payload).unwrap();
+            assert_eq!(
+                expected,
+                cipher.gcm_seal(nonce, &PLAIN_TEXT[..len], aad, 16)?
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use crate::{BlockCipher, RC5ControlBlock, Reason, U24, U80, U256, Version, Word};
+
+macro_rules! schedule_round_trip {
+    ($( $fn_name:ident: ( $w:ty , $key:expr , $rounds:expr , $version:expr) ),*$(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() -> Result<(), Reason> {
+                let control_block = RC5ControlBlock::<$w>::new($key, $rounds)?;
+                let (version, s_table) = control_block.export_schedule();
+
+                assert_eq!($version, version.to_string());
+                assert_eq!(control_block.s_table(), &s_table[..]);
+
+                let version: Version = version.to_string().parse()?;
+                let restored = RC5ControlBlock::<$w>::from_schedule(&version, &s_table)?;
+
+                assert_eq!(control_block.parametric_version(), restored.parametric_version());
+                assert_eq!(control_block.s_table(), restored.s_table());
+
+                let plain_text = [<$w>::from_u8(0x5a); 2];
+                assert_eq!(control_block.encrypt(plain_text), restored.encrypt(plain_text));
+
+                Ok(())
+            }
+        )*
+    };
+}
+
+schedule_round_trip! {
+    rc5_16_schedule: (u16, [0x11u8; 8], 16, "RC5-v1/16/16/8"),
+    rc5_32_schedule: (u32, b"mykey", 12, "RC5-v1/32/12/5"),
+    rc5_64_schedule: (u64, [0u8; 24], 0, "RC5-v1/64/0/24"),
+    rc5_24_schedule: (U24, [0x24u8; 3], 12, "RC5-v1/24/12/3"),
+    rc5_80_schedule: (U80, [0u8; 12], 255, "RC5-v1/80/255/12"),
+    rc5_256_schedule: (U256, [0xffu8; 32], 12, "RC5-v1/256/12/32"),
+}
+
+#[test]
+fn from_schedule_rejects_mismatched_tables() -> Result<(), Reason> {
+    let control_block = RC5ControlBlock::<u32>::new(b"mykey", 12)?;
+    let (version, s_table) = control_block.export_schedule();
+
+    assert!(matches!(
+        RC5ControlBlock::<u32>::from_schedule(&version, &s_table[..25]),
+        Err(Reason::ScheduleLength {
+            expected: 26,
+            current: 25
+        })
+    ));
+
+    let version_16: Version = "RC5-v1/16/12/5".parse()?;
+    assert!(matches!(
+        RC5ControlBlock::<u32>::from_schedule(&version_16, &s_table),
+        Err(Reason::WordSize)
+    ));
+
+    let version_20: Version = "RC5-v1/32/20/5".parse()?;
+    assert!(matches!(
+        RC5ControlBlock::<u32>::from_schedule(&version_20, &s_table),
+        Err(Reason::ScheduleLength {
+            expected: 42,
+            current: 26
+        })
+    ));
+
+    for version in ["RC6-v1/32/12/5", "RC5-v2/32/12/5"] {
+        assert!(matches!(
+            RC5ControlBlock::<u32>::from_schedule(&version.parse()?, &s_table),
+            Err(Reason::InvalidVersion(_))
+        ));
+    }
+
+    let malformed = Version::from_parametric_vector(vec![1, 32, 12]);
+    assert!(matches!(
+        RC5ControlBlock::<u32>::from_schedule(&malformed, &s_table),
+        Err(Reason::InvalidVersion(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn parse_invalid_versions() {
+    for version in [
+        "",
+        "RC5",
+        "RC5-v1/32/12",
+        "RC5-v1/32/12/16/1",
+        "RC5-v1/32/256/16",
+        "RC5-v256/32/12/16",
+        "RC5-v1/65536/12/16",
+        "RC5-v1/32/-1/16",
+        "AES-v1/128/10/16",
+        "rc5-v1/32/12/16",
+    ] {
+        assert!(
+            matches!(version.parse::<Version>(), Err(Reason::InvalidVersion(v)) if v == version),
+            "{version}"
+        );
+    }
+}
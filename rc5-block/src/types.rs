@@ -1,4 +1,14 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
 use rand::Rng;
+use zeroize::Zeroize;
+
+use crate::{Reason, bail, simd};
 
 /// # RC5 version identifier
 ///
@@ -15,40 +25,121 @@ use rand::Rng;
 /// RC5-v<`Algorithm version`>/<`Word-size`>/<`Rounds`>/<`Key-length`>
 ///
 /// This can be useful when asserting what parametric version of RC5 to
-/// use for certain applications.
-pub struct Version(Vec<u8>);
+/// use for certain applications. A version string can be parsed back
+/// with [str::parse], e.g. to restore an exported key schedule.
+///
+/// The parameters are kept as `u16`, since the word-size of RC5-256
+/// does not fit in a byte.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Version {
+    algorithm: &'static str,
+    params: Vec<u16>,
+}
 
 impl Version {
-    /// Construct a new `Version` from a 4‑element parameter vector.
+    /// Construct a new RC5 `Version` from a 4‑element parameter vector.
     ///
     /// Expects the vector to be exactly four bytes:
-    /// `[algorithm, word_bits, rounds, key_bytes]`.
+    /// `[algorithm, word_bits, rounds, key_bytes]`. Word-sizes above
+    /// 255 bits need [Version::from_algorithm_parameters].
     pub fn from_parametric_vector(params: Vec<u8>) -> Self {
-        Self(params)
+        Self::from_algorithm_parameters("RC5", params.into_iter().map(u16::from).collect())
+    }
+
+    /// Construct a new `Version` for a named algorithm of the RC5
+    /// family (e.g. `"RC6"`) from a 4‑element parameter vector.
+    pub fn from_algorithm_parameters(algorithm: &'static str, params: Vec<u16>) -> Self {
+        Self { algorithm, params }
     }
 
-    /// Render the RC5 version string in the form: `RC5-vA/B/C/D`.
+    /// Builds the version of a control block of `W` words.
+    pub(crate) fn of_word<W: Word>(algorithm: &'static str, rounds: usize, key_len: usize) -> Self {
+        let word_bits = u16::try_from(W::BYTES * 8).expect("word-size fits in 16 bits");
+        Self::from_algorithm_parameters(
+            algorithm,
+            vec![1, word_bits, rounds as u16, key_len as u16],
+        )
+    }
+
+    /// Render the version string in the form: `RC5-vA/B/C/D`.
     ///
     /// Where A,B,C,D correspond to the four parameters passed to `new`.
     pub fn version(&self) -> String {
-        let params = &self.0;
+        let params = &self.params;
         format!(
-            "RC5-v{}/{}/{}/{}",
-            params[0], params[1], params[2], params[3]
+            "{}-v{}/{}/{}/{}",
+            self.algorithm, params[0], params[1], params[2], params[3]
         )
     }
+
+    /// Name of the algorithm, e.g. `"RC5"` or `"RC6"`.
+    pub fn algorithm(&self) -> &'static str {
+        self.algorithm
+    }
+
+    /// Raw parameters, `[algorithm, word_bits, rounds, key_bytes]` for a
+    /// well-formed version.
+    pub fn params(&self) -> &[u16] {
+        &self.params
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.version())
+    }
+}
+
+/// Parses a version string in the form `RC5-vA/B/C/D` (or `RC6-vA/B/C/D`).
+///
+/// Fails with [Reason::InvalidVersion] for any other algorithm, or
+/// anything but four decimal parameters within `0-255`, except for the
+/// word-size which may go up to `65535` bits.
+impl FromStr for Version {
+    type Err = Reason;
+
+    fn from_str(version: &str) -> Result<Self, Reason> {
+        let invalid = || Reason::InvalidVersion(version.to_string());
+
+        let (algorithm, params) = version.split_once("-v").ok_or_else(invalid)?;
+        let algorithm = match algorithm {
+            "RC5" => "RC5",
+            "RC6" => "RC6",
+            _ => return Err(invalid()),
+        };
+
+        let params = params
+            .split('/')
+            .map(str::parse)
+            .collect::<Result<Vec<u16>, _>>()
+            .map_err(|_| invalid())?;
+        bail!(
+            params.len() != 4,
+            invalid(),
+            [params[0], params[2], params[3]]
+                .iter()
+                .any(|&param| param > 255),
+            invalid()
+        );
+
+        Ok(Self::from_algorithm_parameters(algorithm, params))
+    }
 }
 
 /// A core trait to define a word in `N-sized` blocks of a block cipher. This
 /// word must support arithmatic and binary operations required for cryptographic
-/// functions.
-pub trait Word: Clone + Copy + std::ops::BitXor<Output = Self> {
+/// functions, and be wipeable with [Zeroize] as it carries key material.
+pub trait Word: Clone + Copy + Send + Sync + Zeroize + core::ops::BitXor<Output = Self> {
     /// A constant zero value for a `Word` type.
     const ZERO: Self;
 
     /// Number of bytes in this word
     const BYTES: usize;
 
+    /// Fixed-size little-endian byte representation of this word,
+    /// e.g. `[u8; 4]` for `u32`, exactly `BYTES` long.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default + Copy;
+
     /// Magic constant `P` represented by this word to
     /// be used in RC5 key expansion.
     const P: Self;
@@ -60,13 +151,21 @@ pub trait Word: Clone + Copy + std::ops::BitXor<Output = Self> {
     /// Cast a 8-bit value to this word type.
     fn from_u8(val: u8) -> Self;
 
+    /// Cast a 64-bit value to this word type, truncating it to
+    /// the word width.
+    fn from_u64(val: u64) -> Self;
+
     /// Parse this word from a little‐endian byte slice of length `BYTES`.
     ///
     /// Returns `None` if the slice length is not equal to `Word::BYTES`
     fn from_bytes_slice(slice: &[u8]) -> Option<Self>;
 
-    /// Serialize this word to a little‐endian bytes list.
-    fn to_bytes_slice(&self) -> Vec<u8>;
+    /// Construct this word from its little-endian byte representation.
+    fn from_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Serialize this word to its little-endian byte representation,
+    /// without allocating.
+    fn to_bytes(self) -> Self::Bytes;
 
     /// Generate a random word using the given RNG.
     fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
@@ -77,53 +176,154 @@ pub trait Word: Clone + Copy + std::ops::BitXor<Output = Self> {
     /// Wrapped subtraction
     fn wrapping_sub(self, val: Self) -> Self;
 
-    /// Left bitwise rotation
+    /// Wrapped multiplication
+    fn wrapping_mul(self, val: Self) -> Self;
+
+    /// Left bitwise rotation, only the low `lg(w)` bits of `bits`
+    /// are used as the rotation amount.
     fn rotate_left(self, bits: Self) -> Self;
 
-    /// Right bitwise rotation
+    /// Right bitwise rotation, only the low `lg(w)` bits of `bits`
+    /// are used as the rotation amount.
     fn rotate_right(self, bits: Self) -> Self;
+
+    /// Encrypts a batch of independent RC5 blocks under the expanded
+    /// `s_table` with a multi-lane SIMD kernel, if one is available for
+    /// this word type on the running CPU.
+    ///
+    /// Returns the number of leading blocks processed, the remaining ones
+    /// are left to the scalar path. Processes none by default.
+    #[doc(hidden)]
+    fn rc5_encrypt_lanes(_s_table: &[Self], _blocks: &mut [[Self; 2]]) -> usize {
+        0
+    }
+
+    /// Decryption counterpart of [Word::rc5_encrypt_lanes].
+    #[doc(hidden)]
+    fn rc5_decrypt_lanes(_s_table: &[Self], _blocks: &mut [[Self; 2]]) -> usize {
+        0
+    }
 }
 
-macro_rules! magic_consts {
-    (u16) => {
-        const P: u16 = 0xb7e1;
-        const Q: u16 = 0x9e37;
-    };
-    (u32) => {
-        const P: u32 = 0xb7e15163;
-        const Q: u32 = 0x9e3779b9;
-    };
-    (u64) => {
-        const P: u64 = 0xb7e151628aed2a6b;
-        const Q: u64 = 0x9e3779b97f4a7c15;
-    };
-    (u128) => {
-        const P: u128 = 0x9E3779B97F4A7C15F39CC0605CEDC835;
-        const Q: u128 = 0xB7E151628AED2A6ABF7158809CF4F3C7;
-    };
+/// Maximum word-width in bits for which the RC5 magic constants
+/// can be derived, bounded by the stored expansions of `e` and `φ`.
+pub const MAGIC_MAX_BITS: usize = 512;
+
+/// Binary expansion of the fractional part of `e` (i.e. `e - 2`),
+/// most significant byte first.
+const E_EXPANSION: [u8; MAGIC_MAX_BITS / 8] = [
+    0xb7, 0xe1, 0x51, 0x62, 0x8a, 0xed, 0x2a, 0x6a, 0xbf, 0x71, 0x58, 0x80, 0x9c, 0xf4, 0xf3, 0xc7,
+    0x62, 0xe7, 0x16, 0x0f, 0x38, 0xb4, 0xda, 0x56, 0xa7, 0x84, 0xd9, 0x04, 0x51, 0x90, 0xcf, 0xef,
+    0x32, 0x4e, 0x77, 0x38, 0x92, 0x6c, 0xfb, 0xe5, 0xf4, 0xbf, 0x8d, 0x8d, 0x8c, 0x31, 0xd7, 0x63,
+    0xda, 0x06, 0xc8, 0x0a, 0xbb, 0x11, 0x85, 0xeb, 0x4f, 0x7c, 0x7b, 0x57, 0x57, 0xf5, 0x95, 0x84,
+];
+
+/// Binary expansion of the fractional part of the golden ratio
+/// (i.e. `φ - 1`), most significant byte first.
+const PHI_EXPANSION: [u8; MAGIC_MAX_BITS / 8] = [
+    0x9e, 0x37, 0x79, 0xb9, 0x7f, 0x4a, 0x7c, 0x15, 0xf3, 0x9c, 0xc0, 0x60, 0x5c, 0xed, 0xc8, 0x34,
+    0x10, 0x82, 0x27, 0x6b, 0xf3, 0xa2, 0x72, 0x51, 0xf8, 0x6c, 0x6a, 0x11, 0xd0, 0xc1, 0x8e, 0x95,
+    0x27, 0x67, 0xf0, 0xb1, 0x53, 0xd2, 0x7b, 0x7f, 0x03, 0x47, 0x04, 0x5b, 0x5b, 0xf1, 0x82, 0x7f,
+    0x01, 0x88, 0x6f, 0x09, 0x28, 0x40, 0x30, 0x02, 0xc1, 0xd6, 0x4b, 0xa4, 0x0f, 0x33, 0x5e, 0x36,
+];
+
+/// Derive `Odd(frac * 2^bits)` from a fractional binary expansion.
+///
+/// The odd integer nearest to `x` is always `floor(x) | 1`, so this takes
+/// the leading `bits` bits of the expansion and sets the lowest one. The
+/// result is returned as `L` little-endian 64-bit limbs.
+const fn derive_magic<const L: usize>(
+    expansion: &[u8; MAGIC_MAX_BITS / 8],
+    bits: usize,
+) -> [u64; L] {
+    assert!(
+        bits > 0 && bits <= MAGIC_MAX_BITS && bits <= 64 * L,
+        "word width out of range for magic constant derivation"
+    );
+
+    let mut limbs = [0u64; L];
+    let mut ix = 0;
+    while ix < bits {
+        let bit = (expansion[ix / 8] >> (7 - ix % 8)) & 1;
+        let position = bits - 1 - ix;
+        limbs[position / 64] |= (bit as u64) << (position % 64);
+        ix += 1;
+    }
+    limbs[0] |= 1;
+
+    limbs
+}
+
+/// RC5 magic constant `P_w = Odd((e - 2) * 2^w)` for a word of `bits` width.
+///
+/// Returns `L` little-endian 64-bit limbs, `bits` must not exceed
+/// `64 * L` nor [MAGIC_MAX_BITS].
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(rc5_block::magic_p::<1>(32), [0xb7e15163]);
+/// assert_eq!(rc5_block::magic_p::<1>(24), [0xb7e151]);
+/// ```
+pub const fn magic_p<const L: usize>(bits: usize) -> [u64; L] {
+    derive_magic(&E_EXPANSION, bits)
+}
+
+/// RC5 magic constant `Q_w = Odd((φ - 1) * 2^w)` for a word of `bits` width.
+///
+/// Returns `L` little-endian 64-bit limbs, `bits` must not exceed
+/// `64 * L` nor [MAGIC_MAX_BITS].
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(rc5_block::magic_q::<1>(32), [0x9e3779b9]);
+/// assert_eq!(rc5_block::magic_q::<2>(80), [0x79b97f4a7c15f39d, 0x9e37]);
+/// ```
+pub const fn magic_q<const L: usize>(bits: usize) -> [u64; L] {
+    derive_magic(&PHI_EXPANSION, bits)
+}
+
+/// Combine two little-endian limbs into a single 128-bit value.
+const fn limbs_to_u128(limbs: [u64; 2]) -> u128 {
+    ((limbs[1] as u128) << 64) | limbs[0] as u128
 }
 
 macro_rules! impl_word_for_prim {
-    ($($t:ident),*) => {
+    ($($t:ident $(=> ($encrypt_lanes:path, $decrypt_lanes:path))?),*) => {
         $(
             impl Word for $t {
                 const ZERO: $t = 0;
                 const BYTES: usize = (<$t>::BITS / 8) as usize;
 
-                magic_consts!($t);
+                type Bytes = [u8; (<$t>::BITS / 8) as usize];
+
+                const P: $t = limbs_to_u128(magic_p::<2>(<$t>::BITS as usize)) as $t;
+                const Q: $t = limbs_to_u128(magic_q::<2>(<$t>::BITS as usize)) as $t;
 
                 #[inline]
                 fn from_u8(val: u8) -> Self {
                     val as $t
                 }
 
+                #[inline]
+                fn from_u64(val: u64) -> Self {
+                    val as $t
+                }
+
                 #[inline]
                 fn from_bytes_slice(slice: &[u8]) -> Option<Self> {
                     slice.try_into().ok().map(|b| <$t>::from_le_bytes(b))
                 }
 
-                fn to_bytes_slice(& self) -> Vec<u8> {
-                    self.to_le_bytes().to_vec()
+                #[inline]
+                fn from_bytes(bytes: Self::Bytes) -> Self {
+                    <$t>::from_le_bytes(bytes)
+                }
+
+                #[inline]
+                fn to_bytes(self) -> Self::Bytes {
+                    self.to_le_bytes()
                 }
 
                 #[inline]
@@ -141,18 +341,103 @@ macro_rules! impl_word_for_prim {
                     <$t>::wrapping_sub(self, other)
                 }
 
+                #[inline]
+                fn wrapping_mul(self, other: Self) -> Self {
+                    <$t>::wrapping_mul(self, other)
+                }
+
                 #[inline]
                 fn rotate_left(self, bits: Self) -> Self {
-                    self.rotate_left(bits as u32)
+                    self.rotate_left((bits as u32) & (<$t>::BITS - 1))
                 }
 
                 #[inline]
                 fn rotate_right(self, bits: Self) -> Self {
-                    self.rotate_right(bits as u32)
+                    self.rotate_right((bits as u32) & (<$t>::BITS - 1))
                 }
+
+                $(
+                    #[inline]
+                    fn rc5_encrypt_lanes(s_table: &[Self], blocks: &mut [[Self; 2]]) -> usize {
+                        $encrypt_lanes(s_table, blocks)
+                    }
+
+                    #[inline]
+                    fn rc5_decrypt_lanes(s_table: &[Self], blocks: &mut [[Self; 2]]) -> usize {
+                        $decrypt_lanes(s_table, blocks)
+                    }
+                )?
             }
         )*
     }
 }
 
-impl_word_for_prim!(u16, u32, u64, u128);
+impl_word_for_prim!(
+    u8,
+    u16,
+    u32 => (simd::encrypt_u32, simd::decrypt_u32),
+    u64 => (simd::encrypt_u64, simd::decrypt_u64),
+    u128
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{Word, magic_p, magic_q};
+
+    #[test]
+    fn derived_constants_for_native_words() {
+        assert_eq!((u8::P, u8::Q), (0xb7, 0x9f));
+        assert_eq!((u16::P, u16::Q), (0xb7e1, 0x9e37));
+        assert_eq!((u32::P, u32::Q), (0xb7e15163, 0x9e3779b9));
+        assert_eq!((u64::P, u64::Q), (0xb7e151628aed2a6b, 0x9e3779b97f4a7c15));
+        assert_eq!(
+            (u128::P, u128::Q),
+            (
+                0xb7e151628aed2a6abf7158809cf4f3c7,
+                0x9e3779b97f4a7c15f39cc0605cedc835
+            )
+        );
+    }
+
+    #[test]
+    fn bytes_are_little_endian_arrays() {
+        let bytes: [u8; 4] = 0x0403_0201u32.to_bytes();
+        assert_eq!(bytes, [1, 2, 3, 4]);
+        assert_eq!(u32::from_bytes(bytes), 0x0403_0201);
+        assert_eq!(u8::from_bytes([0x7f]).to_bytes(), [0x7f]);
+    }
+
+    #[test]
+    fn derived_constants_for_24_bit_words() {
+        assert_eq!(magic_p::<1>(24), [0xb7e151]);
+        assert_eq!(magic_q::<1>(24), [0x9e3779]);
+    }
+
+    #[test]
+    fn derived_constants_for_80_bit_words() {
+        assert_eq!(magic_p::<2>(80), [0x51628aed2a6abf71, 0xb7e1]);
+        assert_eq!(magic_q::<2>(80), [0x79b97f4a7c15f39d, 0x9e37]);
+    }
+
+    #[test]
+    fn derived_constants_for_256_bit_words() {
+        assert_eq!(
+            magic_p::<4>(256),
+            [
+                0xa784d9045190cfef,
+                0x62e7160f38b4da56,
+                0xbf7158809cf4f3c7,
+                0xb7e151628aed2a6a
+            ]
+        );
+        assert_eq!(
+            magic_q::<4>(256),
+            [
+                0xf86c6a11d0c18e95,
+                0x1082276bf3a27251,
+                0xf39cc0605cedc834,
+                0x9e3779b97f4a7c15
+            ]
+        );
+    }
+}
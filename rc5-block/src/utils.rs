@@ -1,5 +1,7 @@
-use std::array::from_fn;
+use alloc::vec::Vec;
+use core::array::from_fn;
 
+#[cfg(feature = "std")]
 use rand::thread_rng;
 
 use crate::{Reason, Word, bail};
@@ -17,6 +19,7 @@ use crate::{Reason, Word, bail};
 /// // generates a pseudo-random iv of block size [u32;2]
 /// let iv: [u32; 2] = rc5_block::random_iv::<u32, 2>();
 /// ```
+#[cfg(feature = "std")]
 pub fn random_iv<W, const N: usize>() -> [W; N]
 where
     W: Word,
@@ -42,6 +45,7 @@ where
 /// let nc: [u32; 2] = rc5_block::random_nonce_and_counter::<u32, 2>();
 /// assert_eq!(nc[1], 0); // counter initialized to zero
 /// ```
+#[cfg(feature = "std")]
 pub fn random_nonce_and_counter<W, const N: usize>() -> [W; N]
 where
     W: Word,
@@ -81,17 +85,51 @@ where
 /// Reutrns the number of bytes padded or removed.
 pub fn pkcs7(buf: &mut Vec<u8>, bs: usize, pad: bool) -> Result<usize, Reason> {
     if pad {
-        let rem = buf.len() % bs;
-        let pad_count = if rem > 0 { bs - rem } else { bs };
-        buf.extend(std::iter::repeat_n(pad_count as u8, pad_count));
-        return Ok(rem);
+        let len = buf.len();
+        buf.resize(pkcs7_padded_len(len, bs), 0);
+        pkcs7_pad_in_place(buf, len, bs)?;
+        return Ok(len % bs);
     }
 
+    let unpadded = pkcs7_unpad_in_place(buf, bs)?;
+    let pad_len = buf.len() - unpadded;
+    buf.truncate(unpadded);
+    Ok(pad_len)
+}
+
+/// Length of a `len` bytes message once PKCS#7 padded to
+/// a multiple of `bs`.
+pub(crate) fn pkcs7_padded_len(len: usize, bs: usize) -> usize {
+    len + bs - len % bs
+}
+
+/// Writes the PKCS#7 padding of the first `len` bytes of `buf` into
+/// its slack, without allocating.
+///
+/// Fails with [Reason::BufferTooSmall] if `buf` cannot hold the padded
+/// message, returns the padded length otherwise.
+pub(crate) fn pkcs7_pad_in_place(buf: &mut [u8], len: usize, bs: usize) -> Result<usize, Reason> {
+    let required = pkcs7_padded_len(len, bs);
+    bail!(
+        required > buf.len(),
+        Reason::BufferTooSmall {
+            required,
+            available: buf.len()
+        }
+    );
+
+    buf[len..required].fill((required - len) as u8);
+    Ok(required)
+}
+
+/// Validates the PKCS#7 padding at the end of `buf` and returns the
+/// length of the message without it.
+pub(crate) fn pkcs7_unpad_in_place(buf: &[u8], bs: usize) -> Result<usize, Reason> {
     let len = buf.len();
 
     // return the same error everytime just to avoid
     // oracle attacks on pading scheme.
-    bail!(len == 0 || len % bs != 0, Reason::Padding);
+    bail!(len == 0 || !len.is_multiple_of(bs), Reason::Padding);
 
     let pad_len = *buf.last().unwrap() as usize;
 
@@ -104,14 +142,74 @@ pub fn pkcs7(buf: &mut Vec<u8>, bs: usize, pad: bool) -> Result<usize, Reason> {
         Reason::Padding
     );
 
-    let padding = len - pad_len;
-    buf.truncate(padding);
-    Ok(pad_len)
+    Ok(len - pad_len)
+}
+
+/// Reads a `[W; N]` block from `N * W::BYTES` little-endian bytes.
+#[inline]
+pub(crate) fn block_from_bytes<W: Word, const N: usize>(bytes: &[u8]) -> [W; N] {
+    from_fn(|ix| {
+        let mut word = W::Bytes::default();
+        word.as_mut()
+            .copy_from_slice(&bytes[ix * W::BYTES..(ix + 1) * W::BYTES]);
+        W::from_bytes(word)
+    })
+}
+
+/// Writes a `[W; N]` block as little-endian bytes, only the first
+/// `bytes.len()` bytes are written if `bytes` is shorter than a block.
+#[inline]
+pub(crate) fn block_to_bytes<W: Word, const N: usize>(block: &[W; N], bytes: &mut [u8]) {
+    for (chunk, word) in bytes.chunks_mut(W::BYTES).zip(block) {
+        chunk.copy_from_slice(&word.to_bytes().as_ref()[..chunk.len()]);
+    }
+}
+
+/// XORs the little-endian bytes of a `[W; N]` block into `bytes`, only
+/// the first `bytes.len()` bytes are masked if shorter than a block.
+#[inline]
+pub(crate) fn xor_block_into_bytes<W: Word, const N: usize>(block: &[W; N], bytes: &mut [u8]) {
+    for (chunk, word) in bytes.chunks_mut(W::BYTES).zip(block) {
+        for (byte, key) in chunk.iter_mut().zip(word.to_bytes().as_ref()) {
+            *byte ^= key;
+        }
+    }
+}
+
+/// Compares the leading `bytes.len()` little-endian bytes of a `[W; N]`
+/// block with `bytes` in constant time, `bytes` must not be longer than
+/// a block.
+pub(crate) fn block_matches_bytes<W: Word, const N: usize>(block: &[W; N], bytes: &[u8]) -> bool {
+    let mut diff = 0u8;
+    for (chunk, word) in bytes.chunks(W::BYTES).zip(block) {
+        for (byte, expected) in chunk.iter().zip(word.to_bytes().as_ref()) {
+            diff |= byte ^ expected;
+        }
+    }
+
+    diff == 0
+}
+
+/// Compares two blocks in constant time.
+pub(crate) fn blocks_match<W: Word, const N: usize>(block: &[W; N], other: &[W; N]) -> bool {
+    let mut diff = 0u8;
+    for (word, other) in block.iter().zip(other) {
+        for (byte, expected) in word
+            .to_bytes()
+            .as_ref()
+            .iter()
+            .zip(other.to_bytes().as_ref())
+        {
+            diff |= byte ^ expected;
+        }
+    }
+
+    diff == 0
 }
 
 #[cfg(test)]
 mod tests {
-    use super::pkcs7;
+    use super::{pkcs7, pkcs7_pad_in_place, pkcs7_unpad_in_place};
     use crate::Reason;
 
     #[test]
@@ -191,4 +289,29 @@ mod tests {
         let result = pkcs7(&mut data, block_size, false);
         assert!(matches!(result, Err(Reason::Padding)));
     }
+
+    #[test]
+    fn pad_in_place_into_slack() {
+        let mut buf = *b"hello\0\0\0\0\0";
+        let block_size = 4;
+
+        let padded = pkcs7_pad_in_place(&mut buf, 5, block_size).unwrap();
+        assert_eq!(padded, 8);
+        assert_eq!(&buf[..8], b"hello\x03\x03\x03");
+        assert_eq!(pkcs7_unpad_in_place(&buf[..8], block_size).unwrap(), 5);
+    }
+
+    #[test]
+    fn pad_in_place_without_slack() {
+        let mut buf = *b"12345678";
+
+        let result = pkcs7_pad_in_place(&mut buf, 8, 8);
+        assert!(matches!(
+            result,
+            Err(Reason::BufferTooSmall {
+                required: 16,
+                available: 8
+            })
+        ));
+    }
 }
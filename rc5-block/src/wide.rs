@@ -0,0 +1,358 @@
+use rand::Rng;
+use zeroize::Zeroize;
+
+use crate::{
+    Word,
+    types::{magic_p, magic_q},
+};
+
+/// Number of 64-bit limbs backing a [WideWord].
+const LIMBS: usize = 4;
+
+/// Maximum width in bits supported by [WideWord].
+pub const WIDE_MAX_BITS: usize = LIMBS * 64;
+
+/// # Wide word
+///
+/// A const-generic multi-limb word of `BITS` width, which makes the
+/// non-native word sizes of RC5 expressible, e.g. RC5-24, RC5-80 or
+/// RC5-256 (see [draft-krovetz-rc6-rc5-vectors](https://datatracker.ietf.org/doc/html/draft-krovetz-rc6-rc5-vectors-00)).
+///
+/// `BITS` must be a non-zero multiple of 8 and at most [WIDE_MAX_BITS].
+/// The value is stored as little-endian 64-bit limbs, all the arithmetic
+/// is performed modulo `2^BITS`.
+///
+/// For widths which are not a power of two, rotations only use the low
+/// `floor(lg(BITS))` bits of the rotation amount.
+///
+/// # Example
+///
+/// ```rust
+/// use rc5_block::{rc5_cipher, OperationMode, WideWord};
+///
+/// // RC5-80/12/16, a 160-bit block cipher
+/// let cipher = rc5_cipher::<WideWord<80>>(b"0123456789abcdef", 12).unwrap();
+///
+/// let ct = cipher.encrypt(b"wide words", OperationMode::ECB).unwrap();
+/// assert_eq!(cipher.decrypt(&ct, OperationMode::ECB).unwrap(), b"wide words");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WideWord<const BITS: usize> {
+    limbs: [u64; LIMBS],
+}
+
+/// Little-endian byte representation of a [WideWord], used as its
+/// [Word::Bytes]. Only the first `BITS / 8` bytes are exposed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WideBytes<const BITS: usize> {
+    bytes: [u8; WIDE_MAX_BITS / 8],
+}
+
+impl<const BITS: usize> Default for WideBytes<BITS> {
+    fn default() -> Self {
+        Self {
+            bytes: [0; WIDE_MAX_BITS / 8],
+        }
+    }
+}
+
+impl<const BITS: usize> AsRef<[u8]> for WideBytes<BITS> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..WideWord::<BITS>::BYTES]
+    }
+}
+
+impl<const BITS: usize> AsMut<[u8]> for WideBytes<BITS> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes[..WideWord::<BITS>::BYTES]
+    }
+}
+
+impl<const BITS: usize> Zeroize for WideWord<BITS> {
+    fn zeroize(&mut self) {
+        self.limbs.zeroize();
+    }
+}
+
+/// 24-bit wide word, e.g. for RC5-24.
+pub type U24 = WideWord<24>;
+
+/// 80-bit wide word, e.g. for RC5-80.
+pub type U80 = WideWord<80>;
+
+/// 256-bit wide word, e.g. for RC5-256.
+pub type U256 = WideWord<256>;
+
+impl<const BITS: usize> WideWord<BITS> {
+    /// Validated width of this word type.
+    const WIDTH: usize = {
+        assert!(
+            BITS > 0 && BITS.is_multiple_of(8) && BITS <= WIDE_MAX_BITS,
+            "WideWord width must be a non-zero multiple of 8 up to 256 bits"
+        );
+        BITS
+    };
+
+    /// Mask of the meaningful bits in each limb.
+    const MASK: [u64; LIMBS] = {
+        let mut mask = [0u64; LIMBS];
+        let mut ix = 0;
+        while ix < LIMBS {
+            let low = ix * 64;
+            if Self::WIDTH >= low + 64 {
+                mask[ix] = u64::MAX;
+            } else if Self::WIDTH > low {
+                mask[ix] = (1 << (Self::WIDTH - low)) - 1;
+            }
+            ix += 1;
+        }
+        mask
+    };
+
+    /// Mask applied to a rotation amount, keeps its low `floor(lg(BITS))` bits.
+    const ROTATION_MASK: u64 = (1 << Self::WIDTH.ilog2()) - 1;
+
+    /// Construct a word from little-endian 64-bit limbs, truncating
+    /// any bits beyond `BITS`.
+    pub fn from_limbs(limbs: [u64; LIMBS]) -> Self {
+        Self { limbs }.truncated()
+    }
+
+    /// Returns the little-endian 64-bit limbs of this word.
+    pub fn limbs(&self) -> [u64; LIMBS] {
+        self.limbs
+    }
+
+    /// Reduce this word modulo `2^BITS`.
+    #[inline]
+    fn truncated(mut self) -> Self {
+        for (limb, mask) in self.limbs.iter_mut().zip(Self::MASK) {
+            *limb &= mask;
+        }
+        self
+    }
+
+    /// Logical left shift by `bits < BITS`.
+    #[inline]
+    fn shl(self, bits: usize) -> Self {
+        let (limb_shift, bit_shift) = (bits / 64, bits % 64);
+        let mut limbs = [0u64; LIMBS];
+
+        for ix in (limb_shift..LIMBS).rev() {
+            let src = ix - limb_shift;
+            limbs[ix] = self.limbs[src] << bit_shift;
+            if bit_shift > 0 && src > 0 {
+                limbs[ix] |= self.limbs[src - 1] >> (64 - bit_shift);
+            }
+        }
+
+        Self { limbs }.truncated()
+    }
+
+    /// Logical right shift by `bits < BITS`.
+    #[inline]
+    fn shr(self, bits: usize) -> Self {
+        let (limb_shift, bit_shift) = (bits / 64, bits % 64);
+        let mut limbs = [0u64; LIMBS];
+
+        for (ix, limb) in limbs.iter_mut().take(LIMBS - limb_shift).enumerate() {
+            let src = ix + limb_shift;
+            *limb = self.limbs[src] >> bit_shift;
+            if bit_shift > 0 && src + 1 < LIMBS {
+                *limb |= self.limbs[src + 1] << (64 - bit_shift);
+            }
+        }
+
+        Self { limbs }
+    }
+
+    /// Rotation amount encoded by this word.
+    #[inline]
+    fn rotation(self) -> usize {
+        (self.limbs[0] & Self::ROTATION_MASK) as usize
+    }
+}
+
+impl<const BITS: usize> core::ops::BitXor for WideWord<BITS> {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(mut self, rhs: Self) -> Self::Output {
+        for (limb, other) in self.limbs.iter_mut().zip(rhs.limbs) {
+            *limb ^= other;
+        }
+        self
+    }
+}
+
+impl<const BITS: usize> Word for WideWord<BITS> {
+    const ZERO: Self = Self { limbs: [0; LIMBS] };
+
+    const BYTES: usize = Self::WIDTH / 8;
+
+    type Bytes = WideBytes<BITS>;
+
+    const P: Self = Self {
+        limbs: magic_p::<LIMBS>(Self::WIDTH),
+    };
+
+    const Q: Self = Self {
+        limbs: magic_q::<LIMBS>(Self::WIDTH),
+    };
+
+    #[inline]
+    fn from_u8(val: u8) -> Self {
+        Self::from_u64(val as u64)
+    }
+
+    #[inline]
+    fn from_u64(val: u64) -> Self {
+        let mut limbs = [0; LIMBS];
+        limbs[0] = val;
+        Self { limbs }.truncated()
+    }
+
+    fn from_bytes_slice(slice: &[u8]) -> Option<Self> {
+        if slice.len() != Self::BYTES {
+            return None;
+        }
+
+        let mut bytes = WideBytes::default();
+        bytes.as_mut().copy_from_slice(slice);
+
+        Some(Self::from_bytes(bytes))
+    }
+
+    #[inline]
+    fn from_bytes(bytes: Self::Bytes) -> Self {
+        let mut limbs = [0u64; LIMBS];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.bytes.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        Self { limbs }.truncated()
+    }
+
+    #[inline]
+    fn to_bytes(self) -> Self::Bytes {
+        let mut bytes = WideBytes::default();
+        for (chunk, limb) in bytes.bytes.chunks_exact_mut(8).zip(self.limbs) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self { limbs: rng.r#gen() }.truncated()
+    }
+
+    #[inline]
+    fn wrapping_add(self, val: Self) -> Self {
+        let mut limbs = [0u64; LIMBS];
+        let mut carry = false;
+
+        for (ix, limb) in limbs.iter_mut().enumerate() {
+            let (sum, c1) = self.limbs[ix].overflowing_add(val.limbs[ix]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+
+        Self { limbs }.truncated()
+    }
+
+    #[inline]
+    fn wrapping_sub(self, val: Self) -> Self {
+        let mut limbs = [0u64; LIMBS];
+        let mut borrow = false;
+
+        for (ix, limb) in limbs.iter_mut().enumerate() {
+            let (diff, b1) = self.limbs[ix].overflowing_sub(val.limbs[ix]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            *limb = diff;
+            borrow = b1 || b2;
+        }
+
+        Self { limbs }.truncated()
+    }
+
+    #[inline]
+    fn wrapping_mul(self, val: Self) -> Self {
+        let mut limbs = [0u64; LIMBS];
+
+        for i in 0..LIMBS {
+            let mut carry = 0u128;
+            for j in 0..LIMBS - i {
+                let product =
+                    self.limbs[i] as u128 * val.limbs[j] as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = product as u64;
+                carry = product >> 64;
+            }
+        }
+
+        Self { limbs }.truncated()
+    }
+
+    #[inline]
+    fn rotate_left(self, bits: Self) -> Self {
+        match bits.rotation() {
+            0 => self,
+            n => self.shl(n) ^ self.shr(BITS - n),
+        }
+    }
+
+    #[inline]
+    fn rotate_right(self, bits: Self) -> Self {
+        match bits.rotation() {
+            0 => self,
+            n => self.shr(n) ^ self.shl(BITS - n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{U24, U80, U256, WideWord};
+    use crate::Word;
+
+    #[test]
+    fn wrapping_arithmetic_matches_modular_arithmetic() {
+        let max = WideWord::<80>::from_limbs([u64::MAX, u64::MAX, 0, 0]);
+        assert_eq!(max.limbs(), [u64::MAX, 0xffff, 0, 0]);
+        assert_eq!(max.wrapping_add(U80::from_u8(1)), U80::ZERO);
+        assert_eq!(U80::ZERO.wrapping_sub(U80::from_u8(1)), max);
+        assert_eq!(max.wrapping_mul(max), U80::from_u8(1));
+    }
+
+    #[test]
+    fn rotations_use_low_lg_w_bits() {
+        let word = U24::from_limbs([0x800001, 0, 0, 0]);
+        assert_eq!(word.rotate_left(U24::from_u8(1)).limbs()[0], 0x000003);
+        // 24-bit words only use the low 4 bits of the amount: 17 & 15 = 1.
+        assert_eq!(word.rotate_left(U24::from_u8(17)).limbs()[0], 0x000003);
+        assert_eq!(word.rotate_right(U24::from_u8(1)).limbs()[0], 0xc00000);
+    }
+
+    #[test]
+    fn rotations_across_limbs_round_trip() {
+        let word = U256::from_limbs([1, 2, 3, 0x8000_0000_0000_0000]);
+        for amount in [1u8, 63, 64, 65, 127, 128, 200, 255] {
+            let rotated = word.rotate_left(U256::from_u8(amount));
+            assert_eq!(rotated.rotate_right(U256::from_u8(amount)), word);
+        }
+        assert_eq!(
+            word.rotate_left(U256::from_u8(64)).limbs(),
+            [0x8000_0000_0000_0000, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let bytes = (0..10).collect::<Vec<u8>>();
+        let word = U80::from_bytes_slice(&bytes).unwrap();
+        assert_eq!(word.limbs(), [0x0706050403020100, 0x0908, 0, 0]);
+        assert_eq!(word.to_bytes().as_ref(), bytes);
+        assert!(U80::from_bytes_slice(&bytes[1..]).is_none());
+    }
+}
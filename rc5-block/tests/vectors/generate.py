@@ -0,0 +1,402 @@
+#!/usr/bin/env python3
+"""Known-answer vectors of the rc5-block mode tests.
+
+Recomputes the RC5 vectors of `src/tests/{cts,eax,ccm,gcm,siv,ocb}.rs` and
+the Blowfish OCB vectors of `src/tests/adapter.rs` from the RC5 paper's
+algorithm and the mode specifications, written here over any block cipher
+given as an encryption function of `bs` bytes blocks.
+
+Before printing anything, every mode is checked over AES-128 against the
+published vectors of its specification, or against the AEAD constructions
+of the `cryptography` package, which also provides the AES and Blowfish
+block ciphers. The vectors were generated with Python 3.11.7 and
+cryptography 48.0.0:
+
+    python3 rc5-block/tests/vectors/generate.py
+"""
+
+from decimal import Decimal, getcontext
+
+from cryptography.hazmat.decrepit.ciphers.algorithms import Blowfish
+from cryptography.hazmat.primitives.ciphers import Cipher, algorithms, modes
+from cryptography.hazmat.primitives.ciphers.aead import AESCCM, AESGCM, AESOCB3, AESSIV
+
+
+def xor(a, b):
+    return bytes(x ^ y for x, y in zip(a, b))
+
+
+# RC5-w/r/b, with the little-endian words of the RC5 paper and OpenSSL.
+class RC5:
+    def __init__(self, key, rounds, w):
+        self.w, self.r, self.mask = w, rounds, (1 << w) - 1
+        u = w // 8
+        getcontext().prec = 100
+        odd = lambda x: int(x) | 1
+        p = odd((Decimal(1).exp() - 2) * 2**w)
+        q = odd(((1 + Decimal(5).sqrt()) / 2 - 1) * 2**w)
+
+        c = max(1, -(-len(key) // u))
+        lw = [int.from_bytes(key[i * u : (i + 1) * u], "little") for i in range(c)]
+        t = 2 * (rounds + 1)
+        s = [(p + i * q) & self.mask for i in range(t)]
+        a = b = i = j = 0
+        for _ in range(3 * max(t, c)):
+            a = s[i] = self.rotl((s[i] + a + b) & self.mask, 3)
+            b = lw[j] = self.rotl((lw[j] + a + b) & self.mask, a + b)
+            i, j = (i + 1) % t, (j + 1) % c
+        self.s = s
+
+    def rotl(self, x, n):
+        n %= self.w
+        return ((x << n) | (x >> (self.w - n))) & self.mask
+
+    def encrypt(self, block):
+        u = self.w // 8
+        a = (int.from_bytes(block[:u], "little") + self.s[0]) & self.mask
+        b = (int.from_bytes(block[u:], "little") + self.s[1]) & self.mask
+        for i in range(1, self.r + 1):
+            a = (self.rotl(a ^ b, b) + self.s[2 * i]) & self.mask
+            b = (self.rotl(b ^ a, a) + self.s[2 * i + 1]) & self.mask
+        return a.to_bytes(u, "little") + b.to_bytes(u, "little")
+
+
+def rc5(key, rounds, w):
+    return RC5(bytes.fromhex(key), rounds, w).encrypt
+
+
+def ecb(algorithm):
+    return lambda block: Cipher(algorithm, modes.ECB()).encryptor().update(block)
+
+
+# Doubling in GF(2^n), 0x1b for 64-bit blocks and 0x87 for 128-bit blocks.
+def dbl(block):
+    n = len(block) * 8
+    v = int.from_bytes(block, "big") << 1
+    if v >> n:
+        v ^= (1 << n) | {64: 0x1B, 128: 0x87}[n]
+    return v.to_bytes(len(block), "big")
+
+
+def cmac(e, bs, msg):
+    k1 = dbl(e(bytes(bs)))
+    k2 = dbl(k1)
+    full = len(msg) // bs if msg and len(msg) % bs == 0 else len(msg) // bs + 1
+    head, last = msg[: (full - 1) * bs], msg[(full - 1) * bs :]
+    if len(last) == bs:
+        last = xor(last, k1)
+    else:
+        last = xor(last + b"\x80" + bytes(bs - len(last) - 1), k2)
+    c = bytes(bs)
+    for i in range(0, len(head), bs):
+        c = e(xor(c, head[i : i + bs]))
+    return e(xor(c, last))
+
+
+# CTR over the whole block as a big-endian counter, as in EAX and SIV.
+def ctr(e, bs, counter, data):
+    c, out = int.from_bytes(counter, "big"), b""
+    for i in range(0, len(data), bs):
+        out += xor(data[i : i + bs], e(c.to_bytes(bs, "big")))
+        c = (c + 1) % (1 << (8 * bs))
+    return out
+
+
+# RFC 2040 CBC with cipher-text stealing, the last two blocks swapped.
+def cbc_cts(e, bs, iv, pt):
+    if len(pt) == bs:
+        return e(xor(pt, iv))
+    c, blocks = iv, []
+    for i in range(0, len(pt), bs):
+        block = pt[i : i + bs]
+        c = e(xor(block + bytes(bs - len(block)), c))
+        blocks.append(c)
+    r = len(pt) - (len(blocks) - 1) * bs
+    return b"".join(blocks[:-2]) + blocks[-1] + blocks[-2][:r]
+
+
+# ECB with cipher-text stealing, the stolen block kept in place.
+def ecb_cts(e, bs, pt):
+    n = -(-len(pt) // bs)
+    r = len(pt) - (n - 1) * bs
+    blocks = [e(pt[i : i + bs]) for i in range(0, (n - 1) * bs, bs)]
+    if r == bs:
+        return b"".join(blocks) + e(pt[-bs:])
+    last = e(pt[-r:] + blocks[-1][r:])
+    return b"".join(blocks[:-1]) + last + blocks[-1][:r]
+
+
+def eax(e, bs, nonce, ad, pt, tag_len):
+    omac = lambda t, m: cmac(e, bs, bytes(bs - 1) + bytes([t]) + m)
+    n, h = omac(0, nonce), omac(1, ad)
+    ct = ctr(e, bs, n, pt)
+    return ct + xor(xor(n, h), omac(2, ct))[:tag_len]
+
+
+# RFC 3610, the nonce takes the `bs - 1 - L` bytes left by the length field.
+def ccm(e, bs, nonce, length_size, ad, pt, tag_len):
+    assert len(nonce) == bs - 1 - length_size
+    flags = (0x40 if ad else 0) | ((tag_len - 2) // 2) << 3 | (length_size - 1)
+    b = bytes([flags]) + nonce + len(pt).to_bytes(length_size, "big")
+    if ad:
+        b += len(ad).to_bytes(2, "big") + ad
+        b += bytes(-(len(b) - bs) % bs)
+    b += pt + bytes(-len(pt) % bs)
+    x = bytes(bs)
+    for i in range(0, len(b), bs):
+        x = e(xor(x, b[i : i + bs]))
+    a = lambda i: bytes([length_size - 1]) + nonce + i.to_bytes(length_size, "big")
+    ct = b"".join(
+        xor(pt[i : i + bs], e(a(i // bs + 1))) for i in range(0, len(pt), bs)
+    )
+    return ct + xor(x, e(a(0)))[:tag_len]
+
+
+def gf128_mul(x, y):
+    z = 0
+    for i in range(127, -1, -1):
+        if (x >> i) & 1:
+            z ^= y
+        y = (y >> 1) ^ (0xE1 << 120) if y & 1 else y >> 1
+    return z
+
+
+def ghash(h, data):
+    y, h = 0, int.from_bytes(h, "big")
+    for i in range(0, len(data), 16):
+        y = gf128_mul(y ^ int.from_bytes(data[i : i + 16], "big"), h)
+    return y.to_bytes(16, "big")
+
+
+# NIST SP 800-38D, for 128-bit blocks only.
+def gcm(e, nonce, ad, pt, tag_len=16):
+    pad = lambda b: b + bytes(-len(b) % 16)
+    inc32 = lambda b: b[:12] + ((int.from_bytes(b[12:], "big") + 1) % 2**32).to_bytes(4, "big")
+    h = e(bytes(16))
+    if len(nonce) == 12:
+        j0 = nonce + b"\0\0\0\1"
+    else:
+        j0 = ghash(h, pad(nonce) + bytes(8) + (len(nonce) * 8).to_bytes(8, "big"))
+    cb, ct = inc32(j0), b""
+    for i in range(0, len(pt), 16):
+        ct += xor(pt[i : i + 16], e(cb))
+        cb = inc32(cb)
+    lengths = (len(ad) * 8).to_bytes(8, "big") + (len(ct) * 8).to_bytes(8, "big")
+    s = ghash(h, pad(ad) + pad(ct) + lengths)
+    return ct + xor(e(j0), s)[:tag_len]
+
+
+# RFC 5297, the two top bits of the 32-bit halves of the last 64 bits of
+# the synthetic iv are cleared in the counter block.
+def siv(e1, e2, bs, ads, pt):
+    d = cmac(e1, bs, bytes(bs))
+    for s in ads:
+        d = xor(dbl(d), cmac(e1, bs, s))
+    if len(pt) >= bs:
+        t = pt[:-bs] + xor(pt[-bs:], d)
+    else:
+        t = xor(dbl(d), pt + b"\x80" + bytes(bs - len(pt) - 1))
+    v = cmac(e1, bs, t)
+    q = bytearray(v)
+    q[bs - 8] &= 0x7F
+    q[bs - 4] &= 0x7F
+    return v + ctr(e2, bs, bytes(q), pt)
+
+
+# RFC 7253 for 128-bit blocks, and the 64-bit row (stretch shift 25, 5
+# bottom bits) of draft-krovetz-ocb-wideblock-00 for 64-bit blocks.
+def ocb(e, bs, nonce, ad, pt, tag_len):
+    n = bs * 8
+    shift, bottom_bits = {8: (25, 5), 16: (8, 6)}[bs]
+    ntz = lambda i: (i & -i).bit_length() - 1
+    l_star = e(bytes(bs))
+    l_dollar = dbl(l_star)
+    l = [dbl(l_dollar)]
+    for _ in range(63):
+        l.append(dbl(l[-1]))
+
+    formatted = ((tag_len * 8 % n) << (n - 7)) | (1 << (len(nonce) * 8))
+    formatted |= int.from_bytes(nonce, "big")
+    bottom = formatted & ((1 << bottom_bits) - 1)
+    ktop = e((formatted >> bottom_bits << bottom_bits).to_bytes(bs, "big"))
+    ktop = int.from_bytes(ktop, "big")
+    stretch = (ktop << n) | (ktop ^ (ktop << shift)) & ((1 << n) - 1)
+    offset = (stretch >> (n - bottom) & ((1 << n) - 1)).to_bytes(bs, "big")
+
+    checksum, ct = bytes(bs), b""
+    m = len(pt) // bs
+    for i in range(1, m + 1):
+        offset = xor(offset, l[ntz(i)])
+        block = pt[(i - 1) * bs : i * bs]
+        ct += xor(offset, e(xor(block, offset)))
+        checksum = xor(checksum, block)
+    rest = pt[m * bs :]
+    if rest:
+        offset = xor(offset, l_star)
+        ct += xor(rest, e(offset))
+        checksum = xor(checksum, rest + b"\x80" + bytes(bs - len(rest) - 1))
+    tag = e(xor(xor(checksum, offset), l_dollar))
+
+    total, offset = bytes(bs), bytes(bs)
+    m = len(ad) // bs
+    for i in range(1, m + 1):
+        offset = xor(offset, l[ntz(i)])
+        total = xor(total, e(xor(ad[(i - 1) * bs : i * bs], offset)))
+    rest = ad[m * bs :]
+    if rest:
+        offset = xor(offset, l_star)
+        total = xor(total, e(xor(rest + b"\x80" + bytes(bs - len(rest) - 1), offset)))
+    return ct + xor(tag, total)[:tag_len]
+
+
+def check_references():
+    h = bytes.fromhex
+    # RC5-32/12/16 of rc5test.c and RC5-64/24/24 of draft-krovetz-rc6-rc5-vectors.
+    assert rc5("915f4619be41b2516355a50110a9ce91", 12, 32)(h("21a5dbee154b8f6d")) == h(
+        "f7c013ac5b2b8952"
+    )
+    key = "000102030405060708090a0b0c0d0e0f1011121314151617"
+    assert rc5(key, 24, 64)(h("000102030405060708090a0b0c0d0e0f")) == h(
+        "a46772820edbce0235abea32ae7178da"
+    )
+
+    aes_key = h("000102030405060708090a0b0c0d0e0f")
+    aes = ecb(algorithms.AES(aes_key))
+    messages = [b"", b"x", bytes(range(16)), bytes(range(40)), bytes(range(255))]
+
+    # First EAX vector of Bellare, Rogaway and Wagner, "The EAX Mode of Operation".
+    e = ecb(algorithms.AES(h("233952dee4d5ed5f9b9c6d6ff80ff478")))
+    assert eax(e, 16, h("62ec67f9c3a4a407fcb2a8c49031a8b3"), h("6bfb914fd07eae6b"), b"", 16) == h(
+        "e037830e8389f27b025a2d6527e79d01"
+    )
+
+    # RFC 3962 AES-CTS, which is the RFC 2040 cipher-text stealing.
+    e = ecb(algorithms.AES(h("636869636b656e207465726979616b69")))
+    assert cbc_cts(e, 16, bytes(16), h("4920776f756c64206c696b652074686520")) == h(
+        "c6353568f2bf8cb4d8a580362da7ff7f97"
+    )
+
+    for ad in messages[:4]:
+        for pt in messages:
+            assert ccm(aes, 16, bytes(13), 2, ad, pt, 8) == AESCCM(aes_key, 8).encrypt(
+                bytes(13), pt, ad or None
+            )
+            for nonce in [bytes(12), bytes(8), bytes(range(60))]:
+                assert gcm(aes, nonce, ad, pt) == AESGCM(aes_key).encrypt(nonce, pt, ad)
+            assert ocb(aes, 16, bytes(range(12)), ad, pt, 16) == AESOCB3(aes_key).encrypt(
+                bytes(range(12)), pt, ad
+            )
+            siv_key = aes_key + bytes(range(16, 32))
+            e2 = ecb(algorithms.AES(siv_key[16:]))
+            assert siv(aes, e2, 16, [ad], pt) == AESSIV(siv_key).encrypt(pt, [ad])
+
+
+def show(name, *fields):
+    print(f"  {name}: " + ", ".join(f.hex() if isinstance(f, bytes) else str(f) for f in fields))
+
+
+def main():
+    check_references()
+    h = bytes.fromhex
+    key = "000102030405060708090a0b0c0d0e0f"
+    rc5_32, rc5_64 = rc5(key, 12, 32), rc5(key, 16, 64)
+    sample, partial = bytes(range(40)), bytes(range(35))
+
+    print("cts.rs rfc_2040_cts_vectors, RC5-32/12/8, IV 0102030405060708")
+    e = rc5("0102030405060708", 12, 32)
+    for pt in [
+        "1020304050607080",
+        "1020304050607080ff",
+        "1020304050607080ffffffffffff",
+        "1020304050607080ffffffffffffffff",
+        "1020304050607080ffffffffffffffff11223344556677",
+    ]:
+        show(pt, cbc_cts(e, 8, h("0102030405060708"), h(pt)), ecb_cts(e, 8, h(pt)))
+
+    print("eax.rs rc5_32_eax_vectors, RC5-32/12/16, 8 bytes tags")
+    for nonce, ad, pt in [
+        ("0001020304050607", "", ""),
+        ("0001020304050607", "0a0b0c", "48656c6c6f"),
+        ("f0f1f2f3f4f5f6f7", "0001020304050607", "000102030405060708090a0b0c0d0e0f1011121314"),
+        ("ffffffffffffffff", "", "00" * 24),
+    ]:
+        show(nonce, eax(rc5_32, 8, h(nonce), h(ad), h(pt), 8))
+
+    print("ccm.rs rc5_32_ccm_vectors, RC5-32/12/16")
+    for nonce, length_size, tag_len, ad, pt in [
+        ("0001020304", 2, 8, "", ""),
+        ("0001020304", 2, 8, "0a0b0c", "48656c6c6f"),
+        ("a0a1a2a3", 3, 6, "0001020304050607", "000102030405060708090a0b0c0d0e0f1011121314"),
+        ("10111213", 3, 4, "", "00" * 24),
+    ]:
+        show(nonce, ccm(rc5_32, 8, h(nonce), length_size, h(ad), h(pt), tag_len))
+
+    print("gcm.rs rc5_64_gcm_vectors, RC5-64/16/16, 16 bytes tags")
+    for nonce, ad, pt in [
+        ("000102030405060708090a0b", "", ""),
+        ("000102030405060708090a0b", "0a0b0c", "48656c6c6f"),
+        (
+            "cafebabefacedbaddecaf888",
+            "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72"
+            "1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+        ),
+        ("cafebabefacedbad", "feedfacedeadbeef", "00" * 33),
+        (
+            "9313225df88406e555909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728"
+            "c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39b",
+            "",
+            "000102030405060708090a0b0c0d0e0f",
+        ),
+        ("ff" * 16, "", "00" * 20),
+    ]:
+        show(nonce[:24], gcm(rc5_64, h(nonce), h(ad), h(pt)))
+    print("gcm.rs rc5_64_gmac_vector")
+    show("gmac", gcm(rc5_64, h("000102030405060708090a0b"), b"GMAC authenticated data", b""))
+
+    print("siv.rs rc5_siv_vectors, RC5-32/12 and RC5-64/16, K1 000102..0f, K2 101112..1f")
+    k2 = "101112131415161718191a1b1c1d1e1f"
+    long_ad = [
+        h("101112131415161718191a1b1c1d1e1f2021222324252627"),
+        b"",
+        h("09f911029d74e35bd84156c5635688c0"),
+    ]
+    long_pt = b"this is some plaintext to encrypt using SIV-AES"
+    for ads, pt in [([], b""), ([], b"Hello"), ([h("0a0b0c")], b"Hello"), (long_ad, long_pt)]:
+        show("rc5-32", siv(rc5_32, rc5(k2, 12, 32), 8, ads, pt))
+        show("rc5-64", siv(rc5_64, rc5(k2, 16, 64), 16, ads, pt))
+
+    print("ocb.rs rc5_ocb_vectors, RC5-32/12/16")
+    for nonce, tag_len, ad, pt in [
+        ("00010203040506", 8, b"", b""),
+        ("00010203040506", 8, bytes(range(8)), bytes(range(8))),
+        ("0a0b0c", 8, sample, sample),
+        ("ff", 4, b"", partial),
+    ]:
+        show(nonce, ocb(rc5_32, 8, h(nonce), ad, pt, tag_len))
+    print("ocb.rs rc5_ocb_vectors, RC5-64/16/16")
+    for nonce, tag_len, ad, pt in [
+        ("bbaa99887766554433221100", 16, b"", b""),
+        ("bbaa99887766554433221101", 16, bytes(range(8)), bytes(range(8))),
+        ("bbaa9988776655443322110d", 12, sample, sample),
+        ("000102030405060708090a0b0c0d0e", 16, b"", partial),
+    ]:
+        show(nonce, ocb(rc5_64, 16, h(nonce), ad, pt, tag_len))
+    print("ocb.rs rc5_ocb_long_message_tags, tags of the 1000 bytes message i mod 251")
+    message = bytes(i % 251 for i in range(1000))
+    show("rc5-32", ocb(rc5_32, 8, h("00010203040506"), message, message, 8)[1000:])
+    show("rc5-64", ocb(rc5_64, 16, h("bbaa99887766554433221100"), message, message, 16)[1000:])
+
+    print("adapter.rs blowfish_ocb_vectors, Blowfish with the 16 bytes key 000102..0f")
+    blowfish = ecb(Blowfish(h(key)))
+    for nonce, tag_len, ad, pt in [
+        ("00010203040506", 8, b"", b""),
+        ("00010203040506", 8, bytes(range(8)), bytes(range(8))),
+        ("0a0b0c", 8, sample, sample),
+        ("ff", 4, b"", partial),
+    ]:
+        show(nonce, ocb(blowfish, 8, h(nonce), ad, pt, tag_len))
+
+
+if __name__ == "__main__":
+    main()
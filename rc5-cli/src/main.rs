@@ -9,6 +9,11 @@ macro_rules! get_cipher {
     ($opts:expr) => {{
         let cipher;
 
+        #[cfg(feature = "word-8")]
+        {
+            cipher = rc5_cipher::<u8>($opts.secret.as_str(), $opts.rounds)?;
+        }
+
         #[cfg(feature = "word-16")]
         {
             cipher = rc5_cipher::<u16>($opts.secret.as_str(), $opts.rounds)?;
@@ -38,7 +43,7 @@ fn main() -> anyhow::Result<()> {
 
     let cipher = get_cipher!(options);
 
-    let mut processed = match options.mode {
+    let processed = match options.mode {
         Mode::ECB => match options.action {
             opts::Action::Encrypt => cipher.encrypt(&text, OperationMode::ECB)?,
             opts::Action::Decrypt => cipher.decrypt(&text, OperationMode::ECB)?,
@@ -78,7 +83,7 @@ fn main() -> anyhow::Result<()> {
 
     let dest = options.dest_path();
     let mut f = std::fs::File::create(dest)?;
-    f.write_all(&mut processed)?;
+    f.write_all(&processed)?;
     f.flush()?;
 
     Ok(())
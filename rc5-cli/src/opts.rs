@@ -54,6 +54,7 @@ impl Opts {
         path
     }
 }
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Subcommand)]
 pub enum Mode {
     /// Electronic-Code-Book operation mode